@@ -103,6 +103,106 @@ fn bench_derive_bip0032(group: &mut BenchmarkGroup<'_>) {
     bench_impl::<Secp256k1FfiBackend>(group, "bip0032 (secp256k1)");
 }
 
+// BIP-0086 receive address path, on the hot path of wallets and indexers.
+const DEEP_PATH: &str = "m/86'/0'/0'/0/1000";
+
+fn bench_derive_deep_bitcoin(group: &mut BenchmarkGroup<'_>) {
+    use bitcoin::{
+        Network,
+        bip32::{DerivationPath, Xpriv},
+        secp256k1::Secp256k1,
+    };
+
+    group.bench_function("bitcoin (secp256k1)", |b| {
+        let secp = Secp256k1::new();
+        let path = DEEP_PATH.parse::<DerivationPath>().unwrap();
+
+        b.iter_batched(
+            || {
+                let seed = random_seed();
+                Xpriv::new_master(Network::Bitcoin, &seed).unwrap()
+            },
+            |xprv| {
+                let key = xprv.derive_priv(black_box(&secp), black_box(&path)).unwrap();
+                black_box(key);
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_derive_deep_bip0032(group: &mut BenchmarkGroup<'_>) {
+    use bip0032::{DerivationPath, ExtendedPrivateKey, curve::secp256k1::*};
+
+    fn bench_impl<B: Secp256k1Backend>(group: &mut BenchmarkGroup<'_>, name: &str) {
+        // step-by-step derivation, which computes every intermediate parent fingerprint
+        group.bench_function(format!("{name} derive_child loop"), |b| {
+            let path = DEEP_PATH.parse::<DerivationPath>().unwrap();
+
+            b.iter_batched(
+                || {
+                    let seed = random_seed();
+                    ExtendedPrivateKey::<Secp256k1Curve<B>>::new(&seed).unwrap()
+                },
+                |master| {
+                    let mut key = master;
+                    for child in black_box(&path) {
+                        key = key.derive_child(*child).unwrap();
+                    }
+                    black_box(key);
+                },
+                BatchSize::SmallInput,
+            )
+        });
+
+        group.bench_function(format!("{name} derive_path"), |b| {
+            let path = DEEP_PATH.parse::<DerivationPath>().unwrap();
+
+            b.iter_batched(
+                || {
+                    let seed = random_seed();
+                    ExtendedPrivateKey::<Secp256k1Curve<B>>::new(&seed).unwrap()
+                },
+                |master| {
+                    let key = master.derive_path(black_box(&path)).unwrap();
+                    black_box(key);
+                },
+                BatchSize::SmallInput,
+            )
+        });
+
+        group.bench_function(format!("{name} xpub derive_path"), |b| {
+            let account = "m/86'/0'/0'".parse::<DerivationPath>().unwrap();
+            let path = "m/0/1000".parse::<DerivationPath>().unwrap();
+
+            b.iter_batched(
+                || {
+                    let seed = random_seed();
+                    let master = ExtendedPrivateKey::<Secp256k1Curve<B>>::new(&seed).unwrap();
+                    master.derive_path(&account).unwrap().public_key()
+                },
+                |xpub| {
+                    let key = xpub.derive_path(black_box(&path)).unwrap();
+                    black_box(key);
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    bench_impl::<K256Backend>(group, "bip0032 (k256)");
+    bench_impl::<Secp256k1FfiBackend>(group, "bip0032 (secp256k1)");
+}
+
+fn bench_derive_deep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("derive_deep");
+
+    bench_derive_deep_bitcoin(&mut group);
+    bench_derive_deep_bip0032(&mut group);
+
+    group.finish();
+}
+
 fn bench_derive(c: &mut Criterion) {
     let mut group = c.benchmark_group("derive");
 
@@ -117,6 +217,6 @@ fn bench_derive(c: &mut Criterion) {
 criterion_group!(
     name = benches;
     config = Criterion::default();
-    targets = bench_derive
+    targets = bench_derive, bench_derive_deep
 );
 criterion_main!(benches);
@@ -170,6 +170,10 @@ fuzz_target!(|input: Input<'_>| {
 
     let payload = encoded.parse::<ExtendedKeyPayload>().unwrap();
     let payload_version = payload.version();
+    let payload_bytes = payload.to_bytes();
+    assert_eq!(Some(payload_bytes.to_vec()), decode_payload(&encoded));
+    let decoded = ExtendedKeyPayload::from_bytes(&payload_bytes).unwrap();
+    assert_eq!(decoded.to_string(), encoded);
     let encoded2 = payload.to_string();
     let payload2 = encoded2.parse::<ExtendedKeyPayload>().unwrap();
     assert_eq!(payload2.version(), payload_version);
@@ -193,6 +197,7 @@ fuzz_target!(|input: Input<'_>| {
     if input.mutate_payload {
         if let Some(mut payload_bytes) = decode_payload(&encoded) {
             mutate_payload_bytes(&mut payload_bytes, input.mutate_bytes, ops);
+            let _ = ExtendedKeyPayload::from_bytes(&payload_bytes);
             if let Some(mutated) = encode_payload(&payload_bytes) {
                 let _ = mutated.parse::<ExtendedKeyPayload>();
             }
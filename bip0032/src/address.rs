@@ -0,0 +1,353 @@
+//! Bitcoin addresses of secp256k1 extended public keys.
+//!
+//! Supported address types:
+//!
+//! - P2PKH (Base58Check, [BIP-0044](https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki))
+//! - P2SH-P2WPKH (Base58Check, [BIP-0049](https://github.com/bitcoin/bips/blob/master/bip-0049.mediawiki))
+//! - P2WPKH (bech32, [BIP-0084](https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki))
+//! - P2TR (bech32m, key path only with the [BIP-0086](https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki)
+//!   tweak)
+//! - P2SH, P2WSH and P2SH-P2WSH of a script, e.g. a [multisig](crate::multisig) script
+//!
+//! ```rust
+//! use bip0032::{
+//!     DerivationPath, ExtendedPublicKey,
+//!     address::{AddressType, Network},
+//!     curve::secp256k1::*,
+//! };
+//!
+//! let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+//! let xpub: ExtendedPublicKey<Secp256k1Curve<K256Backend>> = xpub.parse().unwrap();
+//! let key = xpub.derive_path(&"m/0/7".parse::<DerivationPath>().unwrap()).unwrap();
+//! let address = key.to_address(Network::Bitcoin, AddressType::P2wpkh).unwrap();
+//! assert!(address.to_string().starts_with("bc1q"));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+use bech32::{Fe32, Hrp, segwit};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    curve::{CurvePublicKey, TweakableKey, secp256k1::*},
+    error::{Error, ErrorKind, Result},
+    path::CoinType,
+    taproot::tweak_public_key,
+    xkey::{ExtendedPublicKey, KnownVersion, NetworkKind, ScriptType, key_identifier},
+};
+
+/// Maximum size of a P2SH redeem script, the consensus limit of a pushed script element.
+const MAX_REDEEM_SCRIPT_SIZE: usize = 520;
+
+/// Bitcoin networks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Network {
+    /// Bitcoin mainnet.
+    Bitcoin,
+    /// Bitcoin testnet.
+    Testnet,
+    /// Bitcoin signet.
+    Signet,
+    /// Bitcoin regtest.
+    Regtest,
+}
+
+impl Network {
+    /// Returns the network of a known Bitcoin version: mainnet or testnet.
+    ///
+    /// Signet and regtest keys share the testnet versions. Versions of other coins have no
+    /// Bitcoin network.
+    pub fn from_known_version(version: KnownVersion) -> Option<Self> {
+        let info = version.info();
+        if info.coin() != CoinType::BITCOIN {
+            return None;
+        }
+        Some(match info.network() {
+            NetworkKind::Mainnet => Self::Bitcoin,
+            NetworkKind::Testnet => Self::Testnet,
+        })
+    }
+
+    /// Returns the Base58Check version byte of P2PKH addresses.
+    pub const fn p2pkh_prefix(self) -> u8 {
+        match self {
+            Self::Bitcoin => 0x00,
+            Self::Testnet | Self::Signet | Self::Regtest => 0x6F,
+        }
+    }
+
+    /// Returns the Base58Check version byte of P2SH addresses.
+    pub const fn p2sh_prefix(self) -> u8 {
+        match self {
+            Self::Bitcoin => 0x05,
+            Self::Testnet | Self::Signet | Self::Regtest => 0xC4,
+        }
+    }
+
+    /// Returns the Base58Check version byte of WIF private keys.
+    pub const fn wif_prefix(self) -> u8 {
+        match self {
+            Self::Bitcoin => 0x80,
+            Self::Testnet | Self::Signet | Self::Regtest => 0xEF,
+        }
+    }
+
+    /// Returns the human-readable part of segwit addresses.
+    pub const fn hrp(self) -> &'static str {
+        match self {
+            Self::Bitcoin => "bc",
+            Self::Testnet | Self::Signet => "tb",
+            Self::Regtest => "bcrt",
+        }
+    }
+
+    fn bech32_hrp(self) -> Hrp {
+        match self {
+            Self::Bitcoin => bech32::hrp::BC,
+            Self::Testnet | Self::Signet => bech32::hrp::TB,
+            Self::Regtest => bech32::hrp::BCRT,
+        }
+    }
+}
+
+/// Address types.
+///
+/// Single-key types pay to a public key, see [`Address::from_public_key`]. Script types pay to a
+/// script, see [`Address::from_script`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum AddressType {
+    /// Pay to public key hash.
+    P2pkh,
+    /// Pay to witness public key hash, nested in pay to script hash.
+    P2shP2wpkh,
+    /// Pay to witness public key hash.
+    P2wpkh,
+    /// Pay to taproot, with the BIP-0086 tweak of the key as output key.
+    P2tr,
+    /// Pay to script hash.
+    P2sh,
+    /// Pay to witness script hash.
+    P2wsh,
+    /// Pay to witness script hash, nested in pay to script hash.
+    P2shP2wsh,
+}
+
+impl AddressType {
+    /// Returns whether this address type pays to a script.
+    pub const fn is_script(self) -> bool {
+        matches!(self, Self::P2sh | Self::P2wsh | Self::P2shP2wsh)
+    }
+
+    /// Returns the address type intended by a known version, if it is a single-key version.
+    ///
+    /// `xpub`/`tpub` map to P2PKH, `ypub`/`upub` to P2SH-P2WPKH and `zpub`/`vpub` to P2WPKH.
+    /// Multi-signature versions have no single-key address type. There is no version for P2TR,
+    /// which uses `xpub`/`tpub`.
+    pub fn from_known_version(version: KnownVersion) -> Option<Self> {
+        match version.info().script_type() {
+            ScriptType::P2pkh => Some(Self::P2pkh),
+            ScriptType::P2shP2wpkh => Some(Self::P2shP2wpkh),
+            ScriptType::P2wpkh => Some(Self::P2wpkh),
+            ScriptType::P2shP2wsh | ScriptType::P2wsh => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+enum Payload {
+    PubkeyHash([u8; 20]),
+    ScriptHash([u8; 20]),
+    WitnessV0([u8; 20]),
+    WitnessV0Script([u8; 32]),
+    Taproot([u8; 32]),
+}
+
+/// A Bitcoin address.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Address {
+    network: Network,
+    address_type: AddressType,
+    payload: Payload,
+}
+
+impl Address {
+    /// Creates the address of a compressed secp256k1 public key.
+    pub fn from_public_key<K>(
+        public_key: &K,
+        network: Network,
+        address_type: AddressType,
+    ) -> Result<Self>
+    where
+        K: CurvePublicKey<Bytes = [u8; 33]> + TweakableKey,
+    {
+        let public_key_bytes = public_key.to_bytes();
+        let payload = match address_type {
+            AddressType::P2pkh => Payload::PubkeyHash(key_identifier(&public_key_bytes)),
+            AddressType::P2shP2wpkh => {
+                let redeem_script = p2wpkh_script(&key_identifier(&public_key_bytes));
+                Payload::ScriptHash(key_identifier(&redeem_script))
+            },
+            AddressType::P2wpkh => Payload::WitnessV0(key_identifier(&public_key_bytes)),
+            AddressType::P2tr => {
+                let (output_key, _) = tweak_public_key(public_key, None)?;
+                Payload::Taproot(output_key.to_bytes())
+            },
+            AddressType::P2sh | AddressType::P2wsh | AddressType::P2shP2wsh => {
+                return Err(Error::new(ErrorKind::InvalidScript, "address type requires a script")
+                    .with_context("address_type", format_args!("{address_type:?}")));
+            },
+        };
+
+        Ok(Self { network, address_type, payload })
+    }
+
+    /// Creates the address of a script: the redeem script of P2SH or the witness script of P2WSH
+    /// and P2SH-P2WSH.
+    pub fn from_script(script: &[u8], network: Network, address_type: AddressType) -> Result<Self> {
+        let payload = match address_type {
+            AddressType::P2sh => {
+                if script.len() > MAX_REDEEM_SCRIPT_SIZE {
+                    return Err(Error::new(ErrorKind::InvalidScript, "redeem script too large")
+                        .with_context("script_len", script.len())
+                        .with_context("max_len", MAX_REDEEM_SCRIPT_SIZE));
+                }
+                Payload::ScriptHash(key_identifier(script))
+            },
+            AddressType::P2wsh => Payload::WitnessV0Script(Sha256::digest(script).into()),
+            AddressType::P2shP2wsh => {
+                let redeem_script = p2wsh_script(&Sha256::digest(script).into());
+                Payload::ScriptHash(key_identifier(&redeem_script))
+            },
+            AddressType::P2pkh
+            | AddressType::P2shP2wpkh
+            | AddressType::P2wpkh
+            | AddressType::P2tr => {
+                return Err(Error::new(
+                    ErrorKind::InvalidScript,
+                    "address type requires a public key",
+                )
+                .with_context("address_type", format_args!("{address_type:?}")));
+            },
+        };
+
+        Ok(Self { network, address_type, payload })
+    }
+
+    /// Returns the network.
+    pub const fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Returns the address type.
+    pub const fn address_type(&self) -> AddressType {
+        self.address_type
+    }
+
+    /// Returns the output script (`scriptPubKey`) paying to this address.
+    pub fn script_pubkey(&self) -> Vec<u8> {
+        match &self.payload {
+            // OP_DUP OP_HASH160 <20> OP_EQUALVERIFY OP_CHECKSIG
+            Payload::PubkeyHash(hash) => {
+                let mut script = Vec::with_capacity(25);
+                script.extend_from_slice(&[0x76, 0xA9, 0x14]);
+                script.extend_from_slice(hash);
+                script.extend_from_slice(&[0x88, 0xAC]);
+                script
+            },
+            // OP_HASH160 <20> OP_EQUAL
+            Payload::ScriptHash(hash) => {
+                let mut script = Vec::with_capacity(23);
+                script.extend_from_slice(&[0xA9, 0x14]);
+                script.extend_from_slice(hash);
+                script.push(0x87);
+                script
+            },
+            // OP_0 <20>
+            Payload::WitnessV0(hash) => p2wpkh_script(hash).to_vec(),
+            // OP_0 <32>
+            Payload::WitnessV0Script(hash) => p2wsh_script(hash).to_vec(),
+            // OP_1 <32>
+            Payload::Taproot(output_key) => {
+                let mut script = Vec::with_capacity(34);
+                script.extend_from_slice(&[0x51, 0x20]);
+                script.extend_from_slice(output_key);
+                script
+            },
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (prefix, hash) = match &self.payload {
+            Payload::PubkeyHash(hash) => (self.network.p2pkh_prefix(), hash),
+            Payload::ScriptHash(hash) => (self.network.p2sh_prefix(), hash),
+            Payload::WitnessV0(program) => {
+                return write_segwit(f, self.network, segwit::VERSION_0, program);
+            },
+            Payload::WitnessV0Script(program) => {
+                return write_segwit(f, self.network, segwit::VERSION_0, program);
+            },
+            Payload::Taproot(program) => {
+                return write_segwit(f, self.network, segwit::VERSION_1, program);
+            },
+        };
+
+        let mut data = [0u8; 21];
+        data[0] = prefix;
+        data[1..].copy_from_slice(hash);
+        f.write_str(&bs58::encode(&data[..]).with_check().into_string())
+    }
+}
+
+fn write_segwit(
+    f: &mut fmt::Formatter<'_>,
+    network: Network,
+    version: Fe32,
+    program: &[u8],
+) -> fmt::Result {
+    // the programs of P2WPKH, P2WSH and P2TR outputs always have a valid length
+    segwit::encode_lower_to_fmt_unchecked(f, network.bech32_hrp(), version, program)
+}
+
+/// `OP_0 <20-byte key hash>`, the P2WPKH output script and the P2SH-P2WPKH redeem script.
+fn p2wpkh_script(hash: &[u8; 20]) -> [u8; 22] {
+    let mut script = [0u8; 22];
+    script[0] = 0x00;
+    script[1] = 0x14;
+    script[2..].copy_from_slice(hash);
+    script
+}
+
+/// `OP_0 <32-byte script hash>`, the P2WSH output script and the P2SH-P2WSH redeem script.
+pub(crate) fn p2wsh_script(hash: &[u8; 32]) -> [u8; 34] {
+    let mut script = [0u8; 34];
+    script[0] = 0x00;
+    script[1] = 0x20;
+    script[2..].copy_from_slice(hash);
+    script
+}
+
+// Addresses
+impl<B: Secp256k1Backend> ExtendedPublicKey<Secp256k1Curve<B>> {
+    /// Returns the address of this key for the given network and address type.
+    pub fn to_address(&self, network: Network, address_type: AddressType) -> Result<Address> {
+        Address::from_public_key(&self.public_key, network, address_type)
+    }
+
+    /// Returns the address of this key with the network and address type intended by a known
+    /// version, see [`Network::from_known_version`] and [`AddressType::from_known_version`].
+    pub fn to_default_address(&self, version: KnownVersion) -> Result<Address> {
+        let address_type = AddressType::from_known_version(version).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidVersion, "version has no single-key address type")
+                .with_context("version", version.into_version())
+        })?;
+        let network = Network::from_known_version(version).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidVersion, "version is not a Bitcoin version")
+                .with_context("version", version.into_version())
+        })?;
+        self.to_address(network, address_type)
+    }
+}
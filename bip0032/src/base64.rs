@@ -0,0 +1,52 @@
+//! Standard Base64 (RFC 4648, with padding), as used by BIP-0137 message signatures and BIP-0085
+//! passwords.
+
+use core::fmt;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub(crate) fn encode_to(out: &mut impl fmt::Write, data: &[u8]) -> fmt::Result {
+    for chunk in data.chunks(3) {
+        let mut block = [0u8; 3];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from_be_bytes([0, block[0], block[1], block[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (n >> (18 - 6 * i)) & 0x3F;
+                out.write_char(ALPHABET[index as usize] as char)?;
+            } else {
+                out.write_char('=')?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn decode<const N: usize>(encoded: &str) -> Option<[u8; N]> {
+    let encoded = encoded.as_bytes();
+    if encoded.len() != N.div_ceil(3) * 4 {
+        return None;
+    }
+    let padding = N.div_ceil(3) * 3 - N;
+    let mut out = [0u8; N];
+    for (i, chunk) in encoded.chunks(4).enumerate() {
+        let mut n = 0u32;
+        for (j, &c) in chunk.iter().enumerate() {
+            let last = i == encoded.len() / 4 - 1;
+            let value = match c {
+                b'=' if last && j >= 4 - padding => 0,
+                _ => ALPHABET.iter().position(|&a| a == c)? as u32,
+            };
+            n = (n << 6) | value;
+        }
+        for (j, byte) in n.to_be_bytes()[1..].iter().enumerate() {
+            if let Some(out) = out.get_mut(i * 3 + j) {
+                *out = *byte;
+            } else if *byte != 0 {
+                // non-canonical trailing bits
+                return None;
+            }
+        }
+    }
+    Some(out)
+}
@@ -0,0 +1,585 @@
+//! Bitcoin Secure Multisig Setup ([BIP-0129](https://github.com/bitcoin/bips/blob/master/bip-0129.mediawiki))
+//! records.
+//!
+//! The coordinator hands a [`Token`] to each signer. Each signer answers with a [`KeyRecord`]: its
+//! account key with origin and a description, signed in the Bitcoin message format by that key.
+//! The coordinator verifies the key records and answers with a [`DescriptorRecord`]: the
+//! descriptor template of the multisig wallet and its first address, which each signer checks
+//! against its own derivation.
+//!
+//! Records are handled in their plaintext form. With a token other than
+//! [`Token::NoEncryption`], the records are encrypted for transport by the caller.
+//!
+//! ```rust
+//! use bip0032::{
+//!     Bip48Path, Bip48ScriptType, CoinType, DerivationPath, ExtendedPrivateKey, KnownVersion,
+//!     address::Network,
+//!     bsms::{DescriptorRecord, KeyRecord, Token},
+//!     curve::secp256k1::*,
+//! };
+//!
+//! type Curve = Secp256k1Curve<K256Backend>;
+//!
+//! // the coordinator creates a token from 8 random bytes
+//! let token = Token::Bits64([0xa5; 8]);
+//!
+//! let account = Bip48Path::new(CoinType::BITCOIN, 0, Bip48ScriptType::P2wsh);
+//! let path = DerivationPath::from(account.account_path().unwrap());
+//! let records = [[0x01; 32], [0x02; 32], [0x03; 32]]
+//!     .iter()
+//!     .enumerate()
+//!     .map(|(i, seed)| {
+//!         let key = ExtendedPrivateKey::<Curve>::new(seed).unwrap().derive_path(&path).unwrap();
+//!         let description = format!("Signer {i}");
+//!         KeyRecord::sign(token, &key, KnownVersion::Xpub.into_version(), &description).unwrap()
+//!     })
+//!     .collect::<Vec<_>>();
+//!
+//! // the coordinator verifies the key records and builds the descriptor record
+//! let records = records
+//!     .iter()
+//!     .map(|record| record.to_string().parse::<KeyRecord<K256Backend>>().unwrap())
+//!     .collect::<Vec<_>>();
+//! let descriptor = DescriptorRecord::from_key_records(
+//!     &token,
+//!     2,
+//!     &records,
+//!     account.script_type().address_type(),
+//!     Network::Bitcoin,
+//! )
+//! .unwrap();
+//!
+//! // each signer checks the first address and that its key is part of the wallet
+//! let descriptor = descriptor.to_string().parse::<DescriptorRecord<K256Backend>>().unwrap();
+//! descriptor.verify(Network::Bitcoin).unwrap();
+//! assert!(descriptor.contains_key(records[0].key().key()));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    address::{Address, AddressType, Network},
+    base64,
+    curve::{RecoverableEcdsa, Signer, Verifier, secp256k1::*},
+    descriptor::Descriptor,
+    error::{Error, ErrorKind, Result},
+    multisig::{MultisigPolicy, MultisigScript},
+    path::{Change, ChildNumber},
+    xkey::{ExtendedPrivateKey, ExtendedPublicKey, OriginKey, Version},
+};
+
+/// The version line of BSMS records.
+pub const BSMS_VERSION: &str = "BSMS 1.0";
+/// The path restrictions line of descriptor records without path restrictions.
+pub const NO_PATH_RESTRICTIONS: &str = "No path restrictions";
+/// Maximum length of the description of a key record.
+pub const MAX_DESCRIPTION_LENGTH: usize = 80;
+
+/// Prefix of messages signed in the Bitcoin message format, with its length byte.
+const MESSAGE_MAGIC: &[u8] = b"\x18Bitcoin Signed Message:\n";
+/// Header byte of message signatures by compressed public keys, before the recovery id.
+const COMPRESSED_HEADER: u8 = 27 + 4;
+
+/// The token of a setup session, shared by the coordinator with every signer.
+///
+/// Tokens are created from random bytes of a cryptographically secure source.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Token {
+    /// No encryption of the records (`00`).
+    NoEncryption,
+    /// A 64-bit token.
+    Bits64([u8; 8]),
+    /// A 128-bit token.
+    Bits128([u8; 16]),
+}
+
+impl Token {
+    /// Returns the token bytes, empty for [`Token::NoEncryption`].
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::NoEncryption => &[],
+            Self::Bits64(bytes) => bytes,
+            Self::Bits128(bytes) => bytes,
+        }
+    }
+
+    /// Returns whether records of this token are encrypted for transport.
+    pub const fn is_encrypted(&self) -> bool {
+        !matches!(self, Self::NoEncryption)
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_encrypted() {
+            return f.write_str("00");
+        }
+        self.as_bytes().iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl FromStr for Token {
+    type Err = Error;
+
+    fn from_str(token: &str) -> Result<Self> {
+        fn decode<const N: usize>(token: &str) -> Result<[u8; N]> {
+            let mut bytes = [0u8; N];
+            for (byte, pair) in bytes.iter_mut().zip(token.as_bytes().chunks_exact(2)) {
+                *byte = core::str::from_utf8(pair)
+                    .ok()
+                    .filter(|pair| pair.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidRecord, "invalid token")
+                            .with_context("token", token)
+                    })?;
+            }
+            Ok(bytes)
+        }
+
+        match token.len() {
+            2 if token == "00" => Ok(Self::NoEncryption),
+            16 => decode(token).map(Self::Bits64),
+            32 => decode(token).map(Self::Bits128),
+            _ => Err(Error::new(ErrorKind::InvalidRecord, "invalid token length")
+                .with_context("token", token)),
+        }
+    }
+}
+
+/// A key record of a signer (round 1): the signer's key with origin and a description, signed by
+/// that key.
+pub struct KeyRecord<B: Secp256k1Backend> {
+    token: Token,
+    key: OriginKey<Secp256k1Curve<B>>,
+    description: String,
+    signature: [u8; 65],
+}
+
+impl<B: Secp256k1Backend> Clone for KeyRecord<B> {
+    fn clone(&self) -> Self {
+        Self {
+            token: self.token,
+            key: self.key.clone(),
+            description: self.description.clone(),
+            signature: self.signature,
+        }
+    }
+}
+
+impl<B: Secp256k1Backend> fmt::Debug for KeyRecord<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyRecord")
+            .field("token", &self.token)
+            .field("key", self.key.key())
+            .field("description", &self.description)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: Secp256k1Backend> KeyRecord<B> {
+    /// Creates the key record of an account key, signed by that key.
+    ///
+    /// The key must carry its origin, e.g. by deriving it from the master key. The version bytes
+    /// are used when formatting the public key.
+    pub fn sign(
+        token: Token,
+        key: &ExtendedPrivateKey<Secp256k1Curve<B>>,
+        version: Version,
+        description: &str,
+    ) -> Result<Self>
+    where
+        B::PrivateKey: Signer<RecoverableEcdsa>,
+    {
+        check_description(description)?;
+        let origin = key.origin().ok_or_else(|| {
+            Error::new(ErrorKind::InvalidOrigin, "key record requires a key origin")
+        })?;
+        let public_key = OriginKey::new(origin, key.public_key(), version)?;
+
+        let message = key_record_message(&token, &public_key, description);
+        let signature = key.sign::<RecoverableEcdsa>(&message_digest(&message))?;
+        let mut encoded = [0u8; 65];
+        encoded[0] = COMPRESSED_HEADER + signature[64];
+        encoded[1..].copy_from_slice(&signature[..64]);
+
+        Ok(Self {
+            token,
+            key: public_key,
+            description: description.to_string(),
+            signature: encoded,
+        })
+    }
+
+    /// Returns the token.
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// Returns the signer's key with its origin.
+    pub fn key(&self) -> &OriginKey<Secp256k1Curve<B>> {
+        &self.key
+    }
+
+    /// Returns the description.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns the signature in the Bitcoin message format: header byte and compact `r || s`.
+    pub const fn signature(&self) -> &[u8; 65] {
+        &self.signature
+    }
+
+    /// Verifies the signature of this record by its key.
+    pub fn verify(&self) -> Result<()>
+    where
+        B::PublicKey: Verifier<RecoverableEcdsa>,
+    {
+        let header = self.signature[0];
+        if !(COMPRESSED_HEADER..COMPRESSED_HEADER + 4).contains(&header) {
+            return Err(Error::new(
+                ErrorKind::InvalidSignature,
+                "invalid message signature header",
+            )
+            .with_context("header", header));
+        }
+
+        let mut signature = [0u8; 65];
+        signature[..64].copy_from_slice(&self.signature[1..]);
+        signature[64] = header - COMPRESSED_HEADER;
+        let message = key_record_message(&self.token, &self.key, &self.description);
+        self.key.key().verify::<RecoverableEcdsa>(&message_digest(&message), &signature)
+    }
+}
+
+impl<B: Secp256k1Backend> fmt::Display for KeyRecord<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&key_record_message(&self.token, &self.key, &self.description))?;
+        f.write_str("\n")?;
+        base64::encode_to(f, &self.signature)
+    }
+}
+
+impl<B: Secp256k1Backend> FromStr for KeyRecord<B> {
+    type Err = Error;
+
+    /// Parses a key record, without verifying its signature.
+    fn from_str(record: &str) -> Result<Self> {
+        let [token, key, description, signature] = record_lines(record)?;
+        let token = token.parse::<Token>()?;
+        let key = key.parse::<OriginKey<Secp256k1Curve<B>>>()?;
+        check_description(description)?;
+        let signature = base64::decode::<65>(signature).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidRecord, "invalid signature encoding")
+                .with_context("signature", signature)
+        })?;
+
+        Ok(Self { token, key, description: description.to_string(), signature })
+    }
+}
+
+/// A descriptor record of the coordinator (round 2): the descriptor template of a sorted multisig
+/// wallet, its path restrictions and its first address.
+///
+/// The keys of the template end with `/**`, i.e. the external and internal chains `/<0;1>/*`.
+pub struct DescriptorRecord<B: Secp256k1Backend> {
+    keys: Vec<OriginKey<Secp256k1Curve<B>>>,
+    policy: MultisigPolicy<B>,
+    path_restrictions: Vec<Change>,
+    first_address: String,
+}
+
+impl<B: Secp256k1Backend> Clone for DescriptorRecord<B> {
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            policy: self.policy.clone(),
+            path_restrictions: self.path_restrictions.clone(),
+            first_address: self.first_address.clone(),
+        }
+    }
+}
+
+impl<B: Secp256k1Backend> fmt::Debug for DescriptorRecord<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DescriptorRecord")
+            .field("policy", &self.policy)
+            .field("path_restrictions", &self.path_restrictions)
+            .field("first_address", &self.first_address)
+            .finish()
+    }
+}
+
+impl<B: Secp256k1Backend> DescriptorRecord<B> {
+    /// Creates the descriptor record of a `sortedmulti` wallet over the given keys, restricted to
+    /// the external and internal chains.
+    pub fn new(
+        threshold: usize,
+        keys: Vec<OriginKey<Secp256k1Curve<B>>>,
+        address_type: AddressType,
+        network: Network,
+    ) -> Result<Self> {
+        let cosigners = keys.iter().map(|key| key.key().clone()).collect();
+        let policy = MultisigPolicy::new(threshold, cosigners, address_type)?;
+        let path_restrictions = Vec::from([Change::External, Change::Internal]);
+        let first_address = policy.address(network, Change::External, 0)?.to_string();
+        Ok(Self { keys, policy, path_restrictions, first_address })
+    }
+
+    /// Verifies the key records of a session and creates the descriptor record of a
+    /// `sortedmulti` wallet over their keys, in record order.
+    pub fn from_key_records(
+        token: &Token,
+        threshold: usize,
+        records: &[KeyRecord<B>],
+        address_type: AddressType,
+        network: Network,
+    ) -> Result<Self>
+    where
+        B::PublicKey: Verifier<RecoverableEcdsa>,
+    {
+        for record in records {
+            if record.token() != token {
+                return Err(Error::new(ErrorKind::InvalidRecord, "key record token mismatch")
+                    .with_context("token", record.token())
+                    .with_context("expected", token));
+            }
+            record.verify()?;
+        }
+        let keys = records.iter().map(|record| record.key().clone()).collect();
+        Self::new(threshold, keys, address_type, network)
+    }
+
+    /// Returns the keys in template order.
+    pub fn keys(&self) -> &[OriginKey<Secp256k1Curve<B>>] {
+        &self.keys
+    }
+
+    /// Returns the multisig policy of the wallet.
+    pub fn policy(&self) -> &MultisigPolicy<B> {
+        &self.policy
+    }
+
+    /// Returns the chains the wallet is restricted to, empty without path restrictions.
+    pub fn path_restrictions(&self) -> &[Change] {
+        &self.path_restrictions
+    }
+
+    /// Returns the first address of the wallet, as stated by the record.
+    pub fn first_address(&self) -> &str {
+        &self.first_address
+    }
+
+    /// Returns the descriptor template, e.g. `wsh(sortedmulti(2,[...]xpub.../**,...))`.
+    pub fn template(&self) -> String {
+        let keys = self.keys.iter().map(|key| format!(",{key}/**")).collect::<String>();
+        let multi = format!("sortedmulti({}{keys})", self.policy.threshold());
+        match self.policy.address_type() {
+            AddressType::P2wsh => format!("wsh({multi})"),
+            AddressType::P2shP2wsh => format!("sh(wsh({multi}))"),
+            _ => format!("sh({multi})"),
+        }
+    }
+
+    /// Returns the multisig script at `/change/index`.
+    pub fn script(&self, change: Change, index: u32) -> Result<MultisigScript> {
+        self.policy.script(change, index)
+    }
+
+    /// Returns the address at `/change/index` for the given network.
+    pub fn address(&self, network: Network, change: Change, index: u32) -> Result<Address> {
+        self.policy.address(network, change, index)
+    }
+
+    /// Checks the first address of the record against the address derived from the template: at
+    /// index 0 of the first allowed chain.
+    pub fn verify(&self, network: Network) -> Result<()> {
+        let change = self.path_restrictions.first().copied().unwrap_or(Change::External);
+        let address = self.address(network, change, 0)?.to_string();
+        if address != self.first_address {
+            return Err(Error::new(ErrorKind::InvalidRecord, "first address mismatch")
+                .with_context("first_address", &self.first_address)
+                .with_context("derived", address));
+        }
+        Ok(())
+    }
+
+    /// Returns whether the wallet contains the given key, e.g. the signer's own account key.
+    pub fn contains_key(&self, key: &ExtendedPublicKey<Secp256k1Curve<B>>) -> bool {
+        self.keys.iter().any(|own| own.key() == key)
+    }
+}
+
+impl<B: Secp256k1Backend> fmt::Display for DescriptorRecord<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{BSMS_VERSION}")?;
+        writeln!(f, "{}", self.template())?;
+        if self.path_restrictions.is_empty() {
+            f.write_str(NO_PATH_RESTRICTIONS)?;
+        } else {
+            for (i, change) in self.path_restrictions.iter().enumerate() {
+                let separator = if i == 0 { "" } else { "," };
+                write!(f, "{separator}/{}/*", change.index())?;
+            }
+        }
+        write!(f, "\n{}", self.first_address)
+    }
+}
+
+impl<B: Secp256k1Backend> FromStr for DescriptorRecord<B> {
+    type Err = Error;
+
+    /// Parses a descriptor record, without verifying its first address.
+    fn from_str(record: &str) -> Result<Self> {
+        let [template, path_restrictions, first_address] = record_lines(record)?;
+
+        // the external chain stands for `/**` when parsing the keys of the template
+        let descriptor =
+            template.replace("/**", "/0/*").parse::<Descriptor<Secp256k1Curve<B>>>()?;
+        let (multi, address_type) = match &descriptor {
+            Descriptor::ShMulti(multi) => (multi, AddressType::P2sh),
+            Descriptor::WshMulti(multi) => (multi, AddressType::P2wsh),
+            Descriptor::ShWshMulti(multi) => (multi, AddressType::P2shP2wsh),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidRecord,
+                    "unsupported descriptor template",
+                )
+                .with_context("template", template));
+            },
+        };
+        if !multi.is_sorted() {
+            return Err(Error::new(ErrorKind::InvalidRecord, "unsupported descriptor template")
+                .with_context("template", template));
+        }
+        // `/0/*` parses the same as `/**`, so the raw key expressions are checked
+        let wildcards = template
+            .rsplit_once('(')
+            .and_then(|(_, args)| args.split_once(')'))
+            .is_some_and(|(args, _)| args.split(',').skip(1).all(|key| key.ends_with("/**")));
+        if !wildcards {
+            return Err(Error::new(
+                ErrorKind::InvalidRecord,
+                "template keys require an origin and `/**`",
+            )
+            .with_context("template", template));
+        }
+        let external = ChildNumber::new(Change::External.index(), false)?;
+        let keys = multi
+            .keys()
+            .iter()
+            .map(|key| {
+                let origin = key
+                    .origin()
+                    .filter(|_| key.is_wildcard() && key.path().children() == [external])
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::InvalidRecord,
+                            "template keys require an origin and `/**`",
+                        )
+                        .with_context("template", template)
+                    })?;
+                OriginKey::new(origin.clone(), key.key().clone(), key.version())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let path_restrictions = if path_restrictions == NO_PATH_RESTRICTIONS {
+            Vec::new()
+        } else {
+            path_restrictions
+                .split(',')
+                .map(|restriction| {
+                    restriction
+                        .strip_prefix('/')
+                        .and_then(|restriction| restriction.strip_suffix("/*"))
+                        .and_then(|index| index.parse::<u32>().ok())
+                        .and_then(Change::from_index)
+                        .ok_or_else(|| {
+                            Error::new(ErrorKind::InvalidRecord, "invalid path restriction")
+                                .with_context("path_restriction", restriction)
+                        })
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        let cosigners = keys.iter().map(|key| key.key().clone()).collect();
+        let policy = MultisigPolicy::new(multi.threshold(), cosigners, address_type)?;
+        Ok(Self { keys, policy, path_restrictions, first_address: first_address.to_string() })
+    }
+}
+
+fn check_description(description: &str) -> Result<()> {
+    if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        return Err(Error::new(ErrorKind::InvalidRecord, "description too long")
+            .with_context("length", description.chars().count())
+            .with_context("max_length", MAX_DESCRIPTION_LENGTH));
+    }
+    if description.contains(['\n', '\r']) {
+        return Err(Error::new(ErrorKind::InvalidRecord, "description must be a single line"));
+    }
+    Ok(())
+}
+
+/// The signed part of a key record: every line but the signature.
+fn key_record_message<B: Secp256k1Backend>(
+    token: &Token,
+    key: &OriginKey<Secp256k1Curve<B>>,
+    description: &str,
+) -> String {
+    format!("{BSMS_VERSION}\n{token}\n{key}\n{description}")
+}
+
+/// Splits a record into its lines after the version line.
+fn record_lines<const N: usize>(record: &str) -> Result<[&str; N]> {
+    let mut lines = record.lines();
+    let version = lines.next().unwrap_or_default();
+    if version != BSMS_VERSION {
+        return Err(Error::new(ErrorKind::InvalidRecord, "unsupported BSMS version")
+            .with_context("version", version));
+    }
+
+    let mut out = [""; N];
+    let mut count = 0;
+    for line in lines {
+        if count == N {
+            count += 1;
+            break;
+        }
+        out[count] = line;
+        count += 1;
+    }
+    if count != N {
+        return Err(Error::new(ErrorKind::InvalidRecord, "unexpected number of record lines")
+            .with_context("expected", N + 1));
+    }
+    Ok(out)
+}
+
+/// The digest of a message in the Bitcoin message format: double SHA-256 of the magic prefix and
+/// the length-prefixed message.
+fn message_digest(message: &str) -> [u8; 32] {
+    let mut engine = Sha256::new();
+    engine.update(MESSAGE_MAGIC);
+    // compact size length prefix
+    let len = message.len();
+    if len < 0xFD {
+        engine.update([len as u8]);
+    } else if len <= 0xFFFF {
+        engine.update([0xFD]);
+        engine.update((len as u16).to_le_bytes());
+    } else {
+        engine.update([0xFE]);
+        engine.update((len as u32).to_le_bytes());
+    }
+    engine.update(message.as_bytes());
+    Sha256::digest(engine.finalize()).into()
+}
@@ -1,6 +1,8 @@
-use ed25519_dalek::{SigningKey, VerifyingKey};
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
 
-use crate::curve::{CurveError, CurvePrivateKey, CurvePublicKey, ed25519::Ed25519Backend};
+use crate::curve::{
+    CurveError, CurvePrivateKey, CurvePublicKey, Eddsa, Signer, Verifier, ed25519::Ed25519Backend,
+};
 
 /// Ed25519 backend powered by the [`ed25519-dalek`](https://github.com/dalek-cryptography/curve25519-dalek/tree/main/ed25519-dalek) crate.
 pub struct Ed25519DalekBackend;
@@ -45,6 +47,24 @@ impl CurvePrivateKey for SigningKey {
     }
 }
 
+impl Signer<Eddsa> for SigningKey {
+    type Error = CurveError;
+
+    fn sign(&self, message: &[u8]) -> Result<[u8; 64], Self::Error> {
+        let signature = ed25519_dalek::Signer::try_sign(self, message).map_err(CurveError::new)?;
+        Ok(signature.to_bytes())
+    }
+}
+
+impl Verifier<Eddsa> for VerifyingKey {
+    type Error = CurveError;
+
+    fn verify(&self, message: &[u8], signature: &[u8; 64]) -> Result<(), Self::Error> {
+        let signature = Signature::from_bytes(signature);
+        ed25519_dalek::Verifier::verify(self, message, &signature).map_err(CurveError::new)
+    }
+}
+
 impl Ed25519Backend for Ed25519DalekBackend {
     type PublicKey = VerifyingKey;
     type PrivateKey = SigningKey;
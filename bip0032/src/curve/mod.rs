@@ -58,16 +58,35 @@ pub trait TweakableKey: Sized {
     fn add_tweak(&self, tweak: &[u8; 32]) -> Result<Self, Self::Error>;
 }
 
+/// Keys that support negation.
+pub trait NegatableKey: Sized {
+    /// Returns the negated key.
+    fn negate(&self) -> Self;
+}
+
+/// Public keys that can be serialized as uncompressed SEC1 points.
+pub trait UncompressedPublicKey {
+    /// Serializes this key as an uncompressed SEC1 point (`0x04 || x || y`).
+    fn to_uncompressed_bytes(&self) -> [u8; 65];
+}
+
 /// Marker trait for BIP32-encodable curves.
 pub trait Bip32Curve: Curve {}
 
 mod error;
+mod sign;
 #[cfg(feature = "slip10")]
 mod slip10;
 
-pub use self::error::CurveError;
 #[cfg(feature = "slip10")]
 pub use self::slip10::*;
+pub use self::{
+    error::CurveError,
+    sign::{
+        Ecdsa, Eddsa, RecoverableEcdsa, Schnorr, SchnorrSigner, SignatureAlgorithm, Signer,
+        Verifier,
+    },
+};
 
 #[cfg(feature = "slip10")]
 pub mod ed25519;
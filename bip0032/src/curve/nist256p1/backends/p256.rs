@@ -1,11 +1,13 @@
 use p256::{
     AffinePoint, NonZeroScalar, ProjectivePoint, PublicKey, SecretKey,
+    ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier},
     elliptic_curve::sec1::ToSec1Point,
 };
 use zeroize::Zeroizing;
 
 use crate::curve::{
-    CurveError, CurvePrivateKey, CurvePublicKey, TweakableKey, nist256p1::Nist256p1Backend,
+    CurveError, CurvePrivateKey, CurvePublicKey, Ecdsa, Signer, TweakableKey,
+    UncompressedPublicKey, Verifier, nist256p1::Nist256p1Backend,
 };
 
 /// NIST P-256 backend powered by the [`p256`](https://github.com/RustCrypto/elliptic-curves/tree/master/p256) crate.
@@ -27,6 +29,15 @@ impl CurvePublicKey for PublicKey {
     }
 }
 
+impl UncompressedPublicKey for PublicKey {
+    fn to_uncompressed_bytes(&self) -> [u8; 65] {
+        let encoded = self.to_sec1_point(false);
+        let mut out = [0u8; 65];
+        out.copy_from_slice(encoded.as_bytes());
+        out
+    }
+}
+
 impl TweakableKey for PublicKey {
     type Error = CurveError;
 
@@ -76,6 +87,28 @@ impl TweakableKey for SecretKey {
     }
 }
 
+impl Signer<Ecdsa> for SecretKey {
+    type Error = CurveError;
+
+    fn sign(&self, message: &[u8; 32]) -> Result<[u8; 64], Self::Error> {
+        let signing_key = p256::ecdsa::SigningKey::from(self);
+        let signature: p256::ecdsa::Signature =
+            signing_key.sign_prehash(message).map_err(CurveError::new)?;
+        Ok(signature.to_bytes().into())
+    }
+}
+
+impl Verifier<Ecdsa> for PublicKey {
+    type Error = CurveError;
+
+    fn verify(&self, message: &[u8; 32], signature: &[u8; 64]) -> Result<(), Self::Error> {
+        let signature = p256::ecdsa::Signature::from_slice(signature).map_err(CurveError::new)?;
+        p256::ecdsa::VerifyingKey::from(self)
+            .verify_prehash(message, &signature)
+            .map_err(CurveError::new)
+    }
+}
+
 impl Nist256p1Backend for P256Backend {
     type PublicKey = PublicKey;
     type PrivateKey = SecretKey;
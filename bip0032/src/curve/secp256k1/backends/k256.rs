@@ -1,10 +1,14 @@
 use k256::{
-    NonZeroScalar, ProjectivePoint, PublicKey, SecretKey, elliptic_curve::sec1::ToSec1Point,
+    NonZeroScalar, ProjectivePoint, PublicKey, SecretKey,
+    ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier},
+    elliptic_curve::sec1::ToSec1Point,
+    schnorr,
 };
 use zeroize::Zeroizing;
 
 use crate::curve::{
-    CurveError, CurvePrivateKey, CurvePublicKey, TweakableKey, secp256k1::Secp256k1Backend,
+    CurveError, CurvePrivateKey, CurvePublicKey, Ecdsa, NegatableKey, RecoverableEcdsa, Schnorr,
+    SchnorrSigner, Signer, TweakableKey, Verifier, secp256k1::Secp256k1Backend,
 };
 
 /// Secp256k1 backend powered by the [`k256`](https://github.com/RustCrypto/elliptic-curves/tree/master/k256) crate.
@@ -75,6 +79,104 @@ impl TweakableKey for SecretKey {
     }
 }
 
+impl NegatableKey for SecretKey {
+    fn negate(&self) -> Self {
+        let key_scalar = Zeroizing::new(self.to_nonzero_scalar());
+        SecretKey::from(-*key_scalar)
+    }
+}
+
+impl Signer<Ecdsa> for SecretKey {
+    type Error = CurveError;
+
+    fn sign(&self, message: &[u8; 32]) -> Result<[u8; 64], Self::Error> {
+        let signing_key = k256::ecdsa::SigningKey::from(self);
+        let signature: k256::ecdsa::Signature =
+            signing_key.sign_prehash(message).map_err(CurveError::new)?;
+        Ok(signature.to_bytes().into())
+    }
+}
+
+impl Verifier<Ecdsa> for PublicKey {
+    type Error = CurveError;
+
+    fn verify(&self, message: &[u8; 32], signature: &[u8; 64]) -> Result<(), Self::Error> {
+        let signature = k256::ecdsa::Signature::from_slice(signature).map_err(CurveError::new)?;
+        k256::ecdsa::VerifyingKey::from(self)
+            .verify_prehash(message, &signature)
+            .map_err(CurveError::new)
+    }
+}
+
+impl Signer<RecoverableEcdsa> for SecretKey {
+    type Error = CurveError;
+
+    fn sign(&self, message: &[u8; 32]) -> Result<[u8; 65], Self::Error> {
+        let signing_key = k256::ecdsa::SigningKey::from(self);
+        let (signature, recovery_id) = signing_key.sign_prehash_recoverable(message);
+        let mut out = [0u8; 65];
+        out[..64].copy_from_slice(&signature.to_bytes());
+        out[64] = recovery_id.to_byte();
+        Ok(out)
+    }
+}
+
+impl Verifier<RecoverableEcdsa> for PublicKey {
+    type Error = CurveError;
+
+    fn verify(&self, message: &[u8; 32], signature: &[u8; 65]) -> Result<(), Self::Error> {
+        let recovery_id = k256::ecdsa::RecoveryId::from_byte(signature[64])
+            .ok_or_else(k256::ecdsa::Error::new)
+            .map_err(CurveError::new)?;
+        let signature =
+            k256::ecdsa::Signature::from_slice(&signature[..64]).map_err(CurveError::new)?;
+        // only low-S signatures verify, as with the non-recoverable form
+        if signature.normalize_s() != signature {
+            return Err(CurveError::new(k256::ecdsa::Error::new()));
+        }
+        let recovered =
+            k256::ecdsa::VerifyingKey::recover_from_prehash(message, &signature, recovery_id)
+                .map_err(CurveError::new)?;
+        if recovered != k256::ecdsa::VerifyingKey::from(self) {
+            return Err(CurveError::new(k256::ecdsa::Error::new()));
+        }
+        Ok(())
+    }
+}
+
+impl Signer<Schnorr> for SecretKey {
+    type Error = CurveError;
+
+    fn sign(&self, message: &[u8]) -> Result<[u8; 64], Self::Error> {
+        self.sign_with_aux_rand(message, &[0u8; 32])
+    }
+}
+
+impl SchnorrSigner for SecretKey {
+    fn sign_with_aux_rand(
+        &self,
+        message: &[u8],
+        aux_rand: &[u8; 32],
+    ) -> Result<[u8; 64], Self::Error> {
+        let signing_key = schnorr::SigningKey::from(self);
+        let signature = signing_key.sign_raw(message, aux_rand).map_err(CurveError::new)?;
+        Ok(signature.to_bytes())
+    }
+}
+
+impl Verifier<Schnorr> for PublicKey {
+    type Error = CurveError;
+
+    fn verify(&self, message: &[u8], signature: &[u8; 64]) -> Result<(), Self::Error> {
+        // BIP-0340 public keys are x-only, i.e. the point with the same x and an even y
+        let x_only = CurvePublicKey::to_bytes(self);
+        let verifying_key =
+            schnorr::VerifyingKey::from_slice(&x_only[1..]).map_err(CurveError::new)?;
+        let signature = schnorr::Signature::from_slice(signature).map_err(CurveError::new)?;
+        verifying_key.verify_raw(message, &signature).map_err(CurveError::new)
+    }
+}
+
 impl Secp256k1Backend for K256Backend {
     type PublicKey = PublicKey;
     type PrivateKey = SecretKey;
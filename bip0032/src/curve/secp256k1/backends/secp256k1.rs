@@ -1,8 +1,11 @@
-use secp256k1::{PublicKey, Scalar, Secp256k1, SecretKey, SignOnly, VerifyOnly};
+use secp256k1::{
+    Keypair, Message, PublicKey, Scalar, Secp256k1, SecretKey, SignOnly, VerifyOnly, ecdsa, schnorr,
+};
 use zeroize::Zeroizing;
 
 use crate::curve::{
-    CurveError, CurvePrivateKey, CurvePublicKey, TweakableKey, secp256k1::Secp256k1Backend,
+    CurveError, CurvePrivateKey, CurvePublicKey, Ecdsa, NegatableKey, RecoverableEcdsa, Schnorr,
+    SchnorrSigner, Signer, TweakableKey, Verifier, secp256k1::Secp256k1Backend,
 };
 
 /// Secp256k1 FFI backend powered by the [`secp256k1`](https://github.com/rust-bitcoin/rust-secp256k1) crate.
@@ -126,6 +129,109 @@ impl TweakableKey for SecretKey {
     }
 }
 
+impl NegatableKey for SecretKey {
+    fn negate(&self) -> Self {
+        (*self).negate()
+    }
+}
+
+impl Signer<Ecdsa> for SecretKey {
+    type Error = CurveError;
+
+    fn sign(&self, message: &[u8; 32]) -> Result<[u8; 64], Self::Error> {
+        let message = Message::from_digest(*message);
+        let signature = with_signing_context(|secp| secp.sign_ecdsa(message, self));
+        Ok(signature.serialize_compact())
+    }
+}
+
+impl Verifier<Ecdsa> for PublicKey {
+    type Error = CurveError;
+
+    fn verify(&self, message: &[u8; 32], signature: &[u8; 64]) -> Result<(), Self::Error> {
+        let message = Message::from_digest(*message);
+        let signature = ecdsa::Signature::from_compact(signature).map_err(CurveError::new)?;
+        with_verification_context(|secp| {
+            secp.verify_ecdsa(message, &signature, self).map_err(CurveError::new)
+        })
+    }
+}
+
+impl Signer<RecoverableEcdsa> for SecretKey {
+    type Error = CurveError;
+
+    fn sign(&self, message: &[u8; 32]) -> Result<[u8; 65], Self::Error> {
+        let message = Message::from_digest(*message);
+        let signature = with_signing_context(|secp| secp.sign_ecdsa_recoverable(message, self));
+        let (recovery_id, compact) = signature.serialize_compact();
+        let mut out = [0u8; 65];
+        out[..64].copy_from_slice(&compact);
+        out[64] = i32::from(recovery_id) as u8;
+        Ok(out)
+    }
+}
+
+impl Verifier<RecoverableEcdsa> for PublicKey {
+    type Error = CurveError;
+
+    fn verify(&self, message: &[u8; 32], signature: &[u8; 65]) -> Result<(), Self::Error> {
+        let message = Message::from_digest(*message);
+        let recovery_id =
+            ecdsa::RecoveryId::try_from(i32::from(signature[64])).map_err(CurveError::new)?;
+        let recoverable = ecdsa::RecoverableSignature::from_compact(&signature[..64], recovery_id)
+            .map_err(CurveError::new)?;
+        // only low-S signatures verify, as with the non-recoverable form
+        let mut normalized = recoverable.to_standard();
+        normalized.normalize_s();
+        if normalized != recoverable.to_standard() {
+            return Err(CurveError::new(secp256k1::Error::IncorrectSignature));
+        }
+        let recovered = with_verification_context(|secp| {
+            secp.recover_ecdsa(message, &recoverable).map_err(CurveError::new)
+        })?;
+        if recovered != *self {
+            return Err(CurveError::new(secp256k1::Error::IncorrectSignature));
+        }
+        Ok(())
+    }
+}
+
+impl Signer<Schnorr> for SecretKey {
+    type Error = CurveError;
+
+    fn sign(&self, message: &[u8]) -> Result<[u8; 64], Self::Error> {
+        self.sign_with_aux_rand(message, &[0u8; 32])
+    }
+}
+
+impl SchnorrSigner for SecretKey {
+    fn sign_with_aux_rand(
+        &self,
+        message: &[u8],
+        aux_rand: &[u8; 32],
+    ) -> Result<[u8; 64], Self::Error> {
+        let signature = with_signing_context(|secp| {
+            let mut keypair = Keypair::from_secret_key(secp, self);
+            let signature = secp.sign_schnorr_with_aux_rand(message, &keypair, aux_rand);
+            keypair.non_secure_erase();
+            signature
+        });
+        Ok(signature.to_byte_array())
+    }
+}
+
+impl Verifier<Schnorr> for PublicKey {
+    type Error = CurveError;
+
+    fn verify(&self, message: &[u8], signature: &[u8; 64]) -> Result<(), Self::Error> {
+        let (x_only, _parity) = self.x_only_public_key();
+        let signature = schnorr::Signature::from_byte_array(*signature);
+        with_verification_context(|secp| {
+            secp.verify_schnorr(&signature, message, &x_only).map_err(CurveError::new)
+        })
+    }
+}
+
 impl Secp256k1Backend for Secp256k1FfiBackend {
     type PublicKey = PublicKey;
     type PrivateKey = SecretKey;
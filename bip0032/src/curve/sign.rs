@@ -0,0 +1,85 @@
+//! Signature algorithms and signing capabilities of curve keys.
+
+use crate::error::IntoErrorSource;
+
+/// A signature algorithm, used to select how a key signs or verifies.
+pub trait SignatureAlgorithm {
+    /// Message type accepted by this algorithm.
+    type Message: ?Sized;
+    /// Signature type produced by this algorithm.
+    type Signature;
+}
+
+/// ECDSA over a 32-byte message digest, with RFC 6979 deterministic nonces.
+///
+/// Signatures are in the 64-byte compact `r || s` form. secp256k1 signatures are normalized to
+/// low-S and only low-S signatures verify, as required by Bitcoin.
+pub struct Ecdsa;
+
+impl SignatureAlgorithm for Ecdsa {
+    type Message = [u8; 32];
+    type Signature = [u8; 64];
+}
+
+/// ECDSA over a 32-byte message digest with a recovery id, as used by Bitcoin message signatures.
+///
+/// Signatures are the low-S compact `r || s` form followed by the recovery id (0 ~ 3), with
+/// RFC 6979 deterministic nonces. Verification recovers the public key from the signature and
+/// compares it with the verifying key.
+pub struct RecoverableEcdsa;
+
+impl SignatureAlgorithm for RecoverableEcdsa {
+    type Message = [u8; 32];
+    type Signature = [u8; 65];
+}
+
+/// [BIP-0340](https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki) Schnorr signatures
+/// over secp256k1.
+///
+/// [`Signer::sign`] is the deterministic variant: it uses all-zero auxiliary randomness, so
+/// signatures are identical across backends. BIP-0340 recommends fresh auxiliary randomness to
+/// protect against side channels and fault attacks, see [`SchnorrSigner::sign_with_aux_rand`].
+/// Verification uses the x-only form of the public key.
+pub struct Schnorr;
+
+impl SignatureAlgorithm for Schnorr {
+    type Message = [u8];
+    type Signature = [u8; 64];
+}
+
+/// Ed25519 ([RFC 8032](https://www.rfc-editor.org/rfc/rfc8032)) signatures.
+pub struct Eddsa;
+
+impl SignatureAlgorithm for Eddsa {
+    type Message = [u8];
+    type Signature = [u8; 64];
+}
+
+/// Private keys that sign messages with the signature algorithm `A`.
+pub trait Signer<A: SignatureAlgorithm> {
+    /// Backend-specific error type.
+    type Error: IntoErrorSource + Send + Sync + 'static;
+
+    /// Signs a message.
+    fn sign(&self, message: &A::Message) -> Result<A::Signature, Self::Error>;
+}
+
+/// Private keys that sign [`Schnorr`] signatures with auxiliary randomness.
+pub trait SchnorrSigner: Signer<Schnorr> {
+    /// Signs a message with 32 bytes of auxiliary randomness, freshly drawn from a
+    /// cryptographically secure source for every signature.
+    fn sign_with_aux_rand(
+        &self,
+        message: &[u8],
+        aux_rand: &[u8; 32],
+    ) -> Result<[u8; 64], Self::Error>;
+}
+
+/// Public keys that verify signatures of the signature algorithm `A`.
+pub trait Verifier<A: SignatureAlgorithm> {
+    /// Backend-specific error type.
+    type Error: IntoErrorSource + Send + Sync + 'static;
+
+    /// Verifies a signature of a message.
+    fn verify(&self, message: &A::Message, signature: &A::Signature) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,109 @@
+//! Descriptor checksum (BIP-0380).
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+use crate::error::{Error, ErrorKind, Result};
+
+const INPUT_CHARSET: &[u8] = b"0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Length of a descriptor checksum.
+pub(crate) const CHECKSUM_LENGTH: usize = 8;
+
+fn polymod(c: u64, value: u64) -> u64 {
+    const GENERATOR: [u64; 5] =
+        [0xf5_dee5_1989, 0xa9_fdca_3312, 0x1b_ab10_e32d, 0x37_06b1_677a, 0x64_4d62_6ffd];
+
+    let top = c >> 35;
+    let mut c = ((c & 0x7_ffff_ffff) << 5) ^ value;
+    for (bit, generator) in GENERATOR.iter().enumerate() {
+        if (top >> bit) & 1 == 1 {
+            c ^= generator;
+        }
+    }
+    c
+}
+
+/// Computes the checksum of a descriptor string (without the `#` separator).
+pub fn checksum(descriptor: &str) -> Result<String> {
+    let mut c = 1u64;
+    let mut class = 0u64;
+    let mut class_count = 0;
+
+    for (position, ch) in descriptor.char_indices() {
+        let symbol = u8::try_from(ch)
+            .ok()
+            .and_then(|byte| INPUT_CHARSET.iter().position(|&b| b == byte))
+            .ok_or_else(|| {
+                Error::new(ErrorKind::InvalidDescriptor, "invalid character in descriptor")
+                    .with_context("character", ch)
+                    .with_context("position", position)
+            })? as u64;
+
+        // Every group of three characters contributes their class (symbol >> 5) as an extra
+        // symbol, so that case and character set errors are also detected.
+        c = polymod(c, symbol & 31);
+        class = class * 3 + (symbol >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            c = polymod(c, class);
+            class = 0;
+            class_count = 0;
+        }
+    }
+    if class_count > 0 {
+        c = polymod(c, class);
+    }
+    for _ in 0..CHECKSUM_LENGTH {
+        c = polymod(c, 0);
+    }
+    c ^= 1;
+
+    Ok((0..CHECKSUM_LENGTH)
+        .map(|i| char::from(CHECKSUM_CHARSET[((c >> (5 * (7 - i))) & 31) as usize]))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_vectors() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki#test-vectors
+        let cases = [
+            (
+                "sh(multi(2,[00000000/111'/222]xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc,xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L/0))",
+                "ggrsrxfy",
+            ),
+            (
+                "sh(multi(2,[00000000/111'/222]xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL,xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y/0))",
+                "tjg09x5t",
+            ),
+        ];
+
+        for (descriptor, expected) in cases {
+            assert_eq!(checksum(descriptor).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn checksum_third_group() {
+        // characters of the last group of the input charset, checked against the reference
+        // implementation of BIP-0380
+        let cases =
+            [("raw(deadbeef)#`\"\\ ", "u23axzpn"), ("`#\"\\ ", "uypj0u5q"), ("IJKL", "pg2l9j4j")];
+
+        for (descriptor, expected) in cases {
+            assert_eq!(checksum(descriptor).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn error_invalid_character() {
+        let err = checksum("pkh(\u{e9})").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidDescriptor);
+        assert_eq!(err.message(), "invalid character in descriptor");
+    }
+}
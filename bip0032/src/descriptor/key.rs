@@ -0,0 +1,188 @@
+//! Extended public key expressions in output descriptors.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+
+use crate::{
+    curve::{Bip32Curve, Curve, CurvePublicKey, TweakableKey},
+    error::{Error, ErrorKind, Result},
+    path::{ChildNumber, DerivationPath},
+    xkey::{ExtendedKeyPayload, ExtendedPublicKey, KeyOrigin, Version},
+};
+
+/// An extended public key expression in an output descriptor, e.g.
+/// `[d34db33f/84'/0'/0']xpub.../0/*`.
+///
+/// The key has an optional origin, a path of non-hardened steps derived from the key, and an
+/// optional trailing unhardened wildcard (`/*`). Hardened steps and hardened wildcards need the
+/// private key and are rejected.
+///
+/// Like Bitcoin Core, the origin is not checked against the key depth, since descriptors commonly
+/// carry origins of keys that are not rooted at a master key.
+pub struct DescriptorKey<C: Curve> {
+    origin: Option<KeyOrigin>,
+    key: ExtendedPublicKey<C>,
+    version: Version,
+    path: DerivationPath,
+    wildcard: bool,
+}
+
+impl<C: Curve> Clone for DescriptorKey<C> {
+    fn clone(&self) -> Self {
+        Self {
+            origin: self.origin.clone(),
+            key: self.key.clone(),
+            version: self.version,
+            path: self.path.clone(),
+            wildcard: self.wildcard,
+        }
+    }
+}
+
+impl<C: Curve> DescriptorKey<C> {
+    /// Creates a descriptor key expression.
+    ///
+    /// The version bytes are used when formatting the key.
+    pub fn new(
+        origin: Option<KeyOrigin>,
+        mut key: ExtendedPublicKey<C>,
+        version: Version,
+        path: DerivationPath,
+        wildcard: bool,
+    ) -> Result<Self> {
+        if !version.is_public() {
+            return Err(Error::new(ErrorKind::InvalidVersion, "expected public version bytes")
+                .with_context("version", version));
+        }
+
+        if let Some(child) = path.children().iter().find(|child| child.is_hardened()) {
+            return Err(Error::new(
+                ErrorKind::InvalidDescriptor,
+                "hardened derivation step requires a private key",
+            )
+            .with_context("child_index", child.index()));
+        }
+
+        if let Some(origin) = &origin {
+            key.origin = Some(origin.clone());
+        }
+
+        Ok(Self { origin, key, version, path, wildcard })
+    }
+
+    /// Returns the key origin, if any.
+    pub fn origin(&self) -> Option<&KeyOrigin> {
+        self.origin.as_ref()
+    }
+
+    /// Returns the extended public key.
+    pub fn key(&self) -> &ExtendedPublicKey<C> {
+        &self.key
+    }
+
+    /// Returns the version bytes used when formatting the key.
+    pub const fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns the derivation steps applied to the key before the wildcard.
+    pub fn path(&self) -> &DerivationPath {
+        &self.path
+    }
+
+    /// Returns true if this key ends with an unhardened wildcard (`/*`).
+    pub const fn is_wildcard(&self) -> bool {
+        self.wildcard
+    }
+}
+
+impl<C> DescriptorKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: TweakableKey,
+{
+    /// Derives the concrete extended public key at the given wildcard index.
+    ///
+    /// The index is ignored if this key has no wildcard.
+    pub fn derive(&self, index: u32) -> Result<ExtendedPublicKey<C>> {
+        let key = self.key.derive_path(&self.path)?;
+        if self.wildcard { key.derive_child(ChildNumber::new(index, false)?) } else { Ok(key) }
+    }
+}
+
+impl<C> fmt::Display for DescriptorKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(origin) = &self.origin {
+            write!(f, "{origin}")?;
+        }
+        write!(f, "{}", self.key.encode_with_unchecked(self.version))?;
+        for child in &self.path {
+            write!(f, "/{child}")?;
+        }
+        if self.wildcard {
+            f.write_str("/*")?;
+        }
+        Ok(())
+    }
+}
+
+impl<C> FromStr for DescriptorKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    type Err = Error;
+
+    fn from_str(expression: &str) -> Result<Self> {
+        let (origin, rest) = if expression.starts_with('[') {
+            let end = expression.find(']').ok_or_else(|| {
+                Error::new(ErrorKind::InvalidOrigin, "unterminated key origin")
+                    .with_context("key", expression)
+            })?;
+            let (origin, rest) = expression.split_at(end + 1);
+            (Some(origin.parse::<KeyOrigin>()?), rest)
+        } else {
+            (None, expression)
+        };
+
+        let mut components = rest.split('/');
+        let encoded = components.next().unwrap_or_default();
+        let payload = encoded.parse::<ExtendedKeyPayload>()?;
+        let version = payload.version();
+        let key = ExtendedPublicKey::try_from(payload)?;
+
+        let mut children = Vec::new();
+        let mut wildcard = false;
+        for component in components {
+            if wildcard {
+                return Err(Error::new(
+                    ErrorKind::InvalidDescriptor,
+                    "wildcard must be the last path component",
+                )
+                .with_context("key", expression));
+            }
+            match component {
+                "*" => wildcard = true,
+                "*'" | "*h" | "*H" => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidDescriptor,
+                        "hardened wildcard requires a private key",
+                    )
+                    .with_context("key", expression));
+                },
+                "" => {
+                    return Err(Error::new(ErrorKind::InvalidPath, "empty path component")
+                        .with_context("key", expression));
+                },
+                component => children.push(component.parse::<ChildNumber>()?),
+            }
+        }
+
+        Self::new(origin, key, version, DerivationPath::from(children), wildcard)
+    }
+}
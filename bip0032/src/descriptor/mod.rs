@@ -0,0 +1,343 @@
+//! Output descriptors ([BIP-0380](https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki)
+//! ~ [BIP-0386](https://github.com/bitcoin/bips/blob/master/bip-0386.mediawiki)) over extended
+//! public keys.
+//!
+//! Supported descriptors:
+//!
+//! - `pkh(KEY)`, `wpkh(KEY)`, `sh(wpkh(KEY))` and `tr(KEY)` (key path only)
+//! - `multi(k,KEY,...)` and `sortedmulti(k,KEY,...)`, bare or wrapped in `sh(...)`, `wsh(...)` or
+//!   `sh(wsh(...))`
+//!
+//! Key expressions are extended public keys with an optional origin and derivation steps, see
+//! [`DescriptorKey`].
+//!
+//! ```rust
+//! use bip0032::{curve::secp256k1::*, descriptor::Descriptor};
+//!
+//! let descriptor = "wpkh([d34db33f/84'/0'/0']xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/0/*)";
+//! let descriptor: Descriptor<Secp256k1Curve<K256Backend>> = descriptor.parse().unwrap();
+//! let keys = descriptor.derive_keys(7).unwrap();
+//! assert_eq!(keys[0].origin().unwrap().to_string(), "[d34db33f/84'/0'/0'/0/7]");
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, slice, str::FromStr};
+
+mod checksum;
+mod key;
+
+use self::checksum::CHECKSUM_LENGTH;
+pub use self::{checksum::checksum, key::DescriptorKey};
+use crate::{
+    curve::{Bip32Curve, Curve, CurvePublicKey, TweakableKey},
+    error::{Error, ErrorKind, Result},
+    xkey::ExtendedPublicKey,
+};
+
+/// Maximum number of keys in a `multi`/`sortedmulti` expression.
+const MAX_MULTISIG_KEYS: usize = 20;
+/// Maximum number of keys in a `multi`/`sortedmulti` expression inside `sh(...)`.
+const MAX_SH_MULTISIG_KEYS: usize = 15;
+/// Maximum number of keys in a bare `multi`/`sortedmulti` expression.
+const MAX_BARE_MULTISIG_KEYS: usize = 3;
+
+/// A `multi`/`sortedmulti` expression: a threshold and the keys.
+pub struct Multisig<C: Curve> {
+    threshold: usize,
+    keys: Vec<DescriptorKey<C>>,
+    sorted: bool,
+}
+
+impl<C: Curve> Clone for Multisig<C> {
+    fn clone(&self) -> Self {
+        Self { threshold: self.threshold, keys: self.keys.clone(), sorted: self.sorted }
+    }
+}
+
+impl<C: Curve> Multisig<C> {
+    /// Creates a `multi` (or `sortedmulti` if `sorted` is set) expression.
+    pub fn new(threshold: usize, keys: Vec<DescriptorKey<C>>, sorted: bool) -> Result<Self> {
+        if keys.is_empty() || keys.len() > MAX_MULTISIG_KEYS {
+            return Err(Error::new(
+                ErrorKind::InvalidDescriptor,
+                "invalid number of multisig keys",
+            )
+            .with_context("keys", keys.len())
+            .with_context("max_keys", MAX_MULTISIG_KEYS));
+        }
+        if threshold == 0 || threshold > keys.len() {
+            return Err(Error::new(ErrorKind::InvalidDescriptor, "invalid multisig threshold")
+                .with_context("threshold", threshold)
+                .with_context("keys", keys.len()));
+        }
+
+        Ok(Self { threshold, keys, sorted })
+    }
+
+    /// Returns the number of required signatures.
+    pub const fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns the keys in descriptor order.
+    pub fn keys(&self) -> &[DescriptorKey<C>] {
+        &self.keys
+    }
+
+    /// Returns true for `sortedmulti`, whose derived keys are sorted (BIP-0067).
+    pub const fn is_sorted(&self) -> bool {
+        self.sorted
+    }
+
+    fn check_key_count(&self, max_keys: usize) -> Result<()> {
+        if self.keys.len() > max_keys {
+            return Err(Error::new(ErrorKind::InvalidDescriptor, "too many multisig keys")
+                .with_context("keys", self.keys.len())
+                .with_context("max_keys", max_keys));
+        }
+        Ok(())
+    }
+}
+
+/// An output descriptor over extended public keys.
+pub enum Descriptor<C: Curve> {
+    /// `pkh(KEY)`: P2PKH output.
+    Pkh(DescriptorKey<C>),
+    /// `wpkh(KEY)`: P2WPKH output.
+    Wpkh(DescriptorKey<C>),
+    /// `sh(wpkh(KEY))`: P2SH-P2WPKH output.
+    ShWpkh(DescriptorKey<C>),
+    /// `tr(KEY)`: P2TR output with key path spending only.
+    Tr(DescriptorKey<C>),
+    /// `multi(...)`/`sortedmulti(...)`: bare multisig output.
+    Multi(Multisig<C>),
+    /// `sh(multi(...))`/`sh(sortedmulti(...))`: P2SH multisig output.
+    ShMulti(Multisig<C>),
+    /// `wsh(multi(...))`/`wsh(sortedmulti(...))`: P2WSH multisig output.
+    WshMulti(Multisig<C>),
+    /// `sh(wsh(multi(...)))`/`sh(wsh(sortedmulti(...)))`: P2SH-P2WSH multisig output.
+    ShWshMulti(Multisig<C>),
+}
+
+impl<C: Curve> Clone for Descriptor<C> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Pkh(key) => Self::Pkh(key.clone()),
+            Self::Wpkh(key) => Self::Wpkh(key.clone()),
+            Self::ShWpkh(key) => Self::ShWpkh(key.clone()),
+            Self::Tr(key) => Self::Tr(key.clone()),
+            Self::Multi(multi) => Self::Multi(multi.clone()),
+            Self::ShMulti(multi) => Self::ShMulti(multi.clone()),
+            Self::WshMulti(multi) => Self::WshMulti(multi.clone()),
+            Self::ShWshMulti(multi) => Self::ShWshMulti(multi.clone()),
+        }
+    }
+}
+
+impl<C: Curve> Descriptor<C> {
+    /// Returns the key expressions in descriptor order.
+    pub fn keys(&self) -> &[DescriptorKey<C>] {
+        match self {
+            Self::Pkh(key) | Self::Wpkh(key) | Self::ShWpkh(key) | Self::Tr(key) => {
+                slice::from_ref(key)
+            },
+            Self::Multi(multi)
+            | Self::ShMulti(multi)
+            | Self::WshMulti(multi)
+            | Self::ShWshMulti(multi) => multi.keys(),
+        }
+    }
+
+    /// Returns true if any key expression ends with a wildcard.
+    pub fn has_wildcard(&self) -> bool {
+        self.keys().iter().any(DescriptorKey::is_wildcard)
+    }
+}
+
+impl<C> Descriptor<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]> + TweakableKey,
+{
+    /// Expands this descriptor at the given wildcard index into concrete public keys.
+    ///
+    /// Keys are returned in script order: descriptor order, or sorted by their serialized bytes
+    /// for `sortedmulti` (BIP-0067).
+    pub fn derive_keys(&self, index: u32) -> Result<Vec<ExtendedPublicKey<C>>> {
+        let mut keys =
+            self.keys().iter().map(|key| key.derive(index)).collect::<Result<Vec<_>>>()?;
+
+        if let Self::Multi(multi)
+        | Self::ShMulti(multi)
+        | Self::WshMulti(multi)
+        | Self::ShWshMulti(multi) = self
+        {
+            if multi.is_sorted() {
+                keys.sort_by_cached_key(ExtendedPublicKey::to_bytes);
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+impl<C> fmt::Display for Multisig<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if self.sorted { "sortedmulti" } else { "multi" };
+        write!(f, "{name}({}", self.threshold)?;
+        for key in &self.keys {
+            write!(f, ",{key}")?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl<C> Descriptor<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    /// Returns the descriptor string without checksum.
+    fn to_string_without_checksum(&self) -> String {
+        match self {
+            Self::Pkh(key) => format!("pkh({key})"),
+            Self::Wpkh(key) => format!("wpkh({key})"),
+            Self::ShWpkh(key) => format!("sh(wpkh({key}))"),
+            Self::Tr(key) => format!("tr({key})"),
+            Self::Multi(multi) => multi.to_string(),
+            Self::ShMulti(multi) => format!("sh({multi})"),
+            Self::WshMulti(multi) => format!("wsh({multi})"),
+            Self::ShWshMulti(multi) => format!("sh(wsh({multi}))"),
+        }
+    }
+}
+
+impl<C> fmt::Display for Descriptor<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let descriptor = self.to_string_without_checksum();
+        // Key expressions only produce characters from the checksum input set.
+        let checksum = checksum(&descriptor).map_err(|_| fmt::Error)?;
+        write!(f, "{descriptor}#{checksum}")
+    }
+}
+
+impl<C> FromStr for Descriptor<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    type Err = Error;
+
+    /// Parses a descriptor, verifying the checksum if present.
+    fn from_str(descriptor: &str) -> Result<Self> {
+        let body = match descriptor.split_once('#') {
+            Some((body, expected)) => {
+                if expected.len() != CHECKSUM_LENGTH {
+                    return Err(Error::new(
+                        ErrorKind::InvalidDescriptor,
+                        "invalid descriptor checksum length",
+                    )
+                    .with_context("checksum", expected));
+                }
+                let actual = checksum(body)?;
+                if actual != expected {
+                    return Err(Error::new(
+                        ErrorKind::InvalidDescriptor,
+                        "descriptor checksum mismatch",
+                    )
+                    .with_context("expected", expected)
+                    .with_context("actual", actual));
+                }
+                body
+            },
+            None => {
+                // Reject characters outside of the checksum input set even without a checksum.
+                checksum(descriptor)?;
+                descriptor
+            },
+        };
+
+        if let Some(inner) = strip_call(body, "pkh") {
+            Ok(Self::Pkh(inner.parse()?))
+        } else if let Some(inner) = strip_call(body, "wpkh") {
+            Ok(Self::Wpkh(inner.parse()?))
+        } else if let Some(inner) = strip_call(body, "tr") {
+            if inner.contains(',') {
+                return Err(Error::new(
+                    ErrorKind::InvalidDescriptor,
+                    "taproot script trees are not supported",
+                )
+                .with_context("descriptor", body));
+            }
+            Ok(Self::Tr(inner.parse()?))
+        } else if let Some(inner) = strip_call(body, "wsh") {
+            let multi = parse_multisig(inner)?;
+            multi.check_key_count(MAX_MULTISIG_KEYS)?;
+            Ok(Self::WshMulti(multi))
+        } else if let Some(inner) = strip_call(body, "sh") {
+            if let Some(inner) = strip_call(inner, "wpkh") {
+                Ok(Self::ShWpkh(inner.parse()?))
+            } else if let Some(inner) = strip_call(inner, "wsh") {
+                let multi = parse_multisig(inner)?;
+                multi.check_key_count(MAX_MULTISIG_KEYS)?;
+                Ok(Self::ShWshMulti(multi))
+            } else {
+                let multi = parse_multisig(inner)?;
+                multi.check_key_count(MAX_SH_MULTISIG_KEYS)?;
+                Ok(Self::ShMulti(multi))
+            }
+        } else {
+            let multi = parse_multisig(body)?;
+            multi.check_key_count(MAX_BARE_MULTISIG_KEYS)?;
+            Ok(Self::Multi(multi))
+        }
+    }
+}
+
+/// Strips `name(` and the matching `)` from an expression.
+fn strip_call<'a>(expression: &'a str, name: &str) -> Option<&'a str> {
+    expression.strip_prefix(name)?.strip_prefix('(')?.strip_suffix(')')
+}
+
+fn parse_multisig<C>(expression: &str) -> Result<Multisig<C>>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    let (inner, sorted) = if let Some(inner) = strip_call(expression, "multi") {
+        (inner, false)
+    } else if let Some(inner) = strip_call(expression, "sortedmulti") {
+        (inner, true)
+    } else {
+        return Err(Error::new(ErrorKind::InvalidDescriptor, "unsupported descriptor")
+            .with_context("descriptor", expression.to_string()));
+    };
+
+    let mut args = inner.split(',');
+    let threshold = args.next().unwrap_or_default();
+    let threshold = threshold
+        .bytes()
+        .all(|b| b.is_ascii_digit())
+        .then(|| threshold.parse::<usize>().ok())
+        .flatten()
+        .ok_or_else(|| {
+            Error::new(ErrorKind::InvalidDescriptor, "invalid multisig threshold")
+                .with_context("threshold", threshold.to_string())
+        })?;
+    let keys = args.map(str::parse).collect::<Result<Vec<_>>>()?;
+
+    Multisig::new(threshold, keys, sorted)
+}
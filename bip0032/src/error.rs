@@ -27,6 +27,18 @@ pub enum ErrorKind {
     InvalidKeyData,
     /// Invalid key derivation request or result.
     InvalidDerivation,
+    /// Invalid key origin input.
+    InvalidOrigin,
+    /// Invalid output descriptor input.
+    InvalidDescriptor,
+    /// Signing failure or invalid signature.
+    InvalidSignature,
+    /// Invalid script or script type usage.
+    InvalidScript,
+    /// Invalid multisig setup record input.
+    InvalidRecord,
+    /// Invalid deterministic entropy application input.
+    InvalidEntropy,
 }
 
 /// The lower-level source of [`Error`].
@@ -141,6 +153,12 @@ impl fmt::Display for ErrorKind {
             ErrorKind::InvalidVersion => f.write_str("InvalidVersion"),
             ErrorKind::InvalidKeyData => f.write_str("InvalidKeyData"),
             ErrorKind::InvalidDerivation => f.write_str("InvalidDerivation"),
+            ErrorKind::InvalidOrigin => f.write_str("InvalidOrigin"),
+            ErrorKind::InvalidDescriptor => f.write_str("InvalidDescriptor"),
+            ErrorKind::InvalidSignature => f.write_str("InvalidSignature"),
+            ErrorKind::InvalidScript => f.write_str("InvalidScript"),
+            ErrorKind::InvalidRecord => f.write_str("InvalidRecord"),
+            ErrorKind::InvalidEntropy => f.write_str("InvalidEntropy"),
         }
     }
 }
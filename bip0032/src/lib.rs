@@ -46,7 +46,7 @@
 //! let xprv = child
 //!     .encode_with(Version::XPRV)
 //!     .unwrap()
-//!     .to_string();
+//!     .to_zeroizing_string();
 //! ```
 //!
 //! 2. Private parent key -> public child key.
@@ -97,15 +97,36 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+pub mod address;
+mod base64;
+pub mod bsms;
 pub mod curve;
+pub mod descriptor;
 mod error;
+pub mod multisig;
 mod path;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod taproot;
+pub mod wif;
 mod xkey;
 
+#[cfg(feature = "serde")]
+pub use self::serde_impls::ExposedPrivateKey;
 #[cfg(feature = "slip10")]
-pub use self::xkey::slip10;
+pub use self::xkey::{slip10, slip13};
 pub use self::{
     error::*,
-    path::{ChildNumber, DerivationPath, HardenedChildNumber, HardenedDerivationPath},
-    xkey::{ExtendedKeyPayload, ExtendedPrivateKey, ExtendedPublicKey, KnownVersion, Version},
+    path::{
+        Bip44Path, Bip45Path, Bip48Path, Bip48ScriptType, Bip87Path, Change, ChildNumber, CoinType,
+        DerivationPath, HardenedChildNumber, HardenedDerivationPath, PathTemplate, Purpose,
+        Wildcard,
+    },
+    xkey::{
+        AccountDiscovery, AccountKeychain, AccountScan, ChainScan, ChainState, DerivationCache,
+        ExtendedKeyPayload, ExtendedPrivateKey, ExtendedPublicKey, KeyOrigin, KeyUsage,
+        KeychainState, KnownVersion, NetworkKind, OriginKey, ScriptType, Version, VersionInfo,
+        VersionRegistry, VersionedPublicKey, bip85, convert_version, convert_version_cross_network,
+        slip21,
+    },
 };
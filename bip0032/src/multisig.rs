@@ -0,0 +1,281 @@
+//! Multisig scripts of secp256k1 cosigner keys, with the public keys sorted as in
+//! [BIP-0067](https://github.com/bitcoin/bips/blob/master/bip-0067.mediawiki).
+//!
+//! Cosigners are usually account level keys of [`Bip48Path`](crate::Bip48Path),
+//! [`Bip87Path`](crate::Bip87Path) or [`Bip45Path`](crate::Bip45Path) paths. Each address derives
+//! every cosigner key at the same `/change/index` and pays to a `k`-of-`n` `OP_CHECKMULTISIG`
+//! script of the sorted keys.
+//!
+//! ```rust
+//! use bip0032::{
+//!     Bip48Path, Bip48ScriptType, Change, CoinType, DerivationPath, ExtendedPrivateKey,
+//!     address::Network,
+//!     curve::secp256k1::*,
+//!     multisig::MultisigPolicy,
+//! };
+//!
+//! let account = Bip48Path::new(CoinType::BITCOIN, 0, Bip48ScriptType::P2wsh);
+//! let path = DerivationPath::from(account.account_path().unwrap());
+//! let cosigners = [[0x01; 32], [0x02; 32], [0x03; 32]]
+//!     .map(|seed| {
+//!         let master = ExtendedPrivateKey::<Secp256k1Curve<K256Backend>>::new(&seed).unwrap();
+//!         master.derive_path(&path).unwrap().public_key()
+//!     })
+//!     .to_vec();
+//!
+//! let policy = MultisigPolicy::new(2, cosigners, account.script_type().address_type()).unwrap();
+//! let address = policy.address(Network::Bitcoin, Change::External, 0).unwrap();
+//! assert!(address.to_string().starts_with("bc1q"));
+//!
+//! // the derived keys keep the origins of the cosigners
+//! let keys = policy.derive_keys(Change::External, 0).unwrap();
+//! assert_eq!(keys[0].origin().unwrap().path().to_string(), "m/48'/0'/0'/2'/0/0");
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    address::{Address, AddressType, Network, p2wsh_script},
+    curve::secp256k1::*,
+    error::{Error, ErrorKind, Result},
+    path::{Change, ChildNumber, DerivationPath},
+    xkey::ExtendedPublicKey,
+};
+
+/// Maximum number of public keys of a multisig script, the consensus limit of `OP_CHECKMULTISIG`.
+///
+/// P2SH scripts are further limited to [`MAX_P2SH_PUBLIC_KEYS`] by the size of the redeem script.
+pub const MAX_PUBLIC_KEYS: usize = 20;
+
+/// Maximum number of public keys of a P2SH multisig script, whose redeem script is at most 520
+/// bytes.
+pub const MAX_P2SH_PUBLIC_KEYS: usize = 15;
+
+/// `OP_CHECKMULTISIG`
+const OP_CHECKMULTISIG: u8 = 0xAE;
+
+/// A `k`-of-`n` multisig script: `OP_k <key_1> ... <key_n> OP_n OP_CHECKMULTISIG`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MultisigScript {
+    threshold: usize,
+    public_keys: Vec<[u8; 33]>,
+}
+
+impl MultisigScript {
+    /// Creates a multisig script over compressed public keys, in the given order.
+    pub fn new(threshold: usize, public_keys: Vec<[u8; 33]>) -> Result<Self> {
+        check_threshold(threshold, public_keys.len())?;
+        Ok(Self { threshold, public_keys })
+    }
+
+    /// Creates a multisig script over compressed public keys, sorted as in BIP-0067.
+    pub fn sorted(threshold: usize, mut public_keys: Vec<[u8; 33]>) -> Result<Self> {
+        sort_public_keys(&mut public_keys);
+        Self::new(threshold, public_keys)
+    }
+
+    /// Returns the number of required signatures.
+    pub const fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns the public keys in script order.
+    pub fn public_keys(&self) -> &[[u8; 33]] {
+        &self.public_keys
+    }
+
+    /// Returns the serialized script, used as the redeem script of P2SH and the witness script
+    /// of P2WSH and P2SH-P2WSH.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut script = Vec::with_capacity(3 + self.public_keys.len() * 34 + 2);
+        push_small_int(&mut script, self.threshold);
+        for public_key in &self.public_keys {
+            script.push(0x21);
+            script.extend_from_slice(public_key);
+        }
+        push_small_int(&mut script, self.public_keys.len());
+        script.push(OP_CHECKMULTISIG);
+        script
+    }
+
+    /// Returns the redeem script of a P2SH or P2SH-P2WSH output of this script, or `None` for
+    /// P2WSH outputs.
+    pub fn redeem_script(&self, address_type: AddressType) -> Result<Option<Vec<u8>>> {
+        Ok(match check_script_type(address_type)? {
+            AddressType::P2sh => Some(self.to_bytes()),
+            AddressType::P2shP2wsh => {
+                Some(p2wsh_script(&Sha256::digest(self.to_bytes()).into()).to_vec())
+            },
+            _ => None,
+        })
+    }
+
+    /// Returns the witness script of a P2WSH or P2SH-P2WSH output of this script, or `None` for
+    /// P2SH outputs.
+    pub fn witness_script(&self, address_type: AddressType) -> Result<Option<Vec<u8>>> {
+        Ok(match check_script_type(address_type)? {
+            AddressType::P2sh => None,
+            _ => Some(self.to_bytes()),
+        })
+    }
+
+    /// Returns the address of this script for the given network and script address type.
+    pub fn to_address(&self, network: Network, address_type: AddressType) -> Result<Address> {
+        Address::from_script(&self.to_bytes(), network, check_script_type(address_type)?)
+    }
+
+    /// Returns the output script (`scriptPubKey`) of this script for the given script address
+    /// type.
+    pub fn script_pubkey(&self, address_type: AddressType) -> Result<Vec<u8>> {
+        // output scripts do not depend on the network
+        Ok(self.to_address(Network::Bitcoin, address_type)?.script_pubkey())
+    }
+}
+
+/// Sorts compressed public keys lexicographically, as in BIP-0067.
+pub fn sort_public_keys(public_keys: &mut [[u8; 33]]) {
+    public_keys.sort_unstable();
+}
+
+/// A `k`-of-`n` multisig policy over cosigner extended public keys and a script address type.
+pub struct MultisigPolicy<B: Secp256k1Backend> {
+    threshold: usize,
+    cosigners: Vec<ExtendedPublicKey<Secp256k1Curve<B>>>,
+    address_type: AddressType,
+}
+
+impl<B: Secp256k1Backend> Clone for MultisigPolicy<B> {
+    fn clone(&self) -> Self {
+        Self {
+            threshold: self.threshold,
+            cosigners: self.cosigners.clone(),
+            address_type: self.address_type,
+        }
+    }
+}
+
+impl<B: Secp256k1Backend> fmt::Debug for MultisigPolicy<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultisigPolicy")
+            .field("threshold", &self.threshold)
+            .field("cosigners", &self.cosigners)
+            .field("address_type", &self.address_type)
+            .finish()
+    }
+}
+
+impl<B: Secp256k1Backend> MultisigPolicy<B> {
+    /// Creates a multisig policy.
+    ///
+    /// Cosigner keys must be distinct, at most [`MAX_P2SH_PUBLIC_KEYS`] for P2SH, and
+    /// `address_type` must be a script address type.
+    pub fn new(
+        threshold: usize,
+        cosigners: Vec<ExtendedPublicKey<Secp256k1Curve<B>>>,
+        address_type: AddressType,
+    ) -> Result<Self> {
+        check_threshold(threshold, cosigners.len())?;
+        if address_type == AddressType::P2sh && cosigners.len() > MAX_P2SH_PUBLIC_KEYS {
+            return Err(Error::new(ErrorKind::InvalidScript, "too many keys for a P2SH script")
+                .with_context("keys", cosigners.len())
+                .with_context("max_keys", MAX_P2SH_PUBLIC_KEYS));
+        }
+        check_script_type(address_type)?;
+
+        let mut public_keys = cosigners.iter().map(ExtendedPublicKey::to_bytes).collect::<Vec<_>>();
+        sort_public_keys(&mut public_keys);
+        if public_keys.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(Error::new(ErrorKind::InvalidScript, "duplicate cosigner key"));
+        }
+
+        Ok(Self { threshold, cosigners, address_type })
+    }
+
+    /// Returns the number of required signatures.
+    pub const fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns the cosigner keys in the given order.
+    pub fn cosigners(&self) -> &[ExtendedPublicKey<Secp256k1Curve<B>>] {
+        &self.cosigners
+    }
+
+    /// Returns the script address type.
+    pub const fn address_type(&self) -> AddressType {
+        self.address_type
+    }
+
+    /// Derives every cosigner key at `/change/index`, sorted as in BIP-0067.
+    ///
+    /// The origins of the cosigner keys are kept, e.g. for the key paths of a PSBT.
+    pub fn derive_keys(
+        &self,
+        change: Change,
+        index: u32,
+    ) -> Result<Vec<ExtendedPublicKey<Secp256k1Curve<B>>>> {
+        let path = DerivationPath::from_iter([
+            ChildNumber::new(change.index(), false)?,
+            ChildNumber::new(index, false)?,
+        ]);
+        let mut keys = self
+            .cosigners
+            .iter()
+            .map(|cosigner| cosigner.derive_path(&path))
+            .collect::<Result<Vec<_>>>()?;
+        keys.sort_by_cached_key(ExtendedPublicKey::to_bytes);
+        Ok(keys)
+    }
+
+    /// Returns the multisig script at `/change/index`.
+    pub fn script(&self, change: Change, index: u32) -> Result<MultisigScript> {
+        let public_keys = self
+            .derive_keys(change, index)?
+            .iter()
+            .map(ExtendedPublicKey::to_bytes)
+            .collect::<Vec<_>>();
+        MultisigScript::new(self.threshold, public_keys)
+    }
+
+    /// Returns the address at `/change/index` for the given network.
+    pub fn address(&self, network: Network, change: Change, index: u32) -> Result<Address> {
+        self.script(change, index)?.to_address(network, self.address_type)
+    }
+}
+
+fn check_threshold(threshold: usize, keys: usize) -> Result<()> {
+    if keys == 0 || keys > MAX_PUBLIC_KEYS {
+        return Err(Error::new(ErrorKind::InvalidScript, "invalid number of multisig keys")
+            .with_context("keys", keys)
+            .with_context("max_keys", MAX_PUBLIC_KEYS));
+    }
+    if threshold == 0 || threshold > keys {
+        return Err(Error::new(ErrorKind::InvalidScript, "invalid multisig threshold")
+            .with_context("threshold", threshold)
+            .with_context("keys", keys));
+    }
+    Ok(())
+}
+
+fn check_script_type(address_type: AddressType) -> Result<AddressType> {
+    if !address_type.is_script() {
+        return Err(Error::new(ErrorKind::InvalidScript, "address type requires a public key")
+            .with_context("address_type", format_args!("{address_type:?}")));
+    }
+    Ok(address_type)
+}
+
+/// Pushes a number of at most [`MAX_PUBLIC_KEYS`]: `OP_1` ~ `OP_16`, or a one byte push above.
+fn push_small_int(script: &mut Vec<u8>, n: usize) {
+    debug_assert!((1..=MAX_PUBLIC_KEYS).contains(&n));
+    if n <= 16 {
+        // OP_1 is 0x51
+        script.push(0x50 + n as u8);
+    } else {
+        script.extend_from_slice(&[0x01, n as u8]);
+    }
+}
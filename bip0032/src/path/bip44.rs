@@ -0,0 +1,316 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use super::{ChildNumber, CoinType, DerivationPath, HardenedDerivationPath};
+use crate::{Error, ErrorKind, Result};
+
+/// The [BIP-0043](https://github.com/bitcoin/bips/blob/master/bip-0043.mediawiki) purpose level
+/// of single-signature account paths.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Purpose {
+    /// BIP-0044, legacy P2PKH (`44'`).
+    Bip44,
+    /// BIP-0049, nested segwit P2SH-P2WPKH (`49'`).
+    Bip49,
+    /// BIP-0084, native segwit P2WPKH (`84'`).
+    Bip84,
+    /// BIP-0086, taproot key-path P2TR (`86'`).
+    Bip86,
+}
+
+impl Purpose {
+    /// Returns the purpose index (without hardening bit).
+    pub const fn index(self) -> u32 {
+        match self {
+            Self::Bip44 => 44,
+            Self::Bip49 => 49,
+            Self::Bip84 => 84,
+            Self::Bip86 => 86,
+        }
+    }
+
+    /// Returns the purpose with the given index, if known.
+    pub const fn from_index(index: u32) -> Option<Self> {
+        match index {
+            44 => Some(Self::Bip44),
+            49 => Some(Self::Bip49),
+            84 => Some(Self::Bip84),
+            86 => Some(Self::Bip86),
+            _ => None,
+        }
+    }
+}
+
+/// The change level of BIP-0044 style paths.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Change {
+    /// External chain for receiving addresses (`0`).
+    External,
+    /// Internal chain for change addresses (`1`).
+    Internal,
+}
+
+impl Change {
+    /// Returns the change index.
+    pub const fn index(self) -> u32 {
+        match self {
+            Self::External => 0,
+            Self::Internal => 1,
+        }
+    }
+
+    /// Returns the change level with the given index, if valid.
+    pub const fn from_index(index: u32) -> Option<Self> {
+        match index {
+            0 => Some(Self::External),
+            1 => Some(Self::Internal),
+            _ => None,
+        }
+    }
+}
+
+/// A typed BIP-0044 style path `m/purpose'/coin_type'/account'[/change[/address_index]]`.
+///
+/// ```
+/// use bip0032::{Bip44Path, Change, CoinType, Purpose};
+///
+/// let path = Bip44Path::new(Purpose::Bip84, CoinType::BITCOIN, 0)
+///     .change(Change::External)
+///     .index(5)
+///     .to_derivation_path()
+///     .unwrap();
+/// assert_eq!(path.to_string(), "m/84'/0'/0'/0/5");
+///
+/// let parsed = Bip44Path::try_from(&path).unwrap();
+/// assert_eq!(parsed.purpose(), Purpose::Bip84);
+/// assert_eq!(parsed.address_index(), Some(5));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Bip44Path {
+    purpose: Purpose,
+    coin_type: CoinType,
+    account: u32,
+    change: Option<Change>,
+    index: Option<u32>,
+}
+
+impl Bip44Path {
+    /// Creates an account level path `m/purpose'/coin_type'/account'`.
+    pub const fn new(purpose: Purpose, coin_type: CoinType, account: u32) -> Self {
+        Self { purpose, coin_type, account, change: None, index: None }
+    }
+
+    /// Sets the change level.
+    pub const fn change(mut self, change: Change) -> Self {
+        self.change = Some(change);
+        self
+    }
+
+    /// Sets the address index level, which requires the change level.
+    pub const fn index(mut self, index: u32) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Returns the purpose.
+    pub const fn purpose(&self) -> Purpose {
+        self.purpose
+    }
+
+    /// Returns the coin type.
+    pub const fn coin_type(&self) -> CoinType {
+        self.coin_type
+    }
+
+    /// Returns the account index (without hardening bit).
+    pub const fn account(&self) -> u32 {
+        self.account
+    }
+
+    /// Returns the change level, if set.
+    pub const fn change_level(&self) -> Option<Change> {
+        self.change
+    }
+
+    /// Returns the address index, if set.
+    pub const fn address_index(&self) -> Option<u32> {
+        self.index
+    }
+
+    /// Returns the hardened account level path `m/purpose'/coin_type'/account'`.
+    pub fn account_path(&self) -> Result<HardenedDerivationPath> {
+        let children = [
+            ChildNumber::new(self.purpose.index(), true)?,
+            ChildNumber::new(self.coin_type.index(), true)?,
+            ChildNumber::new(self.account, true)?,
+        ];
+        HardenedDerivationPath::try_from(DerivationPath::from_iter(children))
+    }
+
+    /// Returns the full derivation path, including the change and address index levels if set.
+    pub fn to_derivation_path(&self) -> Result<DerivationPath> {
+        let mut path = DerivationPath::from(self.account_path()?);
+        match (self.change, self.index) {
+            (Some(change), index) => {
+                path.extend([ChildNumber::new(change.index(), false)?]);
+                if let Some(index) = index {
+                    path.extend([ChildNumber::new(index, false)?]);
+                }
+            },
+            (None, Some(index)) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidPath,
+                    "address index requires a change level",
+                )
+                .with_context("child_index", index));
+            },
+            (None, None) => {},
+        }
+        Ok(path)
+    }
+
+    /// Returns the paths of the given address indices on the given chain of this account.
+    pub fn addresses(
+        &self,
+        change: Change,
+        indices: impl IntoIterator<Item = u32>,
+    ) -> Result<Vec<DerivationPath>> {
+        indices
+            .into_iter()
+            .map(|i| self.change(change).index(i).to_derivation_path())
+            .collect()
+    }
+}
+
+impl TryFrom<&DerivationPath> for Bip44Path {
+    type Error = Error;
+
+    /// Classifies a derivation path with 3 to 5 levels into its BIP-0044 style components.
+    fn try_from(path: &DerivationPath) -> Result<Self> {
+        let children = path.children();
+        if !(3..=5).contains(&children.len()) {
+            return Err(Error::new(ErrorKind::InvalidPath, "expected 3 to 5 path levels")
+                .with_context("path", path));
+        }
+
+        if let Some(child) = children[..3].iter().find(|child| !child.is_hardened()) {
+            return Err(Error::new(ErrorKind::InvalidPath, "expected hardened account path")
+                .with_context("child_index", child.index()));
+        }
+        if let Some(child) = children[3..].iter().find(|child| child.is_hardened()) {
+            return Err(Error::new(ErrorKind::InvalidPath, "expected unhardened change and index")
+                .with_context("child_index", child.index()));
+        }
+
+        let purpose = Purpose::from_index(children[0].index()).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidPath, "unknown purpose")
+                .with_context("purpose", children[0].index())
+        })?;
+        let coin_type = CoinType::new(children[1].index())?;
+        let account = children[2].index();
+        let change = children
+            .get(3)
+            .map(|child| {
+                Change::from_index(child.index()).ok_or_else(|| {
+                    Error::new(ErrorKind::InvalidPath, "change must be 0 or 1")
+                        .with_context("change", child.index())
+                })
+            })
+            .transpose()?;
+        let index = children.get(4).map(|child| child.index());
+
+        Ok(Self { purpose, coin_type, account, change, index })
+    }
+}
+
+impl TryFrom<DerivationPath> for Bip44Path {
+    type Error = Error;
+
+    fn try_from(path: DerivationPath) -> Result<Self> {
+        Self::try_from(&path)
+    }
+}
+
+impl FromStr for Bip44Path {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        Self::try_from(&path.parse::<DerivationPath>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_paths() {
+        let account = Bip44Path::new(Purpose::Bip44, CoinType::ETHEREUM, 0);
+        assert_eq!(account.account_path().unwrap().to_string(), "m/44'/60'/0'");
+        assert_eq!(account.to_derivation_path().unwrap().to_string(), "m/44'/60'/0'");
+
+        let path = account.change(Change::Internal).to_derivation_path().unwrap();
+        assert_eq!(path.to_string(), "m/44'/60'/0'/1");
+
+        let path = Bip44Path::new(Purpose::Bip86, CoinType::TESTNET, 3)
+            .change(Change::External)
+            .index(7)
+            .to_derivation_path()
+            .unwrap();
+        assert_eq!(path.to_string(), "m/86'/1'/3'/0/7");
+    }
+
+    #[test]
+    fn build_address_paths() {
+        let account = Bip44Path::new(Purpose::Bip84, CoinType::BITCOIN, 0);
+        let paths = account.addresses(Change::External, 0..2).unwrap();
+        assert_eq!(paths[0].to_string(), "m/84'/0'/0'/0/0");
+        assert_eq!(paths[1].to_string(), "m/84'/0'/0'/0/1");
+    }
+
+    #[test]
+    fn classify_paths() {
+        let path = "m/49h/2'/1'/1/9".parse::<Bip44Path>().unwrap();
+        assert_eq!(path.purpose(), Purpose::Bip49);
+        assert_eq!(path.coin_type(), CoinType::LITECOIN);
+        assert_eq!(path.account(), 1);
+        assert_eq!(path.change_level(), Some(Change::Internal));
+        assert_eq!(path.address_index(), Some(9));
+
+        let path = "m/84'/0'/0'".parse::<Bip44Path>().unwrap();
+        assert_eq!(path, Bip44Path::new(Purpose::Bip84, CoinType::BITCOIN, 0));
+    }
+
+    #[test]
+    fn error_invalid_builders() {
+        let err = Bip44Path::new(Purpose::Bip44, CoinType::BITCOIN, 0x8000_0000)
+            .to_derivation_path()
+            .unwrap_err();
+        assert_eq!(err.message(), "child index must be less than 2^31");
+
+        let err = Bip44Path::new(Purpose::Bip44, CoinType::BITCOIN, 0)
+            .index(0)
+            .to_derivation_path()
+            .unwrap_err();
+        assert_eq!(err.message(), "address index requires a change level");
+    }
+
+    #[test]
+    fn error_classify() {
+        let cases = [
+            ("m/84'/0'", "expected 3 to 5 path levels"),
+            ("m/84'/0'/0'/0/0/0", "expected 3 to 5 path levels"),
+            ("m/84'/0/0'", "expected hardened account path"),
+            ("m/84'/0'/0'/0'", "expected unhardened change and index"),
+            ("m/45'/0'/0'", "unknown purpose"),
+            ("m/84'/0'/0'/2", "change must be 0 or 1"),
+        ];
+
+        for (path, message) in cases {
+            let err = path.parse::<Bip44Path>().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidPath);
+            assert_eq!(err.message(), message, "{path}");
+        }
+    }
+}
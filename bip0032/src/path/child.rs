@@ -3,13 +3,13 @@ use core::{fmt, str::FromStr};
 use crate::{Error, ErrorKind, Result};
 
 /// A BIP32 child number with optional hardened flag.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct ChildNumber(u32);
 
 impl ChildNumber {
     // normal child keys: 0x0000_0000 ~ 0x7FFF_FFFF
     // hardened child keys: 0x8000_0000 ~ 0xFFFF_FFFF
-    const HARDENED_OFFSET: u32 = 0x8000_0000;
+    pub(crate) const HARDENED_OFFSET: u32 = 0x8000_0000;
 
     /// Creates a child number from an index and hardened flag.
     pub fn new(index: u32, hardened: bool) -> Result<Self> {
@@ -62,6 +62,12 @@ impl FromStr for ChildNumber {
     }
 }
 
+impl From<u32> for ChildNumber {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
 impl From<ChildNumber> for u32 {
     fn from(value: ChildNumber) -> Self {
         value.0
@@ -75,7 +81,7 @@ impl From<HardenedChildNumber> for ChildNumber {
 }
 
 /// A hardened-only child number.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct HardenedChildNumber(ChildNumber);
 
 impl HardenedChildNumber {
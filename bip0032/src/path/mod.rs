@@ -8,9 +8,19 @@ use std::vec;
 
 use crate::{Error, ErrorKind, Result};
 
+mod bip44;
 mod child;
-
-pub use child::{ChildNumber, HardenedChildNumber};
+mod multisig;
+mod slip44;
+mod template;
+
+pub use self::{
+    bip44::{Bip44Path, Change, Purpose},
+    child::{ChildNumber, HardenedChildNumber},
+    multisig::{Bip45Path, Bip48Path, Bip48ScriptType, Bip87Path},
+    slip44::CoinType,
+    template::{PathTemplate, Wildcard},
+};
 
 /// A parsed BIP32 derivation path.
 ///
@@ -87,28 +97,10 @@ impl FromStr for DerivationPath {
     type Err = Error;
 
     fn from_str(path: &str) -> Result<Self> {
-        if path.is_empty() {
-            return Err(Error::new(ErrorKind::InvalidPath, "derivation path is empty")
-                .with_context("path", path));
-        }
-
-        if path == "m" || path == "M" {
+        let Some(rest) = strip_path_prefix(path)? else {
             return Ok(Self::default());
-        }
-
-        let rest = if let Some(stripped) = path.strip_prefix("m/") {
-            stripped
-        } else if let Some(stripped) = path.strip_prefix("M/") {
-            stripped
-        } else {
-            path
         };
 
-        if rest.is_empty() {
-            return Err(Error::new(ErrorKind::InvalidPath, "empty path component")
-                .with_context("path", path));
-        }
-
         let mut children =
             Vec::with_capacity(rest.as_bytes().iter().filter(|&&b| b == b'/').count() + 1);
 
@@ -124,6 +116,34 @@ impl FromStr for DerivationPath {
     }
 }
 
+/// Strips the optional `m/` prefix of a path, returning `None` for the root path.
+fn strip_path_prefix(path: &str) -> Result<Option<&str>> {
+    if path.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidPath, "derivation path is empty")
+            .with_context("path", path));
+    }
+
+    if path == "m" || path == "M" {
+        return Ok(None);
+    }
+
+    let rest = if let Some(stripped) = path.strip_prefix("m/") {
+        stripped
+    } else if let Some(stripped) = path.strip_prefix("M/") {
+        stripped
+    } else {
+        path
+    };
+
+    if rest.is_empty() {
+        return Err(
+            Error::new(ErrorKind::InvalidPath, "empty path component").with_context("path", path)
+        );
+    }
+
+    Ok(Some(rest))
+}
+
 impl From<HardenedDerivationPath> for DerivationPath {
     fn from(value: HardenedDerivationPath) -> Self {
         value.inner
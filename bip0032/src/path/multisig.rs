@@ -0,0 +1,529 @@
+use core::str::FromStr;
+
+use super::{Change, ChildNumber, CoinType, DerivationPath, HardenedDerivationPath};
+use crate::{
+    Error, ErrorKind, Result,
+    address::AddressType,
+    xkey::{KnownVersion, ScriptType},
+};
+
+/// A typed [BIP-0045](https://github.com/bitcoin/bips/blob/master/bip-0045.mediawiki) path
+/// `m/45'/cosigner_index[/change[/address_index]]`.
+///
+/// ```
+/// use bip0032::{Bip45Path, Change};
+///
+/// let path = Bip45Path::new(2).change(Change::External).index(5).to_derivation_path().unwrap();
+/// assert_eq!(path.to_string(), "m/45'/2/0/5");
+/// assert_eq!(Bip45Path::try_from(&path).unwrap().cosigner_index(), 2);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Bip45Path {
+    cosigner_index: u32,
+    change: Option<Change>,
+    index: Option<u32>,
+}
+
+impl Bip45Path {
+    /// The purpose index of BIP-0045 paths (without hardening bit).
+    pub const PURPOSE: u32 = 45;
+
+    /// Creates a cosigner level path `m/45'/cosigner_index`.
+    pub const fn new(cosigner_index: u32) -> Self {
+        Self { cosigner_index, change: None, index: None }
+    }
+
+    /// Sets the change level.
+    pub const fn change(mut self, change: Change) -> Self {
+        self.change = Some(change);
+        self
+    }
+
+    /// Sets the address index level, which requires the change level.
+    pub const fn index(mut self, index: u32) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Returns the cosigner index.
+    pub const fn cosigner_index(&self) -> u32 {
+        self.cosigner_index
+    }
+
+    /// Returns the change level, if set.
+    pub const fn change_level(&self) -> Option<Change> {
+        self.change
+    }
+
+    /// Returns the address index, if set.
+    pub const fn address_index(&self) -> Option<u32> {
+        self.index
+    }
+
+    /// Returns the hardened purpose level path `m/45'`, shared by all cosigners.
+    pub fn purpose_path() -> Result<HardenedDerivationPath> {
+        HardenedDerivationPath::try_from(DerivationPath::from_iter([ChildNumber::new(
+            Self::PURPOSE,
+            true,
+        )?]))
+    }
+
+    /// Returns the full derivation path, including the change and address index levels if set.
+    pub fn to_derivation_path(&self) -> Result<DerivationPath> {
+        let mut path = DerivationPath::from(Self::purpose_path()?);
+        path.extend([ChildNumber::new(self.cosigner_index, false)?]);
+        extend_change_index(&mut path, self.change, self.index)?;
+        Ok(path)
+    }
+}
+
+impl TryFrom<&DerivationPath> for Bip45Path {
+    type Error = Error;
+
+    /// Classifies a derivation path with 2 to 4 levels into its BIP-0045 components.
+    fn try_from(path: &DerivationPath) -> Result<Self> {
+        let children = path.children();
+        if !(2..=4).contains(&children.len()) {
+            return Err(Error::new(ErrorKind::InvalidPath, "expected 2 to 4 path levels")
+                .with_context("path", path));
+        }
+        if !children[0].is_hardened() {
+            return Err(Error::new(ErrorKind::InvalidPath, "expected hardened purpose")
+                .with_context("child_index", children[0].index()));
+        }
+        check_purpose(children[0], Self::PURPOSE)?;
+        if let Some(child) = children[1..].iter().find(|child| child.is_hardened()) {
+            return Err(Error::new(
+                ErrorKind::InvalidPath,
+                "expected unhardened cosigner, change and index",
+            )
+            .with_context("child_index", child.index()));
+        }
+
+        let (change, index) = parse_change_index(&children[2..])?;
+        Ok(Self { cosigner_index: children[1].index(), change, index })
+    }
+}
+
+/// The script type level of BIP-0048 paths.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Bip48ScriptType {
+    /// Nested segwit multisig P2SH-P2WSH (`1'`).
+    P2shP2wsh,
+    /// Native segwit multisig P2WSH (`2'`).
+    P2wsh,
+}
+
+impl Bip48ScriptType {
+    /// Returns the script type index (without hardening bit).
+    pub const fn index(self) -> u32 {
+        match self {
+            Self::P2shP2wsh => 1,
+            Self::P2wsh => 2,
+        }
+    }
+
+    /// Returns the script type with the given index, if known.
+    pub const fn from_index(index: u32) -> Option<Self> {
+        match index {
+            1 => Some(Self::P2shP2wsh),
+            2 => Some(Self::P2wsh),
+            _ => None,
+        }
+    }
+
+    /// Returns the script type of extended keys of this path type.
+    pub const fn script_type(self) -> ScriptType {
+        match self {
+            Self::P2shP2wsh => ScriptType::P2shP2wsh,
+            Self::P2wsh => ScriptType::P2wsh,
+        }
+    }
+
+    /// Returns the address type of outputs of this path type.
+    pub const fn address_type(self) -> AddressType {
+        match self {
+            Self::P2shP2wsh => AddressType::P2shP2wsh,
+            Self::P2wsh => AddressType::P2wsh,
+        }
+    }
+
+    /// Returns the script type intended by a known version, if it is a multisig version.
+    ///
+    /// `Ypub`/`Upub` map to P2SH-P2WSH and `Zpub`/`Vpub` to P2WSH.
+    pub fn from_known_version(version: KnownVersion) -> Option<Self> {
+        match version.info().script_type() {
+            ScriptType::P2shP2wsh => Some(Self::P2shP2wsh),
+            ScriptType::P2wsh => Some(Self::P2wsh),
+            ScriptType::P2pkh | ScriptType::P2shP2wpkh | ScriptType::P2wpkh => None,
+        }
+    }
+}
+
+/// A typed [BIP-0048](https://github.com/bitcoin/bips/blob/master/bip-0048.mediawiki) path
+/// `m/48'/coin_type'/account'/script_type'[/change[/address_index]]`.
+///
+/// ```
+/// use bip0032::{Bip48Path, Bip48ScriptType, Change, CoinType};
+///
+/// let path = Bip48Path::new(CoinType::BITCOIN, 0, Bip48ScriptType::P2wsh);
+/// assert_eq!(path.account_path().unwrap().to_string(), "m/48'/0'/0'/2'");
+///
+/// let path = path.change(Change::Internal).index(3).to_derivation_path().unwrap();
+/// assert_eq!(path.to_string(), "m/48'/0'/0'/2'/1/3");
+/// assert_eq!(Bip48Path::try_from(&path).unwrap().script_type(), Bip48ScriptType::P2wsh);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Bip48Path {
+    coin_type: CoinType,
+    account: u32,
+    script_type: Bip48ScriptType,
+    change: Option<Change>,
+    index: Option<u32>,
+}
+
+impl Bip48Path {
+    /// The purpose index of BIP-0048 paths (without hardening bit).
+    pub const PURPOSE: u32 = 48;
+
+    /// Creates an account level path `m/48'/coin_type'/account'/script_type'`.
+    pub const fn new(coin_type: CoinType, account: u32, script_type: Bip48ScriptType) -> Self {
+        Self { coin_type, account, script_type, change: None, index: None }
+    }
+
+    /// Sets the change level.
+    pub const fn change(mut self, change: Change) -> Self {
+        self.change = Some(change);
+        self
+    }
+
+    /// Sets the address index level, which requires the change level.
+    pub const fn index(mut self, index: u32) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Returns the coin type.
+    pub const fn coin_type(&self) -> CoinType {
+        self.coin_type
+    }
+
+    /// Returns the account index (without hardening bit).
+    pub const fn account(&self) -> u32 {
+        self.account
+    }
+
+    /// Returns the script type.
+    pub const fn script_type(&self) -> Bip48ScriptType {
+        self.script_type
+    }
+
+    /// Returns the change level, if set.
+    pub const fn change_level(&self) -> Option<Change> {
+        self.change
+    }
+
+    /// Returns the address index, if set.
+    pub const fn address_index(&self) -> Option<u32> {
+        self.index
+    }
+
+    /// Returns the hardened account level path `m/48'/coin_type'/account'/script_type'`.
+    pub fn account_path(&self) -> Result<HardenedDerivationPath> {
+        let children = [
+            ChildNumber::new(Self::PURPOSE, true)?,
+            ChildNumber::new(self.coin_type.index(), true)?,
+            ChildNumber::new(self.account, true)?,
+            ChildNumber::new(self.script_type.index(), true)?,
+        ];
+        HardenedDerivationPath::try_from(DerivationPath::from_iter(children))
+    }
+
+    /// Returns the full derivation path, including the change and address index levels if set.
+    pub fn to_derivation_path(&self) -> Result<DerivationPath> {
+        let mut path = DerivationPath::from(self.account_path()?);
+        extend_change_index(&mut path, self.change, self.index)?;
+        Ok(path)
+    }
+}
+
+impl TryFrom<&DerivationPath> for Bip48Path {
+    type Error = Error;
+
+    /// Classifies a derivation path with 4 to 6 levels into its BIP-0048 components.
+    fn try_from(path: &DerivationPath) -> Result<Self> {
+        let children = path.children();
+        if !(4..=6).contains(&children.len()) {
+            return Err(Error::new(ErrorKind::InvalidPath, "expected 4 to 6 path levels")
+                .with_context("path", path));
+        }
+        check_account_levels(&children[..4], &children[4..])?;
+        check_purpose(children[0], Self::PURPOSE)?;
+
+        let coin_type = CoinType::new(children[1].index())?;
+        let script_type = Bip48ScriptType::from_index(children[3].index()).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidPath, "unknown script type")
+                .with_context("script_type", children[3].index())
+        })?;
+        let (change, index) = parse_change_index(&children[4..])?;
+
+        Ok(Self { coin_type, account: children[2].index(), script_type, change, index })
+    }
+}
+
+/// A typed [BIP-0087](https://github.com/bitcoin/bips/blob/master/bip-0087.mediawiki) path
+/// `m/87'/coin_type'/account'[/change[/address_index]]`, for multisig accounts of any script type.
+///
+/// ```
+/// use bip0032::{Bip87Path, Change, CoinType};
+///
+/// let path = Bip87Path::new(CoinType::BITCOIN, 0)
+///     .change(Change::External)
+///     .index(1)
+///     .to_derivation_path()
+///     .unwrap();
+/// assert_eq!(path.to_string(), "m/87'/0'/0'/0/1");
+/// assert_eq!(Bip87Path::try_from(&path).unwrap().address_index(), Some(1));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Bip87Path {
+    coin_type: CoinType,
+    account: u32,
+    change: Option<Change>,
+    index: Option<u32>,
+}
+
+impl Bip87Path {
+    /// The purpose index of BIP-0087 paths (without hardening bit).
+    pub const PURPOSE: u32 = 87;
+
+    /// Creates an account level path `m/87'/coin_type'/account'`.
+    pub const fn new(coin_type: CoinType, account: u32) -> Self {
+        Self { coin_type, account, change: None, index: None }
+    }
+
+    /// Sets the change level.
+    pub const fn change(mut self, change: Change) -> Self {
+        self.change = Some(change);
+        self
+    }
+
+    /// Sets the address index level, which requires the change level.
+    pub const fn index(mut self, index: u32) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Returns the coin type.
+    pub const fn coin_type(&self) -> CoinType {
+        self.coin_type
+    }
+
+    /// Returns the account index (without hardening bit).
+    pub const fn account(&self) -> u32 {
+        self.account
+    }
+
+    /// Returns the change level, if set.
+    pub const fn change_level(&self) -> Option<Change> {
+        self.change
+    }
+
+    /// Returns the address index, if set.
+    pub const fn address_index(&self) -> Option<u32> {
+        self.index
+    }
+
+    /// Returns the hardened account level path `m/87'/coin_type'/account'`.
+    pub fn account_path(&self) -> Result<HardenedDerivationPath> {
+        let children = [
+            ChildNumber::new(Self::PURPOSE, true)?,
+            ChildNumber::new(self.coin_type.index(), true)?,
+            ChildNumber::new(self.account, true)?,
+        ];
+        HardenedDerivationPath::try_from(DerivationPath::from_iter(children))
+    }
+
+    /// Returns the full derivation path, including the change and address index levels if set.
+    pub fn to_derivation_path(&self) -> Result<DerivationPath> {
+        let mut path = DerivationPath::from(self.account_path()?);
+        extend_change_index(&mut path, self.change, self.index)?;
+        Ok(path)
+    }
+}
+
+impl TryFrom<&DerivationPath> for Bip87Path {
+    type Error = Error;
+
+    /// Classifies a derivation path with 3 to 5 levels into its BIP-0087 components.
+    fn try_from(path: &DerivationPath) -> Result<Self> {
+        let children = path.children();
+        if !(3..=5).contains(&children.len()) {
+            return Err(Error::new(ErrorKind::InvalidPath, "expected 3 to 5 path levels")
+                .with_context("path", path));
+        }
+        check_account_levels(&children[..3], &children[3..])?;
+        check_purpose(children[0], Self::PURPOSE)?;
+
+        let coin_type = CoinType::new(children[1].index())?;
+        let (change, index) = parse_change_index(&children[3..])?;
+
+        Ok(Self { coin_type, account: children[2].index(), change, index })
+    }
+}
+
+macro_rules! impl_path_conversions {
+    ($($path:ty),*) => {$(
+        impl TryFrom<DerivationPath> for $path {
+            type Error = Error;
+
+            fn try_from(path: DerivationPath) -> Result<Self> {
+                Self::try_from(&path)
+            }
+        }
+
+        impl FromStr for $path {
+            type Err = Error;
+
+            fn from_str(path: &str) -> Result<Self> {
+                Self::try_from(&path.parse::<DerivationPath>()?)
+            }
+        }
+    )*};
+}
+
+impl_path_conversions!(Bip45Path, Bip48Path, Bip87Path);
+
+fn check_purpose(child: ChildNumber, purpose: u32) -> Result<()> {
+    if child.index() != purpose {
+        return Err(Error::new(ErrorKind::InvalidPath, "unexpected purpose")
+            .with_context("purpose", child.index())
+            .with_context("expected", purpose));
+    }
+    Ok(())
+}
+
+fn check_account_levels(account: &[ChildNumber], rest: &[ChildNumber]) -> Result<()> {
+    if let Some(child) = account.iter().find(|child| !child.is_hardened()) {
+        return Err(Error::new(ErrorKind::InvalidPath, "expected hardened account path")
+            .with_context("child_index", child.index()));
+    }
+    if let Some(child) = rest.iter().find(|child| child.is_hardened()) {
+        return Err(Error::new(ErrorKind::InvalidPath, "expected unhardened change and index")
+            .with_context("child_index", child.index()));
+    }
+    Ok(())
+}
+
+fn parse_change_index(children: &[ChildNumber]) -> Result<(Option<Change>, Option<u32>)> {
+    let change = children
+        .first()
+        .map(|child| {
+            Change::from_index(child.index()).ok_or_else(|| {
+                Error::new(ErrorKind::InvalidPath, "change must be 0 or 1")
+                    .with_context("change", child.index())
+            })
+        })
+        .transpose()?;
+    Ok((change, children.get(1).map(|child| child.index())))
+}
+
+fn extend_change_index(
+    path: &mut DerivationPath,
+    change: Option<Change>,
+    index: Option<u32>,
+) -> Result<()> {
+    match (change, index) {
+        (Some(change), index) => {
+            path.extend([ChildNumber::new(change.index(), false)?]);
+            if let Some(index) = index {
+                path.extend([ChildNumber::new(index, false)?]);
+            }
+        },
+        (None, Some(index)) => {
+            return Err(Error::new(
+                ErrorKind::InvalidPath,
+                "address index requires a change level",
+            )
+            .with_context("child_index", index));
+        },
+        (None, None) => {},
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_paths() {
+        let path = Bip45Path::new(0).change(Change::Internal).to_derivation_path().unwrap();
+        assert_eq!(path.to_string(), "m/45'/0/1");
+        assert_eq!(Bip45Path::purpose_path().unwrap().to_string(), "m/45'");
+
+        let account = Bip48Path::new(CoinType::TESTNET, 1, Bip48ScriptType::P2shP2wsh);
+        assert_eq!(account.account_path().unwrap().to_string(), "m/48'/1'/1'/1'");
+        let paths: Vec<_> = (0..2)
+            .map(|i| account.change(Change::External).index(i).to_derivation_path().unwrap())
+            .collect();
+        assert_eq!(paths[1].to_string(), "m/48'/1'/1'/1'/0/1");
+
+        let path = Bip87Path::new(CoinType::BITCOIN, 2).to_derivation_path().unwrap();
+        assert_eq!(path.to_string(), "m/87'/0'/2'");
+    }
+
+    #[test]
+    fn classify_paths() {
+        let path = "m/48h/0h/0h/2h".parse::<Bip48Path>().unwrap();
+        assert_eq!(path, Bip48Path::new(CoinType::BITCOIN, 0, Bip48ScriptType::P2wsh));
+        assert_eq!(path.script_type().script_type(), ScriptType::P2wsh);
+
+        let path = "m/45'/3/1/8".parse::<Bip45Path>().unwrap();
+        assert_eq!(path.cosigner_index(), 3);
+        assert_eq!(path.change_level(), Some(Change::Internal));
+        assert_eq!(path.address_index(), Some(8));
+
+        let path = "m/87'/1'/0'/0".parse::<Bip87Path>().unwrap();
+        assert_eq!(path.coin_type(), CoinType::TESTNET);
+        assert_eq!(path.change_level(), Some(Change::External));
+        assert_eq!(path.address_index(), None);
+    }
+
+    #[test]
+    fn script_type_from_known_version() {
+        assert_eq!(
+            Bip48ScriptType::from_known_version(KnownVersion::ZpubWsh),
+            Some(Bip48ScriptType::P2wsh)
+        );
+        assert_eq!(
+            Bip48ScriptType::from_known_version(KnownVersion::YpubShWsh),
+            Some(Bip48ScriptType::P2shP2wsh)
+        );
+        assert_eq!(Bip48ScriptType::from_known_version(KnownVersion::Zpub), None);
+    }
+
+    #[test]
+    fn error_classify() {
+        let cases = [
+            ("m/48'/0'/0'", "expected 4 to 6 path levels"),
+            ("m/48'/0'/0/2'", "expected hardened account path"),
+            ("m/48'/0'/0'/2'/0'", "expected unhardened change and index"),
+            ("m/84'/0'/0'/2'", "unexpected purpose"),
+            ("m/48'/0'/0'/3'", "unknown script type"),
+            ("m/48'/0'/0'/2'/2", "change must be 0 or 1"),
+        ];
+        for (path, message) in cases {
+            let err = path.parse::<Bip48Path>().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidPath);
+            assert_eq!(err.message(), message, "{path}");
+        }
+
+        let err = "m/45'/0'".parse::<Bip45Path>().unwrap_err();
+        assert_eq!(err.message(), "expected unhardened cosigner, change and index");
+        let err = "m/45/0".parse::<Bip45Path>().unwrap_err();
+        assert_eq!(err.message(), "expected hardened purpose");
+        let err = "m/44'/0'/0'".parse::<Bip87Path>().unwrap_err();
+        assert_eq!(err.message(), "unexpected purpose");
+    }
+}
@@ -0,0 +1,163 @@
+use core::fmt;
+
+use crate::{Error, ErrorKind, Result};
+
+/// A registered [SLIP-0044](https://github.com/satoshilabs/slips/blob/master/slip-0044.md) coin
+/// type, used as the hardened second level of BIP-0044 style paths.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CoinType(u32);
+
+/// Known coin types as `(index, symbol, name)`, sorted by index.
+const COIN_TYPES: &[(u32, &str, &str)] = &[
+    (0, "BTC", "Bitcoin"),
+    (1, "", "Testnet (all coins)"),
+    (2, "LTC", "Litecoin"),
+    (3, "DOGE", "Dogecoin"),
+    (5, "DASH", "Dash"),
+    (42, "DCR", "Decred"),
+    (60, "ETH", "Ether"),
+    (61, "ETC", "Ether Classic"),
+    (118, "ATOM", "Atom"),
+    (128, "XMR", "Monero"),
+    (133, "ZEC", "Zcash"),
+    (144, "XRP", "Ripple"),
+    (145, "BCH", "Bitcoin Cash"),
+    (148, "XLM", "Stellar Lumens"),
+    (195, "TRX", "Tron"),
+    (354, "DOT", "Polkadot"),
+    (397, "NEAR", "NEAR Protocol"),
+    (434, "KSM", "Kusama"),
+    (501, "SOL", "Solana"),
+    (637, "APT", "Aptos"),
+    (714, "BNB", "Binance"),
+    (784, "SUI", "Sui"),
+    (1729, "XTZ", "Tezos"),
+    (1815, "ADA", "Cardano"),
+];
+
+impl CoinType {
+    /// Bitcoin (`0'`).
+    pub const BITCOIN: Self = Self(0);
+    /// Testnet for all coins (`1'`).
+    pub const TESTNET: Self = Self(1);
+    /// Litecoin (`2'`).
+    pub const LITECOIN: Self = Self(2);
+    /// Dogecoin (`3'`).
+    pub const DOGECOIN: Self = Self(3);
+    /// Dash (`5'`).
+    pub const DASH: Self = Self(5);
+    /// Decred (`42'`).
+    pub const DECRED: Self = Self(42);
+    /// Ethereum (`60'`).
+    pub const ETHEREUM: Self = Self(60);
+    /// Ethereum Classic (`61'`).
+    pub const ETHEREUM_CLASSIC: Self = Self(61);
+    /// Cosmos Hub (`118'`).
+    pub const COSMOS: Self = Self(118);
+    /// Monero (`128'`).
+    pub const MONERO: Self = Self(128);
+    /// Zcash (`133'`).
+    pub const ZCASH: Self = Self(133);
+    /// XRP Ledger (`144'`).
+    pub const RIPPLE: Self = Self(144);
+    /// Bitcoin Cash (`145'`).
+    pub const BITCOIN_CASH: Self = Self(145);
+    /// Stellar (`148'`).
+    pub const STELLAR: Self = Self(148);
+    /// Tron (`195'`).
+    pub const TRON: Self = Self(195);
+    /// Polkadot (`354'`).
+    pub const POLKADOT: Self = Self(354);
+    /// NEAR Protocol (`397'`).
+    pub const NEAR: Self = Self(397);
+    /// Kusama (`434'`).
+    pub const KUSAMA: Self = Self(434);
+    /// Solana (`501'`).
+    pub const SOLANA: Self = Self(501);
+    /// Aptos (`637'`).
+    pub const APTOS: Self = Self(637);
+    /// BNB Beacon Chain (`714'`).
+    pub const BINANCE: Self = Self(714);
+    /// Sui (`784'`).
+    pub const SUI: Self = Self(784);
+    /// Tezos (`1729'`).
+    pub const TEZOS: Self = Self(1729);
+    /// Cardano (`1815'`).
+    pub const CARDANO: Self = Self(1815);
+
+    /// Creates a coin type from its index, which does not need to be in the built-in table.
+    pub fn new(index: u32) -> Result<Self> {
+        if index >= 0x8000_0000 {
+            return Err(Error::new(ErrorKind::InvalidPath, "coin type must be less than 2^31")
+                .with_context("coin_type", index));
+        }
+        Ok(Self(index))
+    }
+
+    /// Looks up a coin type in the built-in table by its ticker symbol (case-insensitive).
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        COIN_TYPES
+            .iter()
+            .find(|(_, s, _)| !s.is_empty() && s.eq_ignore_ascii_case(symbol))
+            .map(|(index, _, _)| Self(*index))
+    }
+
+    /// Returns the coin type index (without hardening bit).
+    pub const fn index(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the ticker symbol, if this coin type is in the built-in table and has one.
+    pub fn symbol(self) -> Option<&'static str> {
+        self.entry().map(|(_, symbol, _)| *symbol).filter(|symbol| !symbol.is_empty())
+    }
+
+    /// Returns the coin name, if this coin type is in the built-in table.
+    pub fn name(self) -> Option<&'static str> {
+        self.entry().map(|(_, _, name)| *name)
+    }
+
+    fn entry(self) -> Option<&'static (u32, &'static str, &'static str)> {
+        COIN_TYPES
+            .binary_search_by_key(&self.0, |(index, _, _)| *index)
+            .ok()
+            .map(|i| &COIN_TYPES[i])
+    }
+}
+
+impl fmt::Display for CoinType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{} ({name})", self.0),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_sorted() {
+        assert!(COIN_TYPES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn lookup_names_and_symbols() {
+        assert_eq!(CoinType::ETHEREUM.name(), Some("Ether"));
+        assert_eq!(CoinType::ETHEREUM.symbol(), Some("ETH"));
+        assert_eq!(CoinType::TESTNET.symbol(), None);
+        assert_eq!(CoinType::from_symbol("btc"), Some(CoinType::BITCOIN));
+        assert_eq!(CoinType::from_symbol(""), None);
+        assert_eq!(CoinType::new(9999).unwrap().name(), None);
+        assert_eq!(CoinType::SOLANA.to_string(), "501 (Solana)");
+    }
+
+    #[test]
+    fn error_coin_type_too_large() {
+        let err = CoinType::new(0x8000_0000).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidPath);
+        assert_eq!(err.message(), "coin type must be less than 2^31");
+    }
+}
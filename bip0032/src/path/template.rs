@@ -0,0 +1,319 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::{fmt, ops::Range, str::FromStr};
+
+use super::{ChildNumber, DerivationPath, strip_path_prefix};
+use crate::{Error, ErrorKind, Result};
+
+/// A trailing wildcard in a [`PathTemplate`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Wildcard {
+    /// Unhardened wildcard (`*`).
+    Unhardened,
+    /// Hardened wildcard (`*'`, `*h` or `*H`).
+    Hardened,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Component {
+    Child(ChildNumber),
+    Multipath(Vec<ChildNumber>),
+}
+
+/// A derivation path template with an optional
+/// [BIP-0389](https://github.com/bitcoin/bips/blob/master/bip-0389.mediawiki) multipath tuple and
+/// an optional trailing wildcard, e.g. `m/84'/0'/0'/<0;1>/*`.
+///
+/// The parsing rules match [`DerivationPath`]: the `m/` prefix is optional and hardened
+/// components (including multipath tuple elements) accept the `'`, `h`, or `H` suffix.
+/// At most one multipath tuple is allowed, and it must have at least two distinct elements.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PathTemplate {
+    components: Vec<Component>,
+    wildcard: Option<Wildcard>,
+}
+
+impl PathTemplate {
+    /// Returns the trailing wildcard, if any.
+    pub const fn wildcard(&self) -> Option<Wildcard> {
+        self.wildcard
+    }
+
+    /// Returns true if this template contains a multipath tuple.
+    pub fn is_multipath(&self) -> bool {
+        self.components
+            .iter()
+            .any(|component| matches!(component, Component::Multipath(_)))
+    }
+
+    /// Returns the number of branches (the multipath tuple length, or 1).
+    pub fn branch_count(&self) -> usize {
+        self.components
+            .iter()
+            .find_map(|component| match component {
+                Component::Multipath(children) => Some(children.len()),
+                Component::Child(_) => None,
+            })
+            .unwrap_or(1)
+    }
+
+    /// Splits this template into one template per multipath branch.
+    ///
+    /// A template without multipath tuple yields itself.
+    pub fn branches(&self) -> Vec<PathTemplate> {
+        (0..self.branch_count())
+            .map(|branch| Self {
+                components: self
+                    .components
+                    .iter()
+                    .map(|component| match component {
+                        Component::Child(child) => Component::Child(*child),
+                        Component::Multipath(children) => Component::Child(children[branch]),
+                    })
+                    .collect(),
+                wildcard: self.wildcard,
+            })
+            .collect()
+    }
+
+    /// Returns the concrete path of a single-branch template at the given wildcard index.
+    ///
+    /// The index is ignored if this template has no wildcard.
+    pub fn to_path(&self, index: u32) -> Result<DerivationPath> {
+        let mut children = Vec::with_capacity(self.components.len() + 1);
+        for component in &self.components {
+            match component {
+                Component::Child(child) => children.push(*child),
+                Component::Multipath(_) => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidPath,
+                        "multipath template has no single path",
+                    )
+                    .with_context("template", self));
+                },
+            }
+        }
+
+        match self.wildcard {
+            Some(Wildcard::Unhardened) => children.push(ChildNumber::new(index, false)?),
+            Some(Wildcard::Hardened) => children.push(ChildNumber::new(index, true)?),
+            None => {},
+        }
+
+        Ok(DerivationPath::from(children))
+    }
+
+    /// Expands this template into concrete paths for every branch and every wildcard index in
+    /// the given range, ordered by branch first.
+    ///
+    /// A template without wildcard yields one path per branch.
+    pub fn expand(&self, indices: Range<u32>) -> Result<Vec<DerivationPath>> {
+        let indices = if self.wildcard.is_some() { indices } else { 0..1 };
+        let branches = self.branches();
+
+        let mut paths = Vec::with_capacity(branches.len() * indices.len());
+        for branch in &branches {
+            for index in indices.clone() {
+                paths.push(branch.to_path(index)?);
+            }
+        }
+        Ok(paths)
+    }
+}
+
+impl From<DerivationPath> for PathTemplate {
+    fn from(path: DerivationPath) -> Self {
+        Self { components: path.into_iter().map(Component::Child).collect(), wildcard: None }
+    }
+}
+
+impl fmt::Display for PathTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("m")?;
+
+        for component in &self.components {
+            match component {
+                Component::Child(child) => write!(f, "/{child}")?,
+                Component::Multipath(children) => {
+                    f.write_str("/<")?;
+                    for (i, child) in children.iter().enumerate() {
+                        if i > 0 {
+                            f.write_str(";")?;
+                        }
+                        write!(f, "{child}")?;
+                    }
+                    f.write_str(">")?;
+                },
+            }
+        }
+
+        match self.wildcard {
+            Some(Wildcard::Unhardened) => f.write_str("/*"),
+            Some(Wildcard::Hardened) => f.write_str("/*'"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for PathTemplate {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        let Some(rest) = strip_path_prefix(path)? else {
+            return Ok(Self::default());
+        };
+
+        let mut template = Self::default();
+        for part in rest.split('/') {
+            if template.wildcard.is_some() {
+                return Err(Error::new(
+                    ErrorKind::InvalidPath,
+                    "wildcard must be the last component",
+                )
+                .with_context("path", path));
+            }
+
+            match part {
+                "" => {
+                    return Err(Error::new(ErrorKind::InvalidPath, "empty path component")
+                        .with_context("path", path));
+                },
+                "*" => template.wildcard = Some(Wildcard::Unhardened),
+                "*'" | "*h" | "*H" => template.wildcard = Some(Wildcard::Hardened),
+                part => {
+                    let component = match part.strip_prefix('<').and_then(|p| p.strip_suffix('>')) {
+                        Some(tuple) => parse_multipath(tuple, path)?,
+                        None => Component::Child(part.parse()?),
+                    };
+                    if matches!(component, Component::Multipath(_)) && template.is_multipath() {
+                        return Err(Error::new(
+                            ErrorKind::InvalidPath,
+                            "multiple multipath tuples in path",
+                        )
+                        .with_context("path", path));
+                    }
+                    template.components.push(component);
+                },
+            }
+        }
+
+        Ok(template)
+    }
+}
+
+fn parse_multipath(tuple: &str, path: &str) -> Result<Component> {
+    let mut children: Vec<ChildNumber> = vec![];
+    for part in tuple.split(';') {
+        let child = part.parse()?;
+        if children.contains(&child) {
+            return Err(Error::new(ErrorKind::InvalidPath, "duplicate multipath tuple element")
+                .with_context("path", path));
+        }
+        children.push(child);
+    }
+
+    if children.len() < 2 {
+        return Err(Error::new(
+            ErrorKind::InvalidPath,
+            "multipath tuple needs at least two elements",
+        )
+        .with_context("path", path));
+    }
+
+    Ok(Component::Multipath(children))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_multipath_wildcard() {
+        let template = "m/84h/0'/0H/<0;1>/*".parse::<PathTemplate>().unwrap();
+        assert!(template.is_multipath());
+        assert_eq!(template.branch_count(), 2);
+        assert_eq!(template.wildcard(), Some(Wildcard::Unhardened));
+        assert_eq!(template.to_string(), "m/84'/0'/0'/<0;1>/*");
+    }
+
+    #[test]
+    fn parse_plain_path() {
+        let template = "0/1'".parse::<PathTemplate>().unwrap();
+        assert!(!template.is_multipath());
+        assert_eq!(template.wildcard(), None);
+        assert_eq!(template.to_string(), "m/0/1'");
+        assert_eq!(template, PathTemplate::from("m/0/1'".parse::<DerivationPath>().unwrap()));
+    }
+
+    #[test]
+    fn parse_hardened_wildcard() {
+        let template = "m/0/*h".parse::<PathTemplate>().unwrap();
+        assert_eq!(template.wildcard(), Some(Wildcard::Hardened));
+        assert_eq!(template.to_path(5).unwrap().to_string(), "m/0/5'");
+    }
+
+    #[test]
+    fn split_branches() {
+        let template = "m/48'/0'/<0';1h;2>/*".parse::<PathTemplate>().unwrap();
+        let branches = template.branches();
+        assert_eq!(branches.len(), 3);
+        assert_eq!(branches[0].to_string(), "m/48'/0'/0'/*");
+        assert_eq!(branches[1].to_string(), "m/48'/0'/1'/*");
+        assert_eq!(branches[2].to_string(), "m/48'/0'/2/*");
+    }
+
+    #[test]
+    fn expand_range() {
+        let template = "m/84'/0'/0'/<0;1>/*".parse::<PathTemplate>().unwrap();
+        let paths = template.expand(3..5).unwrap();
+        let paths = paths.iter().map(|path| path.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            ["m/84'/0'/0'/0/3", "m/84'/0'/0'/0/4", "m/84'/0'/0'/1/3", "m/84'/0'/0'/1/4",]
+        );
+
+        // templates without wildcard ignore the range
+        let template = "m/0/<1;2>".parse::<PathTemplate>().unwrap();
+        let paths = template.expand(0..10).unwrap();
+        let paths = paths.iter().map(|path| path.to_string()).collect::<Vec<_>>();
+        assert_eq!(paths, ["m/0/1", "m/0/2"]);
+    }
+
+    #[test]
+    fn error_to_path_multipath() {
+        let template = "m/<0;1>/*".parse::<PathTemplate>().unwrap();
+        let err = template.to_path(0).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidPath);
+        assert_eq!(err.message(), "multipath template has no single path");
+    }
+
+    #[test]
+    fn error_wildcard_index_too_large() {
+        let template = "m/0/*".parse::<PathTemplate>().unwrap();
+        let err = template.to_path(0x8000_0000).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidPath);
+        assert_eq!(err.message(), "child index must be less than 2^31");
+    }
+
+    #[test]
+    fn error_invalid_templates() {
+        let cases = [
+            ("", "derivation path is empty"),
+            ("m/", "empty path component"),
+            ("m/*/0", "wildcard must be the last component"),
+            ("m/<0;1>/<2;3>", "multiple multipath tuples in path"),
+            ("m/<0;0>", "duplicate multipath tuple element"),
+            ("m/<0>", "multipath tuple needs at least two elements"),
+            ("m/<0;>", "missing child index"),
+            ("m/<0;1", "invalid child index"),
+            ("m/'", "missing child index"),
+            ("m/2147483648", "child index must be less than 2^31"),
+        ];
+
+        for (template, message) in cases {
+            let err = template.parse::<PathTemplate>().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidPath);
+            assert_eq!(err.message(), message, "{template}");
+        }
+    }
+}
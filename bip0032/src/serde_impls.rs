@@ -0,0 +1,450 @@
+//! `serde` support.
+//!
+//! Human-readable formats (e.g. JSON) use the canonical strings of the types, binary formats
+//! (e.g. CBOR) use compact bytes:
+//!
+//! | Type                                          | Human-readable                    | Binary                                           |
+//! | --------------------------------------------- | --------------------------------- | ------------------------------------------------ |
+//! | [`ChildNumber`]                               | `"0'"`                            | `u32`                                            |
+//! | [`DerivationPath`]/[`HardenedDerivationPath`] | `"m/84'/0'/0'"`                   | 4 big-endian bytes per child                     |
+//! | [`KeyOrigin`]                                 | `"[d34db33f/84'/0'/0']"`          | 4-byte fingerprint + path bytes                  |
+//! | [`KnownVersion`]                              | `"zpub"`                          | 4 version bytes                                  |
+//! | [`Version`]                                   | `"zpub"` or `"public:0x04B24747"` | 1 byte (0: public, 1: private) + 4 version bytes |
+//! | [`ExtendedPublicKey`]                         | `"xpub..."`                       | 78-byte payload                                  |
+//! | [`VersionedPublicKey`]                        | `"tpub..."`                       | 78-byte payload                                  |
+//! | [`ExposedPrivateKey`]                         | `"xprv..."`                       | 78-byte payload                                  |
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use zeroize::Zeroizing;
+
+use crate::{
+    curve::{Bip32Curve, Curve, CurvePrivateKey, CurvePublicKey},
+    error::{Error, ErrorKind, Result},
+    path::{ChildNumber, DerivationPath, HardenedDerivationPath},
+    xkey::{
+        ExtendedKeyPayload, ExtendedPrivateKey, ExtendedPublicKey, KeyOrigin, KnownVersion,
+        Version, VersionedPublicKey,
+    },
+};
+
+/// Visits a string in human-readable formats, or bytes in binary formats.
+struct StrOrBytesVisitor<T> {
+    expecting: &'static str,
+    from_str: fn(&str) -> Result<T>,
+    from_bytes: fn(&[u8]) -> Result<T>,
+}
+
+impl<'de, T> de::Visitor<'de> for StrOrBytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        (self.from_str)(value).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<T, E> {
+        (self.from_bytes)(value).map_err(E::custom)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(seq.size_hint().unwrap_or(0)));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        (self.from_bytes)(&bytes).map_err(de::Error::custom)
+    }
+}
+
+fn deserialize_str_or_bytes<'de, D, T>(
+    deserializer: D,
+    expecting: &'static str,
+    from_str: fn(&str) -> Result<T>,
+    from_bytes: fn(&[u8]) -> Result<T>,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let visitor = StrOrBytesVisitor { expecting, from_str, from_bytes };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(visitor)
+    } else {
+        deserializer.deserialize_bytes(visitor)
+    }
+}
+
+fn invalid_length(expected: &'static str, len: usize) -> Error {
+    Error::new(ErrorKind::InvalidPayload, "invalid serialized length")
+        .with_context("expected_len", expected)
+        .with_context("len", len)
+}
+
+// ChildNumber
+fn child_from_bytes(bytes: &[u8]) -> Result<ChildNumber> {
+    let bytes: [u8; 4] = bytes.try_into().map_err(|_| invalid_length("4", bytes.len()))?;
+    Ok(ChildNumber::from_bytes(bytes))
+}
+
+impl Serialize for ChildNumber {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u32(u32::from(*self))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ChildNumber {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserialize_str_or_bytes(
+                deserializer,
+                "a child number",
+                ChildNumber::from_str,
+                child_from_bytes,
+            )
+        } else {
+            u32::deserialize(deserializer).map(ChildNumber::from)
+        }
+    }
+}
+
+// DerivationPath
+fn path_to_bytes(path: &DerivationPath) -> Vec<u8> {
+    path.children().iter().flat_map(|child| child.to_bytes()).collect()
+}
+
+fn path_from_bytes(bytes: &[u8]) -> Result<DerivationPath> {
+    if bytes.len() % 4 != 0 {
+        return Err(invalid_length("multiple of 4", bytes.len()));
+    }
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|chunk| ChildNumber::from_bytes(chunk.try_into().expect("4-byte chunk")))
+        .collect())
+}
+
+impl Serialize for DerivationPath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(&path_to_bytes(self))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DerivationPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str_or_bytes(
+            deserializer,
+            "a derivation path",
+            DerivationPath::from_str,
+            path_from_bytes,
+        )
+    }
+}
+
+impl Serialize for HardenedDerivationPath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            let path = DerivationPath::from(self.clone());
+            serializer.serialize_bytes(&path_to_bytes(&path))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HardenedDerivationPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str_or_bytes(
+            deserializer,
+            "a hardened derivation path",
+            HardenedDerivationPath::from_str,
+            |bytes| HardenedDerivationPath::try_from(path_from_bytes(bytes)?),
+        )
+    }
+}
+
+// KeyOrigin
+fn origin_from_bytes(bytes: &[u8]) -> Result<KeyOrigin> {
+    let Some((fingerprint, path)) = bytes.split_first_chunk::<4>() else {
+        return Err(invalid_length("at least 4", bytes.len()));
+    };
+    Ok(KeyOrigin::new(*fingerprint, path_from_bytes(path)?))
+}
+
+impl Serialize for KeyOrigin {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            let mut bytes = self.fingerprint().to_vec();
+            bytes.extend(path_to_bytes(self.path()));
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyOrigin {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str_or_bytes(
+            deserializer,
+            "a key origin",
+            KeyOrigin::from_str,
+            origin_from_bytes,
+        )
+    }
+}
+
+// KnownVersion
+fn known_version_from_prefix(prefix: &str) -> Result<KnownVersion> {
+    KnownVersion::from_prefix(prefix).ok_or_else(|| {
+        Error::new(ErrorKind::InvalidVersion, "unknown version prefix")
+            .with_context("prefix", prefix)
+    })
+}
+
+fn known_version_from_bytes(bytes: &[u8]) -> Result<KnownVersion> {
+    let bytes: [u8; 4] = bytes.try_into().map_err(|_| invalid_length("4", bytes.len()))?;
+    let raw = u32::from_be_bytes(bytes);
+    KnownVersion::from_raw(raw).ok_or_else(|| {
+        Error::new(ErrorKind::InvalidVersion, "unknown version bytes")
+            .with_context("version", format!("0x{raw:08X}"))
+    })
+}
+
+impl Serialize for KnownVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(self.prefix())
+        } else {
+            serializer.serialize_bytes(&self.into_version().to_bytes())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for KnownVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str_or_bytes(
+            deserializer,
+            "a known version prefix",
+            known_version_from_prefix,
+            known_version_from_bytes,
+        )
+    }
+}
+
+// Version
+const PUBLIC_TAG: &str = "public:";
+const PRIVATE_TAG: &str = "private:";
+
+fn version_to_string(version: Version) -> String {
+    match version.into_known_version() {
+        Some(known) => known.prefix().to_string(),
+        None if version.is_public() => format!("{PUBLIC_TAG}{version}"),
+        None => format!("{PRIVATE_TAG}{version}"),
+    }
+}
+
+fn version_from_str(value: &str) -> Result<Version> {
+    let (raw, private) = match (value.strip_prefix(PUBLIC_TAG), value.strip_prefix(PRIVATE_TAG)) {
+        (Some(raw), _) => (raw, false),
+        (_, Some(raw)) => (raw, true),
+        _ => return known_version_from_prefix(value).map(KnownVersion::into_version),
+    };
+
+    let raw = raw
+        .strip_prefix("0x")
+        .and_then(|raw| u32::from_str_radix(raw, 16).ok())
+        .ok_or_else(|| {
+            Error::new(ErrorKind::InvalidVersion, "invalid version bytes")
+                .with_context("version", value)
+        })?;
+    Ok(if private { Version::private(raw) } else { Version::public(raw) })
+}
+
+fn version_from_bytes(bytes: &[u8]) -> Result<Version> {
+    let &[tag, b0, b1, b2, b3] = bytes else {
+        return Err(invalid_length("5", bytes.len()));
+    };
+    match tag {
+        0 => Ok(Version::from_public_bytes([b0, b1, b2, b3])),
+        1 => Ok(Version::from_private_bytes([b0, b1, b2, b3])),
+        _ => Err(Error::new(ErrorKind::InvalidVersion, "invalid version visibility tag")
+            .with_context("tag", tag)),
+    }
+}
+
+impl Serialize for Version {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&version_to_string(*self))
+        } else {
+            let mut bytes = [0u8; 5];
+            bytes[0] = u8::from(self.is_private());
+            bytes[1..].copy_from_slice(&self.to_bytes());
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str_or_bytes(deserializer, "a version", version_from_str, version_from_bytes)
+    }
+}
+
+// ExtendedPublicKey
+//
+// Keys are encoded with the `xpub` version bytes, see `VersionedPublicKey` to keep the version
+// bytes of e.g. a `tpub`. The origin is not serialized.
+impl<C> Serialize for ExtendedPublicKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let payload = self.encode_with_unchecked(Version::XPUB);
+        if serializer.is_human_readable() {
+            serializer.collect_str(&payload)
+        } else {
+            serializer.serialize_bytes(&payload.to_bytes())
+        }
+    }
+}
+
+impl<'de, C> Deserialize<'de> for ExtendedPublicKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str_or_bytes(
+            deserializer,
+            "an extended public key",
+            |encoded| encoded.parse(),
+            |bytes| ExtendedPublicKey::try_from(ExtendedKeyPayload::from_bytes(bytes)?),
+        )
+    }
+}
+
+// VersionedPublicKey
+//
+// Keys are encoded with their own version bytes. The origin is not serialized.
+impl<C> Serialize for VersionedPublicKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let payload = self.key().encode_with_unchecked(self.version());
+        if serializer.is_human_readable() {
+            serializer.collect_str(&payload)
+        } else {
+            serializer.serialize_bytes(&payload.to_bytes())
+        }
+    }
+}
+
+impl<'de, C> Deserialize<'de> for VersionedPublicKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str_or_bytes(
+            deserializer,
+            "an extended public key",
+            |encoded| encoded.parse(),
+            |bytes| VersionedPublicKey::try_from(ExtendedKeyPayload::from_bytes(bytes)?),
+        )
+    }
+}
+
+/// An explicit opt-in wrapper to serialize an [`ExtendedPrivateKey`].
+///
+/// Extended private keys do not implement `Serialize`/`Deserialize` themselves, so that secrets
+/// only end up in serialized data where this wrapper is used on purpose. Keys are encoded with
+/// the `xprv` version bytes, and the intermediate encodings are zeroized.
+///
+/// ```rust
+/// # #[cfg(feature = "k256")] {
+/// use bip0032::{ExposedPrivateKey, ExtendedPrivateKey, curve::secp256k1::*};
+///
+/// let master = ExtendedPrivateKey::<Secp256k1Curve<K256Backend>>::new(&[0x42; 32]).unwrap();
+/// let json = serde_json::to_string(&ExposedPrivateKey::new(master)).unwrap();
+/// assert!(json.starts_with("\"xprv"));
+///
+/// let key: ExposedPrivateKey<Secp256k1Curve<K256Backend>> = serde_json::from_str(&json).unwrap();
+/// let master = key.into_inner();
+/// # }
+/// ```
+pub struct ExposedPrivateKey<C: Curve>(ExtendedPrivateKey<C>);
+
+impl<C: Curve> ExposedPrivateKey<C> {
+    /// Wraps an extended private key for serialization.
+    pub fn new(key: ExtendedPrivateKey<C>) -> Self {
+        Self(key)
+    }
+
+    /// Returns the wrapped extended private key.
+    pub fn as_inner(&self) -> &ExtendedPrivateKey<C> {
+        &self.0
+    }
+
+    /// Unwraps the extended private key.
+    pub fn into_inner(self) -> ExtendedPrivateKey<C> {
+        self.0
+    }
+}
+
+impl<C: Curve> From<ExtendedPrivateKey<C>> for ExposedPrivateKey<C> {
+    fn from(key: ExtendedPrivateKey<C>) -> Self {
+        Self(key)
+    }
+}
+
+impl<C> Serialize for ExposedPrivateKey<C>
+where
+    C: Bip32Curve,
+    C::PrivateKey: CurvePrivateKey<Bytes = [u8; 32]>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let payload = self.0.encode_with_unchecked(Version::XPRV);
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&payload.to_zeroizing_string())
+        } else {
+            let mut bytes = Zeroizing::new([0u8; ExtendedKeyPayload::LENGTH]);
+            payload.write_bytes(&mut bytes);
+            serializer.serialize_bytes(&bytes[..])
+        }
+    }
+}
+
+impl<'de, C> Deserialize<'de> for ExposedPrivateKey<C>
+where
+    C: Bip32Curve,
+    C::PrivateKey: CurvePrivateKey<Bytes = [u8; 32]>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_str_or_bytes(
+            deserializer,
+            "an extended private key",
+            |encoded| encoded.parse().map(Self),
+            |bytes| ExtendedPrivateKey::try_from(ExtendedKeyPayload::from_bytes(bytes)?).map(Self),
+        )
+    }
+}
@@ -0,0 +1,214 @@
+//! [BIP-0340](https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki) x-only public keys
+//! and [BIP-0341](https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki) taproot
+//! tweaking of secp256k1 extended keys.
+//!
+//! ```rust
+//! use bip0032::{DerivationPath, ExtendedPrivateKey, curve::secp256k1::*};
+//!
+//! let seed = [0x42; 32];
+//! let master = ExtendedPrivateKey::<Secp256k1Curve<K256Backend>>::new(&seed).unwrap();
+//! let key = master.derive_path(&"m/86'/0'/0'/0/0".parse::<DerivationPath>().unwrap()).unwrap();
+//!
+//! // BIP-0086: key path only output
+//! let (internal_key, _) = key.public_key().x_only_public_key();
+//! let (output_key, _) = key.public_key().tap_tweak(None).unwrap();
+//! assert_ne!(internal_key, output_key);
+//!
+//! // the tweaked private key signs for the output key
+//! let tweaked = key.tap_tweak(None).unwrap();
+//! assert_eq!(tweaked.len(), 32);
+//! ```
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::{
+    curve::{CurvePrivateKey, CurvePublicKey, NegatableKey, TweakableKey, secp256k1::*},
+    error::{Error, ErrorKind, Result},
+    xkey::{ExtendedPrivateKey, ExtendedPublicKey},
+};
+
+/// The parity of the y coordinate of a secp256k1 point.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Parity {
+    /// Even y coordinate.
+    Even,
+    /// Odd y coordinate.
+    Odd,
+}
+
+impl Parity {
+    fn from_prefix(prefix: u8) -> Self {
+        if prefix == 0x03 { Self::Odd } else { Self::Even }
+    }
+}
+
+/// A BIP-0340 x-only public key: the x coordinate of a point with an even y coordinate.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct XOnlyPublicKey([u8; 32]);
+
+impl XOnlyPublicKey {
+    /// Returns the serialized x coordinate.
+    pub const fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// Returns the serialized x coordinate.
+    pub const fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Splits a compressed public key into its x-only key and parity.
+    fn from_compressed(bytes: &[u8; 33]) -> (Self, Parity) {
+        let mut x = [0u8; 32];
+        x.copy_from_slice(&bytes[1..]);
+        (Self(x), Parity::from_prefix(bytes[0]))
+    }
+}
+
+impl From<XOnlyPublicKey> for [u8; 32] {
+    fn from(key: XOnlyPublicKey) -> Self {
+        key.0
+    }
+}
+
+/// `SHA256(SHA256(tag) || SHA256(tag) || data)` (BIP-0340).
+fn tagged_hash(tag: &[u8], chunks: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag);
+    let mut hasher = Sha256::new().chain_update(tag_hash).chain_update(tag_hash);
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    hasher.finalize().into()
+}
+
+/// `hashTapTweak(x(P) || merkle_root)`, without the merkle root for key path only outputs.
+fn tap_tweak_hash(internal_key: &XOnlyPublicKey, merkle_root: Option<&[u8; 32]>) -> [u8; 32] {
+    match merkle_root {
+        Some(merkle_root) => tagged_hash(b"TapTweak", &[internal_key.as_bytes(), merkle_root]),
+        None => tagged_hash(b"TapTweak", &[internal_key.as_bytes()]),
+    }
+}
+
+fn tweak_error(err: impl crate::error::IntoErrorSource) -> Error {
+    Error::new(ErrorKind::InvalidKeyData, "invalid taproot tweak").set_source(err)
+}
+
+/// Returns the output key `Q = lift_x(x(P)) + int(t)G` and its parity (BIP-0341).
+pub(crate) fn tweak_public_key<K>(
+    internal_key: &K,
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<(XOnlyPublicKey, Parity)>
+where
+    K: CurvePublicKey<Bytes = [u8; 33]> + TweakableKey,
+{
+    let mut even_key = internal_key.to_bytes();
+    even_key[0] = 0x02;
+    let (x_only, _) = XOnlyPublicKey::from_compressed(&even_key);
+    let even_key = K::from_bytes(&even_key).map_err(tweak_error)?;
+
+    let tweak = tap_tweak_hash(&x_only, merkle_root);
+    let output_key = even_key.add_tweak(&tweak).map_err(tweak_error)?;
+    Ok(XOnlyPublicKey::from_compressed(&output_key.to_bytes()))
+}
+
+// BIP-0340/341
+impl<B: Secp256k1Backend> ExtendedPublicKey<Secp256k1Curve<B>> {
+    /// Returns the x-only public key and the parity of the y coordinate of this key.
+    pub fn x_only_public_key(&self) -> (XOnlyPublicKey, Parity) {
+        XOnlyPublicKey::from_compressed(&self.public_key.to_bytes())
+    }
+
+    /// Returns the taproot output key of this key as internal key and its parity (BIP-0341).
+    ///
+    /// Without a merkle root, the output key commits to no script tree, as used by key path only
+    /// outputs (BIP-0086).
+    pub fn tap_tweak(&self, merkle_root: Option<&[u8; 32]>) -> Result<(XOnlyPublicKey, Parity)> {
+        tweak_public_key(&self.public_key, merkle_root)
+    }
+}
+
+// BIP-0340/341
+impl<B> ExtendedPrivateKey<Secp256k1Curve<B>>
+where
+    B: Secp256k1Backend,
+    B::PrivateKey: NegatableKey,
+{
+    /// Returns the private key bytes normalized to an even y public key (BIP-0340), i.e. the
+    /// negated key if the public key has an odd y coordinate.
+    ///
+    /// # Warning
+    ///
+    /// Exposes raw private key material. Handle with care.
+    pub fn to_even_y_bytes(&self) -> Zeroizing<[u8; 32]> {
+        let (_, parity) = self.public_key().x_only_public_key();
+        match parity {
+            Parity::Even => Zeroizing::new(self.private_key.to_bytes()),
+            Parity::Odd => {
+                let mut negated = self.private_key.negate();
+                let bytes = Zeroizing::new(negated.to_bytes());
+                negated.zeroize();
+                bytes
+            },
+        }
+    }
+
+    /// Returns the taproot tweaked private key bytes, the private key of
+    /// [`ExtendedPublicKey::tap_tweak`] (BIP-0341).
+    ///
+    /// # Warning
+    ///
+    /// Exposes raw private key material. Handle with care.
+    pub fn tap_tweak(&self, merkle_root: Option<&[u8; 32]>) -> Result<Zeroizing<[u8; 32]>> {
+        let (internal_key, parity) = self.public_key().x_only_public_key();
+        let tweak = tap_tweak_hash(&internal_key, merkle_root);
+
+        let mut even_key = match parity {
+            Parity::Even => self.private_key.clone(),
+            Parity::Odd => self.private_key.negate(),
+        };
+        let tweaked = even_key.add_tweak(&tweak);
+        even_key.zeroize();
+
+        let mut tweaked = tweaked.map_err(tweak_error)?;
+        let bytes = Zeroizing::new(tweaked.to_bytes());
+        tweaked.zeroize();
+        Ok(bytes)
+    }
+}
+
+#[cfg(all(test, feature = "k256"))]
+mod tests {
+    use super::*;
+
+    fn decode<const N: usize>(hex: &str) -> [u8; N] {
+        const_hex::decode(hex).unwrap().try_into().unwrap()
+    }
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0341/wallet-test-vectors.json
+    #[test]
+    fn tweak_public_key_vectors() {
+        let cases = [
+            (
+                "d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d",
+                None,
+                "53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343",
+            ),
+            (
+                "187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27",
+                Some("5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21"),
+                "147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3",
+            ),
+        ];
+
+        for (internal_key, merkle_root, output_key) in cases {
+            let mut compressed = [0x02; 33];
+            compressed[1..].copy_from_slice(&decode::<32>(internal_key));
+            let internal_key = k256::PublicKey::from_bytes(&compressed).unwrap();
+            let merkle_root = merkle_root.map(decode::<32>);
+
+            let (tweaked, _) = tweak_public_key(&internal_key, merkle_root.as_ref()).unwrap();
+            assert_eq!(tweaked.to_bytes(), decode::<32>(output_key));
+        }
+    }
+}
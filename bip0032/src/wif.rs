@@ -0,0 +1,204 @@
+//! Wallet import format (WIF) of secp256k1 private keys, as used by e.g. Bitcoin Core's
+//! `importprivkey`.
+//!
+//! ```rust
+//! use bip0032::{
+//!     DerivationPath, ExtendedPrivateKey,
+//!     address::Network,
+//!     curve::secp256k1::*,
+//!     wif::WifPrivateKey,
+//! };
+//!
+//! let master = ExtendedPrivateKey::<Secp256k1Curve<K256Backend>>::new(&[0x42; 32]).unwrap();
+//! let key = master.derive_path(&"m/44'/0'/0'/0/0".parse::<DerivationPath>().unwrap()).unwrap();
+//! let wif = key.to_wif(Network::Bitcoin, true);
+//! assert!(wif.starts_with('K') || wif.starts_with('L'));
+//!
+//! let imported = wif.parse::<WifPrivateKey<K256Backend>>().unwrap();
+//! assert_eq!(imported.network(), Network::Bitcoin);
+//! assert!(imported.is_compressed());
+//! assert_eq!(imported.to_bytes(), key.to_bytes());
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String};
+use core::{fmt, str::FromStr};
+
+use zeroize::Zeroizing;
+
+use crate::{
+    address::Network,
+    curve::{CurvePrivateKey, CurvePublicKey, secp256k1::*},
+    error::{Error, ErrorKind, Result},
+    xkey::ExtendedPrivateKey,
+};
+
+/// Length of a WIF payload: version byte, 32-byte private key and optional compression flag.
+const WIF_MAX_LENGTH: usize = 1 + 32 + 1;
+/// Base58Check adds 4 checksum bytes, and Base58 needs at most `len + (len + 1) / 2` characters.
+const WIF_MAX_ENCODED_LENGTH: usize = (WIF_MAX_LENGTH + 4) + (WIF_MAX_LENGTH + 4).div_ceil(2);
+/// Compression flag of private keys whose public key is serialized in compressed form.
+const COMPRESSED_FLAG: u8 = 0x01;
+
+/// A bare secp256k1 private key decoded from WIF.
+pub struct WifPrivateKey<B: Secp256k1Backend> {
+    private_key: B::PrivateKey,
+    network: Network,
+    compressed: bool,
+}
+
+impl<B: Secp256k1Backend> WifPrivateKey<B> {
+    /// Creates a WIF private key.
+    pub fn new(private_key: B::PrivateKey, network: Network, compressed: bool) -> Self {
+        Self { private_key, network, compressed }
+    }
+
+    /// Returns the private key.
+    pub fn private_key(&self) -> &B::PrivateKey {
+        &self.private_key
+    }
+
+    /// Returns the private key bytes.
+    ///
+    /// # Warning
+    ///
+    /// Exposes raw private key material. Handle with care.
+    pub fn to_bytes(&self) -> Zeroizing<[u8; 32]> {
+        Zeroizing::new(self.private_key.to_bytes())
+    }
+
+    /// Returns the compressed public key.
+    pub fn public_key(&self) -> [u8; 33] {
+        self.private_key.to_public().to_bytes()
+    }
+
+    /// Returns the network of this key.
+    ///
+    /// Testnet, signet and regtest keys share the same version byte, so decoded keys of those
+    /// networks report [`Network::Testnet`].
+    pub const fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Returns whether the public key of this key is serialized in compressed form.
+    pub const fn is_compressed(&self) -> bool {
+        self.compressed
+    }
+
+    /// Encodes this key in WIF.
+    pub fn to_wif(&self) -> Zeroizing<String> {
+        let private_key = Zeroizing::new(self.private_key.to_bytes());
+        encode_wif(&private_key, self.network, self.compressed)
+    }
+}
+
+impl<B: Secp256k1Backend> Drop for WifPrivateKey<B> {
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+    }
+}
+
+impl<B: Secp256k1Backend> Clone for WifPrivateKey<B> {
+    fn clone(&self) -> Self {
+        Self::new(self.private_key.clone(), self.network, self.compressed)
+    }
+}
+
+impl<B: Secp256k1Backend> fmt::Debug for WifPrivateKey<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WifPrivateKey")
+            .field("network", &self.network)
+            .field("compressed", &self.compressed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: Secp256k1Backend> FromStr for WifPrivateKey<B> {
+    type Err = Error;
+
+    fn from_str(encoded: &str) -> Result<Self> {
+        if encoded.len() > WIF_MAX_ENCODED_LENGTH {
+            return Err(Error::new(ErrorKind::InvalidPayload, "invalid base58check length")
+                .with_context("encoded_len", encoded.len())
+                .with_context("max_len", WIF_MAX_ENCODED_LENGTH));
+        }
+
+        let mut data = Zeroizing::new([0u8; WIF_MAX_LENGTH + 4]);
+        let len = bs58::decode(encoded).with_check(None).onto(&mut data[..]).map_err(|err| {
+            Error::new(ErrorKind::InvalidPayload, "invalid base58check encoding")
+                .with_context("encoded_len", encoded.len())
+                .set_source({
+                    #[cfg(feature = "std")]
+                    {
+                        anyhow::Error::new(err)
+                    }
+                    #[cfg(not(feature = "std"))]
+                    {
+                        anyhow::Error::msg(err)
+                    }
+                })
+        })?;
+
+        let compressed = match len {
+            33 => false,
+            34 if data[33] == COMPRESSED_FLAG => true,
+            34 => {
+                return Err(Error::new(ErrorKind::InvalidPayload, "invalid WIF compression flag")
+                    .with_context("flag", format!("0x{:02X}", data[33])));
+            },
+            _ => {
+                return Err(Error::new(ErrorKind::InvalidPayload, "invalid WIF length")
+                    .with_context("decoded_len", len));
+            },
+        };
+
+        let network = match data[0] {
+            prefix if prefix == Network::Bitcoin.wif_prefix() => Network::Bitcoin,
+            prefix if prefix == Network::Testnet.wif_prefix() => Network::Testnet,
+            prefix => {
+                return Err(Error::new(ErrorKind::InvalidVersion, "unknown WIF version byte")
+                    .with_context("version", format!("0x{prefix:02X}")));
+            },
+        };
+
+        let mut private_key = Zeroizing::new([0u8; 32]);
+        private_key.copy_from_slice(&data[1..33]);
+        let private_key =
+            <B::PrivateKey as CurvePrivateKey>::from_bytes(&private_key).map_err(|err| {
+                Error::new(ErrorKind::InvalidKeyData, "invalid private key data").set_source(err)
+            })?;
+
+        Ok(Self::new(private_key, network, compressed))
+    }
+}
+
+fn encode_wif(private_key: &[u8; 32], network: Network, compressed: bool) -> Zeroizing<String> {
+    let mut data = Zeroizing::new([0u8; WIF_MAX_LENGTH]);
+    data[0] = network.wif_prefix();
+    data[1..33].copy_from_slice(private_key);
+    data[33] = COMPRESSED_FLAG;
+    let len = if compressed { WIF_MAX_LENGTH } else { WIF_MAX_LENGTH - 1 };
+
+    let mut encoded = Zeroizing::new([0u8; WIF_MAX_ENCODED_LENGTH]);
+    let encoded_len = bs58::encode(&data[..len])
+        .with_check()
+        .onto(&mut encoded[..])
+        .expect("base58 encoding should fit the fixed buffer");
+    let encoded = core::str::from_utf8(&encoded[..encoded_len]).expect("base58 alphabet is ASCII");
+    Zeroizing::new(String::from(encoded))
+}
+
+// WIF
+impl<B: Secp256k1Backend> ExtendedPrivateKey<Secp256k1Curve<B>> {
+    /// Encodes the private key of this key in WIF, dropping the chain code and the metadata.
+    ///
+    /// `compressed` selects whether the key is imported with its compressed public key, as used by
+    /// all segwit and most modern wallets.
+    ///
+    /// # Warning
+    ///
+    /// Exposes raw private key material. Handle with care.
+    pub fn to_wif(&self, network: Network, compressed: bool) -> Zeroizing<String> {
+        encode_wif(&self.to_bytes(), network, compressed)
+    }
+}
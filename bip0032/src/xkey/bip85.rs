@@ -0,0 +1,308 @@
+//! Deterministic entropy derived from a secp256k1 extended private key, as specified by
+//! [BIP-0085](https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki).
+//!
+//! Every application derives a hardened path under `m/83696968'` and turns the derived private key
+//! `k` into 64 bytes of entropy with `HMAC-SHA512(key = "bip-entropy-from-k", msg = k)`. Child
+//! wallets and passwords spawned this way can be recreated from the root key, but do not reveal it.
+//!
+//! ```rust
+//! use bip0032::{ExtendedPrivateKey, bip85::Bip39Language, curve::secp256k1::*};
+//!
+//! let xprv = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+//! let root = xprv.parse::<ExtendedPrivateKey<Secp256k1Curve<K256Backend>>>().unwrap();
+//!
+//! // the entropy of a 12-word English mnemonic, e.g. for `bip0039::Mnemonic::from_entropy`
+//! let entropy = root.bip85_bip39(Bip39Language::English, 12, 0).unwrap();
+//! assert_eq!(entropy.len(), 16);
+//!
+//! // an independent master key, e.g. for a hot wallet spawned from a hardware seed
+//! let child = root.bip85_xprv(0).unwrap();
+//! assert_eq!(child.depth(), 0);
+//!
+//! let password = root.bip85_base64_password(21, 0).unwrap();
+//! assert_eq!(password.as_str(), "dKLoepugzdVJvdL56ogNV");
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+use core::iter;
+
+use hmac::Mac;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+    address::Network,
+    base64,
+    curve::{CurvePrivateKey, secp256k1::*},
+    error::{Error, ErrorKind, Result},
+    path::{ChildNumber, DerivationPath},
+    wif::WifPrivateKey,
+    xkey::core::*,
+};
+
+/// The purpose level of BIP-0085 derivation paths, `m/83696968'`.
+pub const PURPOSE: u32 = 83_696_968;
+
+/// HMAC key turning a derived private key into entropy.
+const HMAC_KEY: &[u8] = b"bip-entropy-from-k";
+
+/// Application numbers of the supported applications.
+const APP_BIP39: u32 = 39;
+const APP_HD_SEED_WIF: u32 = 2;
+const APP_XPRV: u32 = 32;
+const APP_HEX: u32 = 128_169;
+const APP_PWD_BASE64: u32 = 707_764;
+const APP_PWD_BASE85: u32 = 707_785;
+const APP_DICE: u32 = 89_101;
+
+/// Alphabet of base85 passwords, as in RFC 1924.
+const BASE85_ALPHABET: &[u8; 85] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/// Language of a BIP-0039 mnemonic, part of the derivation path of its entropy.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Bip39Language {
+    /// English, index 0.
+    English,
+    /// Japanese, index 1.
+    Japanese,
+    /// Korean, index 2.
+    Korean,
+    /// Spanish, index 3.
+    Spanish,
+    /// Chinese (simplified), index 4.
+    ChineseSimplified,
+    /// Chinese (traditional), index 5.
+    ChineseTraditional,
+    /// French, index 6.
+    French,
+    /// Italian, index 7.
+    Italian,
+    /// Czech, index 8.
+    Czech,
+    /// Portuguese, index 9.
+    Portuguese,
+}
+
+impl Bip39Language {
+    /// All languages, in index order.
+    pub const ALL: [Self; 10] = [
+        Self::English,
+        Self::Japanese,
+        Self::Korean,
+        Self::Spanish,
+        Self::ChineseSimplified,
+        Self::ChineseTraditional,
+        Self::French,
+        Self::Italian,
+        Self::Czech,
+        Self::Portuguese,
+    ];
+
+    /// Returns the language index (without hardening bit).
+    pub const fn index(self) -> u32 {
+        self as u32
+    }
+
+    /// Returns the language with the given index, if known.
+    pub const fn from_index(index: u32) -> Option<Self> {
+        if index < Self::ALL.len() as u32 { Some(Self::ALL[index as usize]) } else { None }
+    }
+}
+
+/// The BIP85-DRNG: a SHAKE256 stream seeded with 64 bytes of BIP-0085 entropy, for applications
+/// that need more entropy or rejection sampling, e.g. dice rolls.
+pub struct Drng {
+    state: [u64; 25],
+    offset: usize,
+}
+
+impl Drng {
+    /// Rate of SHAKE256 in bytes.
+    const RATE: usize = 136;
+
+    /// Creates a DRNG seeded with the given entropy.
+    pub fn new(entropy: &[u8; 64]) -> Self {
+        let mut state = [0u64; 25];
+        for (lane, chunk) in state.iter_mut().zip(entropy.chunks_exact(8)) {
+            *lane ^= u64::from_le_bytes(chunk.try_into().expect("chunk of 8 bytes"));
+        }
+        // SHAKE domain separation and padding of the single absorbed block
+        state[entropy.len() / 8] ^= 0x1F;
+        state[Self::RATE / 8 - 1] ^= 0x80 << 56;
+        keccak::f1600(&mut state);
+        Self { state, offset: 0 }
+    }
+
+    /// Fills `out` with the next bytes of the stream.
+    pub fn fill_bytes(&mut self, out: &mut [u8]) {
+        for byte in out {
+            if self.offset == Self::RATE {
+                keccak::f1600(&mut self.state);
+                self.offset = 0;
+            }
+            *byte = (self.state[self.offset / 8] >> (8 * (self.offset % 8))) as u8;
+            self.offset += 1;
+        }
+    }
+}
+
+impl Drop for Drng {
+    fn drop(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+// BIP-0085
+impl<B: Secp256k1Backend> ExtendedPrivateKey<Secp256k1Curve<B>> {
+    /// Derives the entropy of an application path `m/83696968'/<application>...`, relative to this
+    /// key, which is usually a master key.
+    ///
+    /// Every index of `application` is hardened, e.g. `[39, 0, 12, 0]` for the first 12-word
+    /// English mnemonic.
+    ///
+    /// # Warning
+    ///
+    /// Exposes raw secret material. Handle with care.
+    pub fn bip85_entropy(&self, application: &[u32]) -> Result<Zeroizing<[u8; 64]>> {
+        let path = iter::once(PURPOSE)
+            .chain(application.iter().copied())
+            .map(|index| ChildNumber::new(index, true))
+            .collect::<Result<DerivationPath>>()?;
+        let key = self.derive_path(&path)?;
+        let k = key.to_bytes();
+        Ok(hmac_sha512_bytes(HMAC_KEY, |mac| mac.update(&*k)))
+    }
+
+    /// Derives the entropy of a BIP-0039 mnemonic of 12, 15, 18, 21 or 24 words, at
+    /// `m/83696968'/39'/<language>'/<words>'/<index>'`.
+    pub fn bip85_bip39(
+        &self,
+        language: Bip39Language,
+        words: u32,
+        index: u32,
+    ) -> Result<Zeroizing<Vec<u8>>> {
+        if !matches!(words, 12 | 15 | 18 | 21 | 24) {
+            return Err(Error::new(ErrorKind::InvalidEntropy, "invalid mnemonic word count")
+                .with_context("words", words));
+        }
+        let entropy = self.bip85_entropy(&[APP_BIP39, language.index(), words, index])?;
+        // 32 bits of entropy per 3 words
+        Ok(Zeroizing::new(entropy[..words as usize * 4 / 3].to_vec()))
+    }
+
+    /// Derives a private key for the HD seed of e.g. Bitcoin Core, at `m/83696968'/2'/<index>'`,
+    /// exported as a compressed mainnet WIF.
+    pub fn bip85_wif(&self, index: u32) -> Result<WifPrivateKey<B>> {
+        let entropy = self.bip85_entropy(&[APP_HD_SEED_WIF, index])?;
+        let private_key = entropy_private_key::<B>(&entropy[..32])?;
+        Ok(WifPrivateKey::new(private_key, Network::Bitcoin, true))
+    }
+
+    /// Derives a master extended private key at `m/83696968'/32'/<index>'`, with the first half of
+    /// the entropy as chain code and the second half as private key.
+    pub fn bip85_xprv(&self, index: u32) -> Result<Self> {
+        let entropy = self.bip85_entropy(&[APP_XPRV, index])?;
+        let private_key = entropy_private_key::<B>(&entropy[32..])?;
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&entropy[..32]);
+        Ok(Self {
+            meta: ExtendedKeyMetadata {
+                depth: 0,
+                parent_fingerprint: [0u8; 4],
+                child_number: 0,
+                chain_code,
+            },
+            private_key,
+            origin: None,
+        })
+    }
+
+    /// Derives `len` bytes of entropy, from 16 to 64, at `m/83696968'/128169'/<len>'/<index>'`.
+    pub fn bip85_hex(&self, len: u32, index: u32) -> Result<Zeroizing<Vec<u8>>> {
+        check_length("invalid entropy length", len, 16, 64)?;
+        let entropy = self.bip85_entropy(&[APP_HEX, len, index])?;
+        Ok(Zeroizing::new(entropy[..len as usize].to_vec()))
+    }
+
+    /// Derives a base64 password of `len` characters, from 20 to 86, at
+    /// `m/83696968'/707764'/<len>'/<index>'`.
+    pub fn bip85_base64_password(&self, len: u32, index: u32) -> Result<Zeroizing<String>> {
+        check_length("invalid password length", len, 20, 86)?;
+        let entropy = self.bip85_entropy(&[APP_PWD_BASE64, len, index])?;
+        let mut password = Zeroizing::new(String::with_capacity(88));
+        base64::encode_to(&mut *password, &entropy[..]).expect("writing to a string never fails");
+        password.truncate(len as usize);
+        Ok(password)
+    }
+
+    /// Derives a base85 password of `len` characters, from 10 to 80, at
+    /// `m/83696968'/707785'/<len>'/<index>'`.
+    pub fn bip85_base85_password(&self, len: u32, index: u32) -> Result<Zeroizing<String>> {
+        check_length("invalid password length", len, 10, 80)?;
+        let entropy = self.bip85_entropy(&[APP_PWD_BASE85, len, index])?;
+        let mut password = Zeroizing::new(String::with_capacity(80));
+        for chunk in entropy.chunks_exact(4) {
+            let mut n = u32::from_be_bytes(chunk.try_into().expect("chunk of 4 bytes"));
+            let mut digits = [0u8; 5];
+            for digit in digits.iter_mut().rev() {
+                *digit = BASE85_ALPHABET[(n % 85) as usize];
+                n /= 85;
+            }
+            password.extend(digits.iter().map(|&digit| digit as char));
+            digits.zeroize();
+        }
+        password.truncate(len as usize);
+        Ok(password)
+    }
+
+    /// Derives `rolls` rolls of a die with `sides` sides, each from 0 to `sides - 1`, at
+    /// `m/83696968'/89101'/<sides>'/<rolls>'/<index>'`.
+    ///
+    /// Rolls are sampled from the [`Drng`] of the entropy, discarding values out of range.
+    pub fn bip85_dice(&self, sides: u32, rolls: u32, index: u32) -> Result<Zeroizing<Vec<u32>>> {
+        if sides < 2 {
+            return Err(Error::new(ErrorKind::InvalidEntropy, "invalid number of dice sides")
+                .with_context("sides", sides));
+        }
+        if rolls == 0 {
+            return Err(Error::new(ErrorKind::InvalidEntropy, "invalid number of dice rolls")
+                .with_context("rolls", rolls));
+        }
+        let entropy = self.bip85_entropy(&[APP_DICE, sides, rolls, index])?;
+        let mut drng = Drng::new(&entropy);
+
+        let bits = u32::BITS - (sides - 1).leading_zeros();
+        let bytes = bits.div_ceil(8);
+        let mut result = Zeroizing::new(Vec::with_capacity(rolls as usize));
+        let mut buf = Zeroizing::new([0u8; 4]);
+        while result.len() < rolls as usize {
+            drng.fill_bytes(&mut buf[4 - bytes as usize..]);
+            // the leading `bits` bits of the bytes read
+            let roll = u32::from_be_bytes(*buf) >> (bytes * 8 - bits);
+            if roll < sides {
+                result.push(roll);
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn entropy_private_key<B: Secp256k1Backend>(entropy: &[u8]) -> Result<B::PrivateKey> {
+    let mut bytes = Zeroizing::new([0u8; 32]);
+    bytes.copy_from_slice(entropy);
+    <B::PrivateKey as CurvePrivateKey>::from_bytes(&bytes).map_err(|err| {
+        Error::new(ErrorKind::InvalidKeyData, "invalid private key derived from entropy")
+            .set_source(err)
+    })
+}
+
+fn check_length(message: &'static str, len: u32, min: u32, max: u32) -> Result<()> {
+    if !(min..=max).contains(&len) {
+        return Err(Error::new(ErrorKind::InvalidEntropy, message)
+            .with_context("len", len)
+            .with_context("min_len", min)
+            .with_context("max_len", max));
+    }
+    Ok(())
+}
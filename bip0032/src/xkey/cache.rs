@@ -0,0 +1,213 @@
+//! Memoized derivation of path prefixes.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::num::NonZeroUsize;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use crate::{
+    curve::{Bip32Curve, TweakableKey},
+    error::Result,
+    path::{ChildNumber, DerivationPath},
+    xkey::{ExtendedPrivateKey, ExtendedPublicKey},
+};
+#[cfg(feature = "slip10")]
+use crate::{
+    path::HardenedDerivationPath,
+    xkey::slip10::{Slip10HardenedOnlyDerivation, Slip10NonHardenedDerivation},
+};
+
+/// A root extended key with a bounded, least-recently-used cache of intermediate nodes.
+///
+/// Deriving a path reuses the node of its longest cached prefix, so deriving many leaves below
+/// the same account (e.g. `m/84'/0'/0'/0/i`) only derives the path suffix. Every strict prefix
+/// of a derived path is cached; the derived key itself is not.
+///
+/// `K` is [`ExtendedPrivateKey`] or [`ExtendedPublicKey`]. Evicted private nodes are zeroized
+/// when dropped, like any other extended private key.
+///
+/// ```
+/// # #[cfg(feature = "k256")] {
+/// use core::num::NonZeroUsize;
+///
+/// use bip0032::{DerivationCache, DerivationPath, ExtendedPrivateKey, curve::secp256k1::*};
+///
+/// let seed = [0x42; 32];
+/// let master = ExtendedPrivateKey::<Secp256k1Curve<K256Backend>>::new(&seed).unwrap();
+/// let mut cache = DerivationCache::new(master, NonZeroUsize::new(16).unwrap());
+///
+/// for i in 0..3 {
+///     let path = format!("m/84'/0'/0'/0/{i}").parse::<DerivationPath>().unwrap();
+///     let key = cache.derive_path(&path).unwrap();
+///     assert_eq!(key.depth(), 5);
+/// }
+/// // m/84', m/84'/0', m/84'/0'/0' and m/84'/0'/0'/0
+/// assert_eq!(cache.len(), 4);
+/// # }
+/// ```
+pub struct DerivationCache<K> {
+    root: K,
+    capacity: NonZeroUsize,
+    tick: u64,
+    nodes: BTreeMap<Vec<ChildNumber>, Node<K>>,
+    /// The paths of the cached nodes by last use, the least recently used first.
+    recency: BTreeMap<u64, Vec<ChildNumber>>,
+}
+
+struct Node<K> {
+    key: K,
+    last_used: u64,
+}
+
+impl<K: Clone> DerivationCache<K> {
+    /// Creates a cache over the given root key, holding at most `capacity` intermediate nodes.
+    ///
+    /// Lookups and evictions of the least recently used node take `O(log capacity)`.
+    pub fn new(root: K, capacity: NonZeroUsize) -> Self {
+        Self { root, capacity, tick: 0, nodes: BTreeMap::new(), recency: BTreeMap::new() }
+    }
+
+    /// Returns the root key.
+    pub fn root(&self) -> &K {
+        &self.root
+    }
+
+    /// Returns the maximum number of cached nodes.
+    pub const fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
+    /// Returns the number of cached nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if no nodes are cached.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns true if the node at the given path (relative to the root) is cached.
+    pub fn contains(&self, path: &DerivationPath) -> bool {
+        self.nodes.contains_key(path.children())
+    }
+
+    /// Removes all cached nodes.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.recency.clear();
+    }
+
+    /// Derives the key at the given path (relative to the root) with the given child derivation,
+    /// starting from the longest cached prefix and caching the intermediate nodes.
+    ///
+    /// The same derivation must be used for every call on a cache, since the nodes are keyed by
+    /// path only.
+    pub fn derive_with<F>(&mut self, path: &[ChildNumber], mut derive: F) -> Result<K>
+    where
+        F: FnMut(&K, ChildNumber) -> Result<K>,
+    {
+        if path.is_empty() {
+            return Ok(self.root.clone());
+        }
+
+        let start = (1..path.len()).rev().find(|&len| self.nodes.contains_key(&path[..len]));
+        let (start, mut key) = match start {
+            Some(start) => (start, derive(self.touch(&path[..start]), path[start])?),
+            None => (0, derive(&self.root, path[0])?),
+        };
+
+        for (i, child) in path.iter().enumerate().skip(start + 1) {
+            let parent = self.insert(&path[..i], key);
+            key = derive(parent, *child)?;
+        }
+
+        Ok(key)
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn touch(&mut self, prefix: &[ChildNumber]) -> &K {
+        let tick = self.next_tick();
+        let node = self.nodes.get_mut(prefix).expect("cached prefix");
+        let path = self.recency.remove(&node.last_used).expect("indexed node");
+        self.recency.insert(tick, path);
+        node.last_used = tick;
+        &node.key
+    }
+
+    fn insert(&mut self, prefix: &[ChildNumber], key: K) -> &K {
+        if self.nodes.contains_key(prefix) {
+            return self.touch(prefix);
+        }
+
+        if self.nodes.len() >= self.capacity.get() {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                // dropping a private node zeroizes it
+                self.nodes.remove(&oldest);
+            }
+        }
+
+        let last_used = self.next_tick();
+        self.recency.insert(last_used, prefix.to_vec());
+        &self.nodes.entry(prefix.to_vec()).or_insert(Node { key, last_used }).key
+    }
+}
+
+impl<C> DerivationCache<ExtendedPrivateKey<C>>
+where
+    C: Bip32Curve,
+    C::PrivateKey: TweakableKey,
+{
+    /// Derives a child extended private key along a path (relative to the root).
+    ///
+    /// The result matches [`ExtendedPrivateKey::derive_path`] on the root key.
+    pub fn derive_path(&mut self, path: &DerivationPath) -> Result<ExtendedPrivateKey<C>> {
+        self.derive_with(path.children(), |key, child| key.derive_child(child))
+    }
+}
+
+impl<C> DerivationCache<ExtendedPublicKey<C>>
+where
+    C: Bip32Curve,
+    C::PublicKey: TweakableKey,
+{
+    /// Derives a child extended public key along a path (relative to the root, non-hardened only).
+    ///
+    /// The result matches [`ExtendedPublicKey::derive_path`] on the root key.
+    pub fn derive_path(&mut self, path: &DerivationPath) -> Result<ExtendedPublicKey<C>> {
+        self.derive_with(path.children(), |key, child| key.derive_child(child))
+    }
+}
+
+#[cfg(feature = "slip10")]
+impl<K> DerivationCache<K>
+where
+    K: Clone + Slip10NonHardenedDerivation,
+{
+    /// Derives a child extended key along a path (relative to the root) with
+    /// [`Slip10NonHardenedDerivation`].
+    pub fn derive_slip10_path(&mut self, path: &DerivationPath) -> Result<K> {
+        self.derive_with(path.children(), |key, child| key.derive_slip10_child(child))
+    }
+}
+
+#[cfg(feature = "slip10")]
+impl<K> DerivationCache<K>
+where
+    K: Clone + Slip10HardenedOnlyDerivation,
+{
+    /// Derives a hardened child extended private key along a path (relative to the root) with
+    /// [`Slip10HardenedOnlyDerivation`].
+    pub fn derive_slip10_hardened_path(&mut self, path: &HardenedDerivationPath) -> Result<K> {
+        let children = path.children().map(ChildNumber::from).collect::<Vec<_>>();
+        self.derive_with(&children, |key, child| {
+            // the path only contains hardened children
+            key.derive_slip10_child(child.try_into()?)
+        })
+    }
+}
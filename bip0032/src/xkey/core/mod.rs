@@ -2,7 +2,13 @@
 
 use hmac::{Hmac, KeyInit, Mac};
 use sha2::Sha512;
-use zeroize::Zeroize;
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+    path::{ChildNumber, DerivationPath},
+    xkey::KeyOrigin,
+};
 
 mod private;
 mod public;
@@ -10,7 +16,7 @@ mod public;
 pub use self::{private::ExtendedPrivateKey, public::ExtendedPublicKey};
 
 /// Common metadata for extended keys (depth, parent link, and chain code).
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub(crate) struct ExtendedKeyMetadata {
     pub(crate) depth: u8,
     pub(crate) parent_fingerprint: [u8; 4],
@@ -33,20 +39,64 @@ impl Drop for ExtendedKeyMetadata {
     }
 }
 
-pub(crate) fn key_fingerprint(public_key_bytes: &[u8]) -> [u8; 4] {
+impl ConstantTimeEq for ExtendedKeyMetadata {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.depth.ct_eq(&other.depth)
+            & self.parent_fingerprint.ct_eq(&other.parent_fingerprint)
+            & self.child_number.ct_eq(&other.child_number)
+            & self.chain_code.ct_eq(&other.chain_code)
+    }
+}
+
+pub(crate) fn key_identifier(public_key_bytes: &[u8]) -> [u8; 20] {
     use sha2::Digest;
 
     // Extended keys can be identified by the Hash160 (RIPEMD160 after SHA256) of
     // the serialized ECDSA public key K, ignoring the chain code.
     let hash = sha2::Sha256::digest(public_key_bytes);
-    let identifier = ripemd::Ripemd160::digest(hash);
+    ripemd::Ripemd160::digest(hash).into()
+}
 
+pub(crate) fn key_fingerprint(public_key_bytes: &[u8]) -> [u8; 4] {
     // the first 32 bits of the identifier are called the key fingerprint
+    let identifier = key_identifier(public_key_bytes);
     let mut out = [0u8; 4];
     out.copy_from_slice(&identifier[..4]);
     out
 }
 
+/// Returns the origin of a child key, given the parent metadata and origin.
+///
+/// The origin of a master key is implied by its own fingerprint, so the children of a master key
+/// start a new origin rooted at the fingerprint of their parent.
+pub(crate) fn child_origin(
+    parent_meta: &ExtendedKeyMetadata,
+    parent_origin: Option<&KeyOrigin>,
+    parent_fingerprint: [u8; 4],
+    child: ChildNumber,
+) -> Option<KeyOrigin> {
+    if parent_meta.depth == 0 {
+        Some(KeyOrigin::new(parent_fingerprint, [child].into_iter().collect()))
+    } else {
+        parent_origin.map(|origin| origin.child(child))
+    }
+}
+
+/// Returns the origin of a key derived along a path, given the metadata and origin of the key the
+/// path starts from.
+pub(crate) fn path_origin(
+    meta: &ExtendedKeyMetadata,
+    origin: Option<&KeyOrigin>,
+    fingerprint: impl FnOnce() -> [u8; 4],
+    path: &DerivationPath,
+) -> Option<KeyOrigin> {
+    if meta.depth == 0 {
+        Some(KeyOrigin::new(fingerprint(), path.clone()))
+    } else {
+        origin.map(|origin| origin.descendant(path))
+    }
+}
+
 pub(crate) fn derive_master_key_parts(seed: &[u8], domain: &[u8]) -> ([u8; 32], [u8; 32]) {
     hmac_sha512_split(domain, |mac| mac.update(seed))
 }
@@ -55,11 +105,27 @@ pub(crate) fn hmac_sha512_split(
     key: &[u8],
     f: impl FnOnce(&mut Hmac<Sha512>),
 ) -> ([u8; 32], [u8; 32]) {
-    let mut mac = Hmac::<Sha512>::new_from_slice(key)
-        .expect("HMAC-SHA512 must accept the provided key length");
+    let mut mac = hmac_sha512(key);
+    f(&mut mac);
+    finalize_split(mac)
+}
 
+pub(crate) fn hmac_sha512_bytes(
+    key: &[u8],
+    f: impl FnOnce(&mut Hmac<Sha512>),
+) -> Zeroizing<[u8; 64]> {
+    let mut mac = hmac_sha512(key);
     f(&mut mac);
+    let mut output = Zeroizing::new([0u8; 64]);
+    output.copy_from_slice(&mac.finalize().into_bytes());
+    output
+}
+
+pub(crate) fn hmac_sha512(key: &[u8]) -> Hmac<Sha512> {
+    Hmac::<Sha512>::new_from_slice(key).expect("HMAC-SHA512 must accept the provided key length")
+}
 
+pub(crate) fn finalize_split(mac: Hmac<Sha512>) -> ([u8; 32], [u8; 32]) {
     let output = mac.finalize().into_bytes();
     debug_assert_eq!(output.len(), 64, "HMAC-SHA512 should produce a 64-byte output");
 
@@ -1,16 +1,20 @@
 #[cfg(not(feature = "std"))]
 use alloc::format;
-use core::str::FromStr;
+use core::{fmt, str::FromStr};
 
 use hmac::Mac;
-use zeroize::Zeroizing;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
 
 use super::*;
 use crate::{
-    curve::{Bip32Curve, Curve, CurvePrivateKey, CurvePublicKey, TweakableKey},
+    curve::{
+        Bip32Curve, Curve, CurvePrivateKey, CurvePublicKey, SchnorrSigner, SignatureAlgorithm,
+        Signer, TweakableKey,
+    },
     error::{Error, ErrorKind, Result},
     path::{ChildNumber, DerivationPath},
-    xkey::payload::*,
+    xkey::{KeyOrigin, origin::Fingerprint, payload::*},
 };
 
 /// A BIP32 extended private key.
@@ -19,14 +23,47 @@ pub struct ExtendedPrivateKey<C: Curve> {
     pub(crate) meta: ExtendedKeyMetadata,
     /// Private key data.
     pub(crate) private_key: C::PrivateKey,
+    /// The origin of a non-master key, if known.
+    pub(crate) origin: Option<KeyOrigin>,
 }
 
 impl<C: Curve> Clone for ExtendedPrivateKey<C> {
     fn clone(&self) -> Self {
-        Self { meta: self.meta.clone(), private_key: self.private_key.clone() }
+        Self {
+            meta: self.meta.clone(),
+            private_key: self.private_key.clone(),
+            origin: self.origin.clone(),
+        }
+    }
+}
+
+/// Prints the fingerprint, depth and child number of the key, never the private key material.
+impl<C: Curve> fmt::Debug for ExtendedPrivateKey<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("fingerprint", &Fingerprint(self.fingerprint()))
+            .field("depth", &self.meta.depth)
+            .field("child_number", &format_args!("{}", self.child_number()))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Compares the private key and the metadata (depth, parent link and chain code) in constant time.
+/// The origin is not compared.
+impl<C: Curve> PartialEq for ExtendedPrivateKey<C>
+where
+    <C::PrivateKey as CurvePrivateKey>::Bytes: Zeroize,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let key = Zeroizing::new(CurvePrivateKey::to_bytes(&self.private_key));
+        let other_key = Zeroizing::new(CurvePrivateKey::to_bytes(&other.private_key));
+        let key_eq = key.as_ref().ct_eq(other_key.as_ref());
+        (self.meta.ct_eq(&other.meta) & key_eq).into()
     }
 }
 
+impl<C: Curve> Eq for ExtendedPrivateKey<C> where <C::PrivateKey as CurvePrivateKey>::Bytes: Zeroize {}
+
 impl<C: Curve> ExtendedPrivateKey<C> {
     /// Generates a master extended private key from a seed.
     pub fn new(seed: &[u8]) -> Result<Self>
@@ -51,21 +88,60 @@ impl<C: Curve> ExtendedPrivateKey<C> {
                 chain_code,
             },
             private_key,
+            origin: None,
         })
     }
 
     /// Returns the corresponding extended public key.
     pub fn public_key(&self) -> ExtendedPublicKey<C> {
-        ExtendedPublicKey { meta: self.meta.clone(), public_key: self.private_key.to_public() }
+        ExtendedPublicKey {
+            meta: self.meta.clone(),
+            public_key: self.private_key.to_public(),
+            origin: self.origin.clone(),
+        }
     }
 }
 
 impl<C: Curve> ExtendedPrivateKey<C> {
+    /// Returns the depth of this key (0 for the master key).
+    pub fn depth(&self) -> u8 {
+        self.meta.depth
+    }
+
+    /// Returns the child number of this key (0 for the master key).
+    pub fn child_number(&self) -> ChildNumber {
+        ChildNumber::from(self.meta.child_number)
+    }
+
     /// Returns the fingerprint of the parent's key.
     pub fn parent_fingerprint(&self) -> [u8; 4] {
         self.meta.parent_fingerprint
     }
 
+    /// Returns the identifier (Hash160 of the serialized public key) of this key.
+    pub fn identifier(&self) -> [u8; 20] {
+        let public_key = self.private_key.to_public();
+        key_identifier(CurvePublicKey::to_bytes(&public_key).as_ref())
+    }
+
+    /// Returns the fingerprint (first 4 bytes of the identifier) of this key.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let public_key = self.private_key.to_public();
+        key_fingerprint(CurvePublicKey::to_bytes(&public_key).as_ref())
+    }
+
+    /// Returns the origin (master key fingerprint and derivation path) of this key.
+    ///
+    /// The origin is known for master keys and for every key derived from one. Keys decoded at
+    /// a non-zero depth have no known origin.
+    pub fn origin(&self) -> Option<KeyOrigin> {
+        if self.meta.depth == 0 {
+            Some(KeyOrigin::new(self.fingerprint(), DerivationPath::default()))
+        } else {
+            self.origin.clone()
+        }
+    }
+
     /// Returns the chain code for this key.
     pub fn chain_code(&self) -> [u8; 32] {
         self.meta.chain_code
@@ -91,9 +167,63 @@ where
 {
     /// Derives a child extended private key.
     pub fn derive_child(&self, child: ChildNumber) -> Result<Self> {
-        let parent_public = self.private_key.to_public();
-        let parent_public_bytes = CurvePublicKey::to_bytes(&parent_public);
+        let parent_public_bytes = CurvePublicKey::to_bytes(&self.private_key.to_public());
+        let (child_key, chain_code) = self.derive_child_key(child, Some(&parent_public_bytes))?;
+
+        let parent_fingerprint = key_fingerprint(parent_public_bytes.as_ref());
+        Ok(Self {
+            meta: ExtendedKeyMetadata {
+                depth: self.meta.depth.saturating_add(1),
+                parent_fingerprint,
+                child_number: child.into(),
+                chain_code,
+            },
+            private_key: child_key,
+            origin: child_origin(&self.meta, self.origin.as_ref(), parent_fingerprint, child),
+        })
+    }
 
+    /// Derives a child extended private key along a path.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self> {
+        let Some((last, init)) = path.children().split_last() else {
+            return Ok(self.clone());
+        };
+        if init.is_empty() {
+            return self.derive_child(*last);
+        }
+
+        // Only the parent fingerprint of the last step is observable, so the intermediate keys
+        // leave it unset, and hardened intermediate steps skip the parent public key entirely.
+        let mut key: Option<Self> = None;
+        for child in init {
+            let parent = key.as_ref().unwrap_or(self);
+            let (child_key, chain_code) = parent.derive_child_key(*child, None)?;
+            key = Some(Self {
+                meta: ExtendedKeyMetadata {
+                    depth: parent.meta.depth.saturating_add(1),
+                    parent_fingerprint: [0u8; 4],
+                    child_number: (*child).into(),
+                    chain_code,
+                },
+                private_key: child_key,
+                origin: None,
+            });
+        }
+
+        let mut derived = key.as_ref().unwrap_or(self).derive_child(*last)?;
+        derived.origin = path_origin(&self.meta, self.origin.as_ref(), || self.fingerprint(), path);
+        Ok(derived)
+    }
+
+    /// Derives the private key and chain code of a child.
+    ///
+    /// The parent public key bytes are only used by non-hardened children, and are computed if
+    /// not provided.
+    fn derive_child_key(
+        &self,
+        child: ChildNumber,
+        parent_public_bytes: Option<&<C::PublicKey as CurvePublicKey>::Bytes>,
+    ) -> Result<(C::PrivateKey, [u8; 32])> {
         let (left, right) = hmac_sha512_split(&self.meta.chain_code, |mac| {
             if child.is_hardened() {
                 let mut data = Zeroizing::new([0u8; 1 + 32 + 4]);
@@ -101,7 +231,12 @@ where
                 data[33..].copy_from_slice(&child.to_bytes());
                 mac.update(data.as_ref());
             } else {
-                mac.update(parent_public_bytes.as_ref());
+                match parent_public_bytes {
+                    Some(bytes) => mac.update(bytes.as_ref()),
+                    None => {
+                        mac.update(CurvePublicKey::to_bytes(&self.private_key.to_public()).as_ref())
+                    },
+                }
                 mac.update(&child.to_bytes());
             }
         });
@@ -114,24 +249,58 @@ where
                 .set_source(err)
         })?;
 
-        Ok(Self {
-            meta: ExtendedKeyMetadata {
-                depth: self.meta.depth.saturating_add(1),
-                parent_fingerprint: key_fingerprint(parent_public_bytes.as_ref()),
-                child_number: child.into(),
-                chain_code: right,
-            },
-            private_key: child_key,
+        Ok((child_key, right))
+    }
+}
+
+// Signing
+impl<C: Curve> ExtendedPrivateKey<C> {
+    /// Signs a message with this key using the signature algorithm `A`.
+    ///
+    /// ```
+    /// # #[cfg(feature = "k256")] {
+    /// use bip0032::{
+    ///     ExtendedPrivateKey,
+    ///     curve::{Ecdsa, Schnorr, secp256k1::*},
+    /// };
+    ///
+    /// let master = ExtendedPrivateKey::<Secp256k1Curve<K256Backend>>::new(&[0x42; 32]).unwrap();
+    /// let digest = [0x01; 32];
+    ///
+    /// let signature = master.sign::<Ecdsa>(&digest).unwrap();
+    /// master.public_key().verify::<Ecdsa>(&digest, &signature).unwrap();
+    ///
+    /// let signature = master.sign::<Schnorr>(b"message").unwrap();
+    /// master.public_key().verify::<Schnorr>(b"message", &signature).unwrap();
+    /// # }
+    /// ```
+    pub fn sign<A>(&self, message: &A::Message) -> Result<A::Signature>
+    where
+        A: SignatureAlgorithm,
+        C::PrivateKey: Signer<A>,
+    {
+        Signer::<A>::sign(&self.private_key, message).map_err(|err| {
+            Error::new(ErrorKind::InvalidSignature, "signing failed").set_source(err)
         })
     }
 
-    /// Derives a child extended private key along a path.
-    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self> {
-        let mut key = self.clone();
-        for child in path.children() {
-            key = key.derive_child(*child)?;
-        }
-        Ok(key)
+    /// Signs a message with this key using [`Schnorr`](crate::curve::Schnorr) signatures with
+    /// auxiliary randomness.
+    ///
+    /// The auxiliary randomness should be freshly drawn from a cryptographically secure source
+    /// for every signature, `sign::<Schnorr>` is the deterministic variant with all-zero
+    /// auxiliary randomness.
+    pub fn sign_schnorr_with_aux_rand(
+        &self,
+        message: &[u8],
+        aux_rand: &[u8; 32],
+    ) -> Result<[u8; 64]>
+    where
+        C::PrivateKey: SchnorrSigner,
+    {
+        self.private_key.sign_with_aux_rand(message, aux_rand).map_err(|err| {
+            Error::new(ErrorKind::InvalidSignature, "signing failed").set_source(err)
+        })
     }
 }
 
@@ -149,11 +318,7 @@ where
 {
     /// Encodes this key with the specified version bytes.
     pub fn encode_with(&self, version: Version) -> Result<ExtendedKeyPayload> {
-        if !version.is_private() {
-            return Err(Error::new(ErrorKind::InvalidVersion, "expected private version bytes")
-                .with_context("version", version));
-        }
-
+        check_version_visibility(version, true)?;
         Ok(self.encode_with_unchecked(version))
     }
 
@@ -208,6 +373,6 @@ where
                 .set_source(err)
         })?;
 
-        Ok(Self { meta: payload.meta.clone(), private_key })
+        Ok(Self { meta: payload.meta.clone(), private_key, origin: None })
     }
 }
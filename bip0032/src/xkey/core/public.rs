@@ -1,16 +1,30 @@
 #[cfg(not(feature = "std"))]
-use alloc::format;
-use core::str::FromStr;
+use alloc::{format, vec::Vec};
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Range,
+    str::FromStr,
+};
 
-use hmac::Mac;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
 use zeroize::Zeroizing;
 
-use super::{ExtendedKeyMetadata, hmac_sha512_split, key_fingerprint};
+use super::{
+    ExtendedKeyMetadata, child_origin, finalize_split, hmac_sha512, key_fingerprint,
+    key_identifier, path_origin,
+};
 use crate::{
-    curve::{Bip32Curve, Curve, CurvePublicKey, TweakableKey},
+    curve::{Bip32Curve, Curve, CurvePublicKey, SignatureAlgorithm, TweakableKey, Verifier},
     error::{Error, ErrorKind, Result},
     path::{ChildNumber, DerivationPath},
-    xkey::{Version, payload::ExtendedKeyPayload},
+    xkey::{
+        KeyOrigin, Version,
+        origin::Fingerprint,
+        payload::{ExtendedKeyPayload, check_version_visibility},
+    },
 };
 
 /// A BIP32 extended public key.
@@ -19,20 +33,99 @@ pub struct ExtendedPublicKey<C: Curve> {
     pub(crate) meta: ExtendedKeyMetadata,
     /// Public key data.
     pub(crate) public_key: C::PublicKey,
+    /// The origin of a non-master key, if known.
+    pub(crate) origin: Option<KeyOrigin>,
 }
 
 impl<C: Curve> Clone for ExtendedPublicKey<C> {
     fn clone(&self) -> Self {
-        Self { meta: self.meta.clone(), public_key: self.public_key.clone() }
+        Self {
+            meta: self.meta.clone(),
+            public_key: self.public_key.clone(),
+            origin: self.origin.clone(),
+        }
+    }
+}
+
+impl<C: Curve> fmt::Debug for ExtendedPublicKey<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedPublicKey")
+            .field("fingerprint", &Fingerprint(self.fingerprint()))
+            .field("depth", &self.meta.depth)
+            .field("child_number", &format_args!("{}", self.child_number()))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Compares the public key and the metadata (depth, parent link and chain code). The origin is not
+/// compared, and hashing and ordering are consistent with this.
+impl<C: Curve> PartialEq for ExtendedPublicKey<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.meta == other.meta && self.to_bytes().as_ref() == other.to_bytes().as_ref()
+    }
+}
+
+impl<C: Curve> Eq for ExtendedPublicKey<C> {}
+
+impl<C: Curve> Hash for ExtendedPublicKey<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.meta.hash(state);
+        self.to_bytes().as_ref().hash(state);
+    }
+}
+
+impl<C: Curve> PartialOrd for ExtendedPublicKey<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Curve> Ord for ExtendedPublicKey<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.meta
+            .cmp(&other.meta)
+            .then_with(|| self.to_bytes().as_ref().cmp(other.to_bytes().as_ref()))
     }
 }
 
 impl<C: Curve> ExtendedPublicKey<C> {
+    /// Returns the depth of this key (0 for the master key).
+    pub fn depth(&self) -> u8 {
+        self.meta.depth
+    }
+
+    /// Returns the child number of this key (0 for the master key).
+    pub fn child_number(&self) -> ChildNumber {
+        ChildNumber::from(self.meta.child_number)
+    }
+
     /// Returns the fingerprint of the parent's key.
     pub fn parent_fingerprint(&self) -> [u8; 4] {
         self.meta.parent_fingerprint
     }
 
+    /// Returns the identifier (Hash160 of the serialized public key) of this key.
+    pub fn identifier(&self) -> [u8; 20] {
+        key_identifier(CurvePublicKey::to_bytes(&self.public_key).as_ref())
+    }
+
+    /// Returns the fingerprint (first 4 bytes of the identifier) of this key.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        key_fingerprint(CurvePublicKey::to_bytes(&self.public_key).as_ref())
+    }
+
+    /// Returns the origin (master key fingerprint and derivation path) of this key.
+    ///
+    /// The origin is known for master keys and for every key derived from one. Keys decoded at
+    /// a non-zero depth have no known origin.
+    pub fn origin(&self) -> Option<KeyOrigin> {
+        if self.meta.depth == 0 {
+            Some(KeyOrigin::new(self.fingerprint(), DerivationPath::default()))
+        } else {
+            self.origin.clone()
+        }
+    }
+
     /// Returns the chain code for this key.
     pub fn chain_code(&self) -> [u8; 32] {
         self.meta.chain_code
@@ -51,6 +144,102 @@ where
 {
     /// Derives a child extended public key (non-hardened only).
     pub fn derive_child(&self, child: ChildNumber) -> Result<Self> {
+        ChildDeriver::new(self).derive(child)
+    }
+
+    /// Derives the child extended public keys at the given non-hardened indices.
+    ///
+    /// The parent public key serialization, fingerprint and HMAC key schedule are computed once
+    /// and shared by all children. The results and errors match [`Self::derive_child`].
+    pub fn derive_children(
+        &self,
+        children: impl IntoIterator<Item = ChildNumber>,
+    ) -> Result<Vec<Self>> {
+        let deriver = ChildDeriver::new(self);
+        children.into_iter().map(|child| deriver.derive(child)).collect()
+    }
+
+    /// Derives the child extended public keys at every non-hardened index in the given range.
+    ///
+    /// See [`Self::derive_children`].
+    pub fn derive_range(&self, indices: Range<u32>) -> Result<Vec<Self>> {
+        let deriver = ChildDeriver::new(self);
+        indices.map(|index| deriver.derive(ChildNumber::new(index, false)?)).collect()
+    }
+
+    /// Derives the child extended public keys at every non-hardened index in the given range,
+    /// spread over the rayon thread pool.
+    ///
+    /// The results are in index order and match [`Self::derive_range`]. If several indices fail,
+    /// the error of any one of them may be returned.
+    #[cfg(feature = "rayon")]
+    pub fn par_derive_range(&self, indices: Range<u32>) -> Result<Vec<Self>>
+    where
+        C::PublicKey: Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        let deriver = ChildDeriver::new(self);
+        indices
+            .into_par_iter()
+            .map(|index| deriver.derive(ChildNumber::new(index, false)?))
+            .collect()
+    }
+
+    /// Derives a child extended public key along a path (non-hardened only).
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self> {
+        let Some((last, init)) = path.children().split_last() else {
+            return Ok(self.clone());
+        };
+        if init.is_empty() {
+            return self.derive_child(*last);
+        }
+
+        // Only the parent fingerprint of the last step is observable, so the intermediate keys
+        // leave it unset.
+        let mut key: Option<Self> = None;
+        for child in init {
+            let parent = key.as_ref().unwrap_or(self);
+            key = Some(ChildDeriver::without_fingerprint(parent).derive(*child)?);
+        }
+
+        let mut derived = key.as_ref().unwrap_or(self).derive_child(*last)?;
+        derived.origin = path_origin(&self.meta, self.origin.as_ref(), || self.fingerprint(), path);
+        Ok(derived)
+    }
+}
+
+/// Parent-side state shared by the children of one extended public key.
+struct ChildDeriver<'a, C: Curve> {
+    parent: &'a ExtendedPublicKey<C>,
+    /// Unset for the intermediate keys of a path, whose children get no fingerprint or origin.
+    parent_fingerprint: Option<[u8; 4]>,
+    /// HMAC-SHA512 keyed with the parent chain code, with the parent public key absorbed.
+    mac: Hmac<Sha512>,
+}
+
+impl<'a, C> ChildDeriver<'a, C>
+where
+    C: Bip32Curve,
+    C::PublicKey: TweakableKey,
+{
+    fn new(parent: &'a ExtendedPublicKey<C>) -> Self {
+        Self::with_fingerprint(parent, true)
+    }
+
+    fn without_fingerprint(parent: &'a ExtendedPublicKey<C>) -> Self {
+        Self::with_fingerprint(parent, false)
+    }
+
+    fn with_fingerprint(parent: &'a ExtendedPublicKey<C>, fingerprint: bool) -> Self {
+        let public_key_bytes = CurvePublicKey::to_bytes(&parent.public_key);
+        let mut mac = hmac_sha512(&parent.meta.chain_code);
+        mac.update(public_key_bytes.as_ref());
+        let parent_fingerprint = fingerprint.then(|| key_fingerprint(public_key_bytes.as_ref()));
+        Self { parent, parent_fingerprint, mac }
+    }
+
+    fn derive(&self, child: ChildNumber) -> Result<ExtendedPublicKey<C>> {
         if child.is_hardened() {
             return Err(Error::new(
                 ErrorKind::InvalidDerivation,
@@ -60,38 +249,45 @@ where
             .with_context("hardened", true));
         }
 
-        let public_key_bytes = CurvePublicKey::to_bytes(&self.public_key);
-        let (left, right) = hmac_sha512_split(&self.meta.chain_code, |mac| {
-            mac.update(public_key_bytes.as_ref());
-            mac.update(&child.to_bytes());
-        });
+        let mut mac = self.mac.clone();
+        mac.update(&child.to_bytes());
+        let (left, right) = finalize_split(mac);
 
         let left = Zeroizing::new(left);
-        let child_public = self.public_key.add_tweak(&left).map_err(|err| {
+        let child_public = self.parent.public_key.add_tweak(&left).map_err(|err| {
             Error::new(ErrorKind::InvalidDerivation, "invalid child public key")
                 .with_context("child_index", child.index())
                 .with_context("hardened", false)
                 .set_source(err)
         })?;
 
-        Ok(Self {
+        let parent = self.parent;
+        Ok(ExtendedPublicKey {
             meta: ExtendedKeyMetadata {
-                depth: self.meta.depth.saturating_add(1),
-                parent_fingerprint: key_fingerprint(public_key_bytes.as_ref()),
+                depth: parent.meta.depth.saturating_add(1),
+                parent_fingerprint: self.parent_fingerprint.unwrap_or_default(),
                 child_number: child.into(),
                 chain_code: right,
             },
             public_key: child_public,
+            origin: self.parent_fingerprint.and_then(|parent_fingerprint| {
+                child_origin(&parent.meta, parent.origin.as_ref(), parent_fingerprint, child)
+            }),
         })
     }
+}
 
-    /// Derives a child extended public key along a path (non-hardened only).
-    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self> {
-        let mut key = self.clone();
-        for child in path.children() {
-            key = key.derive_child(*child)?;
-        }
-        Ok(key)
+// Signature verification
+impl<C: Curve> ExtendedPublicKey<C> {
+    /// Verifies a signature of a message by this key using the signature algorithm `A`.
+    pub fn verify<A>(&self, message: &A::Message, signature: &A::Signature) -> Result<()>
+    where
+        A: SignatureAlgorithm,
+        C::PublicKey: Verifier<A>,
+    {
+        Verifier::<A>::verify(&self.public_key, message, signature).map_err(|err| {
+            Error::new(ErrorKind::InvalidSignature, "signature verification failed").set_source(err)
+        })
     }
 }
 
@@ -103,11 +299,7 @@ where
 {
     /// Encodes this key with the specified version bytes.
     pub fn encode_with(&self, version: Version) -> Result<ExtendedKeyPayload> {
-        if !version.is_public() {
-            return Err(Error::new(ErrorKind::InvalidVersion, "expected public version bytes")
-                .with_context("version", version));
-        }
-
+        check_version_visibility(version, false)?;
         Ok(self.encode_with_unchecked(version))
     }
 
@@ -155,6 +347,6 @@ where
                     .set_source(err)
             })?;
 
-        Ok(Self { meta: payload.meta.clone(), public_key })
+        Ok(Self { meta: payload.meta.clone(), public_key, origin: None })
     }
 }
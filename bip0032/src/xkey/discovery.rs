@@ -0,0 +1,244 @@
+//! Gap-limit account discovery (BIP-0044).
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{
+    curve::{Bip32Curve, CurvePublicKey, TweakableKey},
+    error::Result,
+    path::{Bip44Path, Change, ChildNumber, CoinType, DerivationPath, Purpose},
+    xkey::{ExtendedPrivateKey, ExtendedPublicKey},
+};
+
+/// Tells whether a public key has been used, e.g. by looking up its addresses in a local index.
+///
+/// Implemented for closures `FnMut(&B) -> bool`, where `B` is the serialized public key (e.g.
+/// `[u8; 33]` for secp256k1 keys).
+pub trait KeyUsage<B> {
+    /// Returns whether the public key has been used.
+    fn is_used(&mut self, public_key: &B) -> bool;
+}
+
+impl<B, F: FnMut(&B) -> bool> KeyUsage<B> for F {
+    fn is_used(&mut self, public_key: &B) -> bool {
+        self(public_key)
+    }
+}
+
+/// The used indices of an external or internal chain.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChainScan {
+    change: Change,
+    used: Vec<u32>,
+}
+
+impl ChainScan {
+    /// Returns the scanned chain.
+    pub const fn change(&self) -> Change {
+        self.change
+    }
+
+    /// Returns the used indices, in ascending order.
+    pub fn used(&self) -> &[u32] {
+        &self.used
+    }
+
+    /// Returns whether any index of this chain is used.
+    pub fn is_used(&self) -> bool {
+        !self.used.is_empty()
+    }
+
+    /// Returns the next unused index: the one after the highest used index, or 0.
+    pub fn next_unused(&self) -> u32 {
+        self.used.last().map_or(0, |last| last + 1)
+    }
+}
+
+/// The used indices of the external and internal chains of an account.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountScan {
+    account: Option<Bip44Path>,
+    external: ChainScan,
+    internal: ChainScan,
+}
+
+impl AccountScan {
+    /// Returns the account level path, if known from the origin of the scanned key.
+    pub const fn account(&self) -> Option<&Bip44Path> {
+        self.account.as_ref()
+    }
+
+    /// Returns the scan of the external chain (receiving addresses).
+    pub const fn external(&self) -> &ChainScan {
+        &self.external
+    }
+
+    /// Returns the scan of the internal chain (change addresses).
+    pub const fn internal(&self) -> &ChainScan {
+        &self.internal
+    }
+
+    /// Returns the scan of the given chain.
+    pub const fn chain(&self, change: Change) -> &ChainScan {
+        match change {
+            Change::External => &self.external,
+            Change::Internal => &self.internal,
+        }
+    }
+
+    /// Returns whether any index of this account is used.
+    pub fn is_used(&self) -> bool {
+        self.external.is_used() || self.internal.is_used()
+    }
+}
+
+/// Discovers used accounts and addresses with a gap limit, as in BIP-0044.
+///
+/// A chain is scanned from index 0 until `gap_limit` consecutive indices are unused. Accounts are
+/// discovered in order for each purpose, until an account without used external addresses.
+///
+/// ```
+/// # #[cfg(feature = "k256")] {
+/// use bip0032::{
+///     AccountDiscovery, Bip44Path, Change, CoinType, DerivationPath, ExtendedPrivateKey, Purpose,
+///     curve::secp256k1::*,
+/// };
+///
+/// let master = ExtendedPrivateKey::<Secp256k1Curve<K256Backend>>::new(&[0x42; 32]).unwrap();
+///
+/// // a local index of used keys, here the receiving key m/84'/0'/0'/0/3
+/// let path = "m/84'/0'/0'/0/3".parse::<DerivationPath>().unwrap();
+/// let used_key = master.derive_path(&path).unwrap().public_key().to_bytes();
+///
+/// let discovery = AccountDiscovery::new(CoinType::BITCOIN).with_gap_limit(5);
+/// let accounts = discovery
+///     .discover(&master, &[Purpose::Bip84], &mut |key: &[u8; 33]| *key == used_key)
+///     .unwrap();
+/// assert_eq!(accounts.len(), 1);
+/// assert_eq!(accounts[0].account(), Some(&Bip44Path::new(Purpose::Bip84, CoinType::BITCOIN, 0)));
+/// assert_eq!(accounts[0].chain(Change::External).used(), &[3]);
+/// assert_eq!(accounts[0].chain(Change::External).next_unused(), 4);
+/// assert_eq!(accounts[0].chain(Change::Internal).next_unused(), 0);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AccountDiscovery {
+    coin_type: CoinType,
+    gap_limit: u32,
+}
+
+impl AccountDiscovery {
+    /// The gap limit of BIP-0044.
+    pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+    /// Creates a discovery of accounts of the given coin type with the default gap limit.
+    pub const fn new(coin_type: CoinType) -> Self {
+        Self { coin_type, gap_limit: Self::DEFAULT_GAP_LIMIT }
+    }
+
+    /// Sets the number of consecutive unused indices that ends the scan of a chain.
+    ///
+    /// A gap limit of 0 is treated as 1.
+    pub const fn with_gap_limit(mut self, gap_limit: u32) -> Self {
+        self.gap_limit = if gap_limit == 0 { 1 } else { gap_limit };
+        self
+    }
+
+    /// Returns the coin type.
+    pub const fn coin_type(&self) -> CoinType {
+        self.coin_type
+    }
+
+    /// Returns the gap limit.
+    pub const fn gap_limit(&self) -> u32 {
+        self.gap_limit
+    }
+
+    /// Discovers the used accounts below a root key for each purpose, in purpose then account
+    /// order.
+    ///
+    /// For each purpose, accounts are scanned from account 0 until an account without used
+    /// external addresses, which is not reported.
+    pub fn discover<C, U>(
+        &self,
+        root: &ExtendedPrivateKey<C>,
+        purposes: &[Purpose],
+        usage: &mut U,
+    ) -> Result<Vec<AccountScan>>
+    where
+        C: Bip32Curve,
+        C::PrivateKey: TweakableKey,
+        C::PublicKey: TweakableKey,
+        U: KeyUsage<<C::PublicKey as CurvePublicKey>::Bytes> + ?Sized,
+    {
+        let mut accounts = Vec::new();
+        for &purpose in purposes {
+            for account in 0..ChildNumber::HARDENED_OFFSET {
+                let path = Bip44Path::new(purpose, self.coin_type, account);
+                let key = root.derive_path(&DerivationPath::from(path.account_path()?))?;
+                let mut scan = self.scan_account(&key.public_key(), usage)?;
+                if !scan.external.is_used() {
+                    break;
+                }
+                // the root key may have no origin to tell the account path from
+                scan.account = Some(path);
+                accounts.push(scan);
+            }
+        }
+        Ok(accounts)
+    }
+
+    /// Scans the external and internal chains of an account level key.
+    pub fn scan_account<C, U>(
+        &self,
+        account: &ExtendedPublicKey<C>,
+        usage: &mut U,
+    ) -> Result<AccountScan>
+    where
+        C: Bip32Curve,
+        C::PublicKey: TweakableKey,
+        U: KeyUsage<<C::PublicKey as CurvePublicKey>::Bytes> + ?Sized,
+    {
+        let origin = account.origin();
+        Ok(AccountScan {
+            account: origin.and_then(|origin| Bip44Path::try_from(origin.path()).ok()),
+            external: self.scan_chain(account, Change::External, usage)?,
+            internal: self.scan_chain(account, Change::Internal, usage)?,
+        })
+    }
+
+    /// Scans one chain of an account level key.
+    pub fn scan_chain<C, U>(
+        &self,
+        account: &ExtendedPublicKey<C>,
+        change: Change,
+        usage: &mut U,
+    ) -> Result<ChainScan>
+    where
+        C: Bip32Curve,
+        C::PublicKey: TweakableKey,
+        U: KeyUsage<<C::PublicKey as CurvePublicKey>::Bytes> + ?Sized,
+    {
+        let chain = account.derive_child(ChildNumber::new(change.index(), false)?)?;
+
+        let mut used = Vec::new();
+        let mut start = 0;
+        let mut unused_run = 0;
+        while unused_run < self.gap_limit && start < ChildNumber::HARDENED_OFFSET {
+            // derive the indices that may still end the scan in one batch
+            let end = start
+                .saturating_add(self.gap_limit - unused_run)
+                .min(ChildNumber::HARDENED_OFFSET);
+            for (index, key) in (start..end).zip(chain.derive_range(start..end)?) {
+                if usage.is_used(&key.to_bytes()) {
+                    used.push(index);
+                    unused_run = 0;
+                } else {
+                    unused_run += 1;
+                }
+            }
+            start = end;
+        }
+        Ok(ChainScan { change, used })
+    }
+}
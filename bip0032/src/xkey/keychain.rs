@@ -0,0 +1,334 @@
+//! Account keychains: the external and internal chains of an account with index bookkeeping.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use crate::{
+    curve::{Bip32Curve, Curve, CurvePublicKey, TweakableKey},
+    error::{Error, ErrorKind, Result},
+    path::{Change, ChildNumber},
+    xkey::{ExtendedPublicKey, KeyOrigin, OriginKey, Version, VersionedPublicKey},
+};
+
+/// The index bookkeeping of one chain of an [`AccountKeychain`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChainState {
+    /// One past the highest handed out index (0 if no key was handed out).
+    pub next_index: u32,
+    /// The highest used index, if any.
+    pub highest_used: Option<u32>,
+}
+
+impl ChainState {
+    /// Returns the index of the next key to hand out: after every handed out and used index.
+    pub fn next_unused(&self) -> u32 {
+        match self.highest_used {
+            Some(used) => self.next_index.max(used.saturating_add(1)),
+            None => self.next_index,
+        }
+    }
+}
+
+/// The persistent state of an [`AccountKeychain`].
+///
+/// With the `serde` feature, the state can be serialized, e.g. to store a watch-only wallet.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "VersionedPublicKey<C>: serde::Serialize",
+        deserialize = "VersionedPublicKey<C>: serde::Deserialize<'de>"
+    ))
+)]
+pub struct KeychainState<C: Curve> {
+    /// The account level extended public key, with its version bytes.
+    pub account: VersionedPublicKey<C>,
+    /// The origin of the account key, if known.
+    pub origin: Option<KeyOrigin>,
+    /// The number of keys looked ahead of the next unused index of each chain.
+    pub lookahead: u32,
+    /// The bookkeeping of the external chain.
+    pub external: ChainState,
+    /// The bookkeeping of the internal chain.
+    pub internal: ChainState,
+}
+
+impl<C: Curve> Clone for KeychainState<C> {
+    fn clone(&self) -> Self {
+        Self {
+            account: self.account.clone(),
+            origin: self.origin.clone(),
+            lookahead: self.lookahead,
+            external: self.external,
+            internal: self.internal,
+        }
+    }
+}
+
+struct Chain<C: Curve> {
+    key: ExtendedPublicKey<C>,
+    state: ChainState,
+    /// Indices below are in the reverse lookup, except for the ones skipped by marking a later
+    /// index as used.
+    derived: u32,
+}
+
+/// The external (`/0`) and internal (`/1`) chains of an account level extended public key, with
+/// the bookkeeping of handed out and used indices.
+///
+/// Keys are looked ahead of the next unused index of each chain, so that the keys of payments to
+/// not yet handed out indices can be looked up too. Only the window of keys after the next unused
+/// index is derived: marking a far index as used skips the keys in between.
+///
+/// ```
+/// # #[cfg(feature = "k256")] {
+/// use bip0032::{AccountKeychain, Change, DerivationPath, ExtendedPrivateKey, curve::secp256k1::*};
+///
+/// let master = ExtendedPrivateKey::<Secp256k1Curve<K256Backend>>::new(&[0x42; 32]).unwrap();
+/// let path = "m/84'/0'/0'".parse::<DerivationPath>().unwrap();
+/// let account = master.derive_path(&path).unwrap().public_key();
+/// let mut keychain = AccountKeychain::new(account).unwrap();
+///
+/// let (index, key) = keychain.next_receive().unwrap();
+/// assert_eq!(index, 0);
+/// assert_eq!(keychain.lookup(&key.to_bytes()), Some((Change::External, 0)));
+///
+/// // a payment to a later index is found by the lookahead
+/// let key = keychain.derive(Change::External, 5).unwrap();
+/// assert_eq!(keychain.mark_used_key(&key.to_bytes()).unwrap(), Some((Change::External, 5)));
+/// assert_eq!(keychain.highest_used(Change::External), Some(5));
+/// assert_eq!(keychain.next_receive().unwrap().0, 6);
+/// # }
+/// ```
+pub struct AccountKeychain<C: Curve> {
+    account: ExtendedPublicKey<C>,
+    version: Version,
+    lookahead: u32,
+    external: Chain<C>,
+    internal: Chain<C>,
+    lookup: BTreeMap<Vec<u8>, (Change, u32)>,
+}
+
+impl<C> AccountKeychain<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: TweakableKey,
+{
+    /// The default number of keys looked ahead of the next unused index of each chain.
+    pub const DEFAULT_LOOKAHEAD: u32 = 20;
+    /// The maximum number of keys looked ahead of the next unused index of each chain.
+    pub const MAX_LOOKAHEAD: u32 = 1_000;
+
+    /// Creates a keychain over an account level extended public key, without handed out or used
+    /// indices.
+    ///
+    /// The origin of the key is kept, see [`OriginKey`] to attach one to a decoded key. A plain
+    /// [`ExtendedPublicKey`] is versioned as `xpub`, pass a [`VersionedPublicKey`] or an
+    /// [`OriginKey`] to keep e.g. a `tpub`.
+    pub fn new(account: impl Into<VersionedPublicKey<C>>) -> Result<Self> {
+        let VersionedPublicKey { key, version } = account.into();
+        Self::with_state(
+            key,
+            version,
+            Self::DEFAULT_LOOKAHEAD,
+            ChainState::default(),
+            ChainState::default(),
+        )
+    }
+
+    /// Restores a keychain from its state.
+    ///
+    /// The indices of the chain states must be non-hardened and the lookahead at most
+    /// [`Self::MAX_LOOKAHEAD`].
+    pub fn from_state(state: KeychainState<C>) -> Result<Self> {
+        let KeychainState { account, origin, lookahead, external, internal } = state;
+        for chain in [external, internal] {
+            ChildNumber::new(chain.next_index, false)?;
+            if let Some(used) = chain.highest_used {
+                ChildNumber::new(used, false)?;
+            }
+        }
+        let VersionedPublicKey { key, version } = account;
+        let key = match origin {
+            Some(origin) => OriginKey::new(origin, key, version)?.into_key(),
+            None => key,
+        };
+        Self::with_state(key, version, lookahead, external, internal)
+    }
+
+    fn with_state(
+        account: ExtendedPublicKey<C>,
+        version: Version,
+        lookahead: u32,
+        external: ChainState,
+        internal: ChainState,
+    ) -> Result<Self> {
+        Self::check_lookahead(lookahead)?;
+        let chain = |change: Change, state| -> Result<Chain<C>> {
+            let key = account.derive_child(ChildNumber::new(change.index(), false)?)?;
+            Ok(Chain { key, state, derived: 0 })
+        };
+        let mut keychain = Self {
+            external: chain(Change::External, external)?,
+            internal: chain(Change::Internal, internal)?,
+            account,
+            version,
+            lookahead,
+            lookup: BTreeMap::new(),
+        };
+        keychain.look_ahead(Change::External)?;
+        keychain.look_ahead(Change::Internal)?;
+        Ok(keychain)
+    }
+
+    /// Sets the number of keys looked ahead of the next unused index of each chain, at most
+    /// [`Self::MAX_LOOKAHEAD`].
+    pub fn set_lookahead(&mut self, lookahead: u32) -> Result<()> {
+        Self::check_lookahead(lookahead)?;
+        self.lookahead = lookahead;
+        self.look_ahead(Change::External)?;
+        self.look_ahead(Change::Internal)
+    }
+
+    /// Returns the state of this keychain.
+    pub fn state(&self) -> KeychainState<C> {
+        KeychainState {
+            account: VersionedPublicKey { key: self.account.clone(), version: self.version },
+            origin: self.account.origin(),
+            lookahead: self.lookahead,
+            external: self.external.state,
+            internal: self.internal.state,
+        }
+    }
+
+    /// Returns the account level extended public key.
+    pub fn account(&self) -> &ExtendedPublicKey<C> {
+        &self.account
+    }
+
+    /// Returns the version bytes of the account key.
+    pub const fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns the origin of the account key, if known.
+    pub fn origin(&self) -> Option<KeyOrigin> {
+        self.account.origin()
+    }
+
+    /// Returns the number of keys looked ahead of the next unused index of each chain.
+    pub const fn lookahead(&self) -> u32 {
+        self.lookahead
+    }
+
+    /// Returns the bookkeeping of a chain.
+    pub fn chain_state(&self, change: Change) -> ChainState {
+        self.chain(change).state
+    }
+
+    /// Returns the highest used index of a chain, if any.
+    pub fn highest_used(&self, change: Change) -> Option<u32> {
+        self.chain(change).state.highest_used
+    }
+
+    /// Derives the key at an index of a chain, without handing it out.
+    pub fn derive(&self, change: Change, index: u32) -> Result<ExtendedPublicKey<C>> {
+        self.chain(change).key.derive_child(ChildNumber::new(index, false)?)
+    }
+
+    /// Hands out the next unused key of a chain and returns it with its index.
+    pub fn next_key(&mut self, change: Change) -> Result<(u32, ExtendedPublicKey<C>)> {
+        let index = self.chain(change).state.next_unused();
+        let key = self.derive(change, index)?;
+        self.chain_mut(change).state.next_index = index + 1;
+        self.look_ahead(change)?;
+        Ok((index, key))
+    }
+
+    /// Hands out the next unused receiving key (external chain).
+    pub fn next_receive(&mut self) -> Result<(u32, ExtendedPublicKey<C>)> {
+        self.next_key(Change::External)
+    }
+
+    /// Hands out the next unused change key (internal chain).
+    pub fn next_change(&mut self) -> Result<(u32, ExtendedPublicKey<C>)> {
+        self.next_key(Change::Internal)
+    }
+
+    /// Marks an index of a chain as used.
+    pub fn mark_used(&mut self, change: Change, index: u32) -> Result<()> {
+        ChildNumber::new(index, false)?;
+        let state = &mut self.chain_mut(change).state;
+        state.highest_used = Some(state.highest_used.map_or(index, |used| used.max(index)));
+        self.look_ahead(change)
+    }
+
+    /// Looks up the chain and index of a handed out or looked ahead public key.
+    pub fn lookup(
+        &self,
+        public_key: &<C::PublicKey as CurvePublicKey>::Bytes,
+    ) -> Option<(Change, u32)> {
+        self.lookup.get(public_key.as_ref()).copied()
+    }
+
+    /// Marks a handed out or looked ahead public key as used, returning its chain and index.
+    ///
+    /// Returns `None` for keys not in the lookup.
+    pub fn mark_used_key(
+        &mut self,
+        public_key: &<C::PublicKey as CurvePublicKey>::Bytes,
+    ) -> Result<Option<(Change, u32)>> {
+        let Some((change, index)) = self.lookup(public_key) else {
+            return Ok(None);
+        };
+        self.mark_used(change, index)?;
+        Ok(Some((change, index)))
+    }
+
+    fn chain(&self, change: Change) -> &Chain<C> {
+        match change {
+            Change::External => &self.external,
+            Change::Internal => &self.internal,
+        }
+    }
+
+    fn chain_mut(&mut self, change: Change) -> &mut Chain<C> {
+        match change {
+            Change::External => &mut self.external,
+            Change::Internal => &mut self.internal,
+        }
+    }
+
+    /// Extends the reverse lookup of a chain up to the lookahead after its next unused index.
+    fn look_ahead(&mut self, change: Change) -> Result<()> {
+        let chain = match change {
+            Change::External => &mut self.external,
+            Change::Internal => &mut self.internal,
+        };
+        let next_unused = chain.state.next_unused();
+        let start = chain.derived.max(next_unused);
+        let end = next_unused.saturating_add(self.lookahead).min(ChildNumber::HARDENED_OFFSET);
+        if start >= end {
+            return Ok(());
+        }
+
+        let keys = chain.key.derive_range(start..end)?;
+        for (index, key) in (start..end).zip(keys) {
+            self.lookup.insert(key.to_bytes().as_ref().to_vec(), (change, index));
+        }
+        chain.derived = end;
+        Ok(())
+    }
+
+    fn check_lookahead(lookahead: u32) -> Result<()> {
+        if lookahead > Self::MAX_LOOKAHEAD {
+            return Err(Error::new(ErrorKind::InvalidDerivation, "lookahead is too large")
+                .with_context("lookahead", lookahead)
+                .with_context("max", Self::MAX_LOOKAHEAD));
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,27 @@
 //! Extended key types for BIP32.
 
+pub mod bip85;
+mod cache;
 mod core;
+mod discovery;
+mod keychain;
+mod origin;
 mod payload;
 #[cfg(feature = "slip10")]
 pub mod slip10;
+#[cfg(feature = "slip10")]
+pub mod slip13;
+pub mod slip21;
 
+pub(crate) use self::core::key_identifier;
 pub use self::{
+    cache::DerivationCache,
     core::{ExtendedPrivateKey, ExtendedPublicKey},
-    payload::{ExtendedKeyPayload, KnownVersion, Version},
+    discovery::{AccountDiscovery, AccountScan, ChainScan, KeyUsage},
+    keychain::{AccountKeychain, ChainState, KeychainState},
+    origin::{KeyOrigin, OriginKey, VersionedPublicKey},
+    payload::{
+        ExtendedKeyPayload, KnownVersion, NetworkKind, ScriptType, Version, VersionInfo,
+        VersionRegistry, convert_version, convert_version_cross_network,
+    },
 };
@@ -0,0 +1,404 @@
+//! Key origin tracking and key origin expressions (`[d34db33f/84'/0'/0']xpub...`), and public
+//! keys that keep their version bytes.
+
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+use core::{fmt, str::FromStr};
+
+use crate::{
+    curve::{Bip32Curve, Curve, CurvePublicKey},
+    error::{Error, ErrorKind, Result},
+    path::{ChildNumber, DerivationPath},
+    xkey::{ExtendedKeyPayload, ExtendedPublicKey, Version, payload::check_version_visibility},
+};
+
+/// The origin of an extended key: the master key fingerprint and the derivation path from the
+/// master key.
+///
+/// The string form is the key origin expression used by output descriptors, PSBT tooling and
+/// hardware wallets, e.g. `[d34db33f/84'/0'/0']`. Hardened components accept the `'`, `h`, or
+/// `H` suffix and display as `'`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct KeyOrigin {
+    fingerprint: [u8; 4],
+    path: DerivationPath,
+}
+
+impl KeyOrigin {
+    /// Creates a key origin from a master key fingerprint and a derivation path.
+    pub fn new(fingerprint: [u8; 4], path: DerivationPath) -> Self {
+        Self { fingerprint, path }
+    }
+
+    /// Returns the fingerprint of the master key.
+    pub const fn fingerprint(&self) -> [u8; 4] {
+        self.fingerprint
+    }
+
+    /// Returns the derivation path from the master key.
+    pub fn path(&self) -> &DerivationPath {
+        &self.path
+    }
+
+    /// Returns the origin of the given child of a key with this origin.
+    pub(crate) fn child(&self, child: ChildNumber) -> Self {
+        let mut path = self.path.clone();
+        path.extend([child]);
+        Self { fingerprint: self.fingerprint, path }
+    }
+
+    /// Returns the origin of the key derived along the given path from a key with this origin.
+    pub(crate) fn descendant(&self, path: &DerivationPath) -> Self {
+        let mut children = self.path.clone();
+        children.extend(path.children().iter().copied());
+        Self { fingerprint: self.fingerprint, path: children }
+    }
+
+    /// Checks that this origin is consistent with the given key metadata.
+    fn check_key(
+        &self,
+        depth: u8,
+        child_number: ChildNumber,
+        parent_fingerprint: [u8; 4],
+    ) -> Result<()> {
+        let children = self.path.children();
+        if children.len() != usize::from(depth) {
+            return Err(Error::new(
+                ErrorKind::InvalidOrigin,
+                "origin path length does not match key depth",
+            )
+            .with_context("path", &self.path)
+            .with_context("depth", depth));
+        }
+
+        if let Some(last) = children.last() {
+            if *last != child_number {
+                return Err(Error::new(
+                    ErrorKind::InvalidOrigin,
+                    "origin path does not end with key child number",
+                )
+                .with_context("path", &self.path)
+                .with_context("child_number", child_number));
+            }
+        }
+
+        if children.len() == 1 && self.fingerprint != parent_fingerprint {
+            return Err(Error::new(
+                ErrorKind::InvalidOrigin,
+                "origin fingerprint does not match key parent fingerprint",
+            )
+            .with_context("fingerprint", Fingerprint(self.fingerprint))
+            .with_context("parent_fingerprint", Fingerprint(parent_fingerprint)));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for KeyOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}", Fingerprint(self.fingerprint))?;
+        for child in &self.path {
+            write!(f, "/{child}")?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl FromStr for KeyOrigin {
+    type Err = Error;
+
+    fn from_str(origin: &str) -> Result<Self> {
+        let inner =
+            origin
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::InvalidOrigin, "key origin must be enclosed in brackets")
+                        .with_context("origin", origin)
+                })?;
+
+        let (fingerprint, path) = match inner.split_once('/') {
+            Some((fingerprint, path)) => (fingerprint, Some(path)),
+            None => (inner, None),
+        };
+
+        let fingerprint =
+            parse_fingerprint(fingerprint).map_err(|err| err.with_context("origin", origin))?;
+        let path = match path {
+            Some(path) => {
+                // `DerivationPath` accepts a leading `m/`, which is not valid in a key origin.
+                if path.starts_with(['m', 'M']) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidOrigin,
+                        "unexpected path prefix in key origin",
+                    )
+                    .with_context("origin", origin));
+                }
+                path.parse::<DerivationPath>()?
+            },
+            None => DerivationPath::default(),
+        };
+
+        Ok(Self { fingerprint, path })
+    }
+}
+
+/// An extended public key together with its origin, e.g. `[d34db33f/84'/0'/0']xpub...`.
+///
+/// The origin is checked against the key: the origin path length must equal the key depth, the
+/// last origin path component must equal the key child number, and for depth 1 keys the origin
+/// fingerprint must equal the parent fingerprint.
+pub struct OriginKey<C: Curve> {
+    origin: KeyOrigin,
+    key: ExtendedPublicKey<C>,
+    version: Version,
+}
+
+impl<C: Curve> Clone for OriginKey<C> {
+    fn clone(&self) -> Self {
+        Self { origin: self.origin.clone(), key: self.key.clone(), version: self.version }
+    }
+}
+
+impl<C: Curve> OriginKey<C> {
+    /// Creates an origin key, checking the origin against the key.
+    ///
+    /// The version bytes are used when formatting the key.
+    pub fn new(origin: KeyOrigin, mut key: ExtendedPublicKey<C>, version: Version) -> Result<Self> {
+        check_version_visibility(version, false)?;
+
+        if key.depth() == 0 {
+            if !origin.path().children().is_empty() || origin.fingerprint() != key.fingerprint() {
+                return Err(Error::new(
+                    ErrorKind::InvalidOrigin,
+                    "origin does not match master key",
+                )
+                .with_context("origin", &origin));
+            }
+        } else {
+            origin.check_key(key.depth(), key.child_number(), key.parent_fingerprint())?;
+        }
+
+        key.origin = Some(origin.clone());
+        Ok(Self { origin, key, version })
+    }
+
+    /// Returns the key origin.
+    pub fn origin(&self) -> &KeyOrigin {
+        &self.origin
+    }
+
+    /// Returns the extended public key.
+    ///
+    /// The returned key carries the origin, so keys derived from it keep tracking their origin.
+    pub fn key(&self) -> &ExtendedPublicKey<C> {
+        &self.key
+    }
+
+    /// Returns the version bytes used when formatting the key.
+    pub const fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Consumes this origin key and returns the extended public key.
+    pub fn into_key(self) -> ExtendedPublicKey<C> {
+        self.key
+    }
+}
+
+impl<C> fmt::Display for OriginKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.origin, self.key.encode_with_unchecked(self.version))
+    }
+}
+
+impl<C> FromStr for OriginKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    type Err = Error;
+
+    fn from_str(encoded: &str) -> Result<Self> {
+        let (origin, key) = match encoded.find(']') {
+            Some(end) if encoded.starts_with('[') => encoded.split_at(end + 1),
+            _ => {
+                return Err(Error::new(ErrorKind::InvalidOrigin, "missing key origin")
+                    .with_context("key", encoded));
+            },
+        };
+
+        let origin = origin.parse::<KeyOrigin>()?;
+        let payload = key.parse::<ExtendedKeyPayload>()?;
+        let version = payload.version();
+        let key = ExtendedPublicKey::try_from(payload)?;
+        Self::new(origin, key, version)
+    }
+}
+
+/// An extended public key together with the version bytes it was decoded with or is formatted
+/// with, e.g. to keep a `tpub` a `tpub`.
+///
+/// [`ExtendedPublicKey`] does not carry its version bytes and is always formatted as `xpub` by
+/// `serde`, use this type to keep them.
+pub struct VersionedPublicKey<C: Curve> {
+    pub(crate) key: ExtendedPublicKey<C>,
+    pub(crate) version: Version,
+}
+
+impl<C: Curve> Clone for VersionedPublicKey<C> {
+    fn clone(&self) -> Self {
+        Self { key: self.key.clone(), version: self.version }
+    }
+}
+
+impl<C: Curve> VersionedPublicKey<C> {
+    /// Creates a versioned key, the version bytes must be public.
+    pub fn new(key: ExtendedPublicKey<C>, version: Version) -> Result<Self> {
+        check_version_visibility(version, false)?;
+        Ok(Self { key, version })
+    }
+
+    /// Returns the extended public key.
+    pub fn key(&self) -> &ExtendedPublicKey<C> {
+        &self.key
+    }
+
+    /// Returns the version bytes used when formatting the key.
+    pub const fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Consumes this versioned key and returns the extended public key.
+    pub fn into_key(self) -> ExtendedPublicKey<C> {
+        self.key
+    }
+}
+
+/// Keys are formatted with the `xpub` version bytes.
+impl<C: Curve> From<ExtendedPublicKey<C>> for VersionedPublicKey<C> {
+    fn from(key: ExtendedPublicKey<C>) -> Self {
+        Self { key, version: Version::XPUB }
+    }
+}
+
+impl<C: Curve> From<OriginKey<C>> for VersionedPublicKey<C> {
+    fn from(key: OriginKey<C>) -> Self {
+        Self { key: key.key, version: key.version }
+    }
+}
+
+impl<C> fmt::Display for VersionedPublicKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.key.encode_with_unchecked(self.version), f)
+    }
+}
+
+impl<C> FromStr for VersionedPublicKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    type Err = Error;
+
+    fn from_str(encoded: &str) -> Result<Self> {
+        Self::try_from(encoded.parse::<ExtendedKeyPayload>()?)
+    }
+}
+
+impl<C> TryFrom<ExtendedKeyPayload> for VersionedPublicKey<C>
+where
+    C: Bip32Curve,
+    C::PublicKey: CurvePublicKey<Bytes = [u8; 33]>,
+{
+    type Error = Error;
+
+    fn try_from(payload: ExtendedKeyPayload) -> Result<Self> {
+        let version = payload.version();
+        Ok(Self { key: ExtendedPublicKey::try_from(payload)?, version })
+    }
+}
+
+/// Lowercase hex formatting for a key fingerprint.
+pub(crate) struct Fingerprint(pub(crate) [u8; 4]);
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Parses a key fingerprint from 8 hex characters.
+pub(crate) fn parse_fingerprint(hex: &str) -> Result<[u8; 4]> {
+    if hex.len() != 8 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(Error::new(ErrorKind::InvalidOrigin, "fingerprint must be 8 hex characters")
+            .with_context("fingerprint", hex.to_string()));
+    }
+
+    let value = u32::from_str_radix(hex, 16).expect("validated hex digits");
+    Ok(value.to_be_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display_origin() {
+        let origin = "[d34db33f/84h/0'/0H/1]".parse::<KeyOrigin>().unwrap();
+        assert_eq!(origin.fingerprint(), [0xd3, 0x4d, 0xb3, 0x3f]);
+        assert_eq!(origin.path().to_string(), "m/84'/0'/0'/1");
+        assert_eq!(origin.to_string(), "[d34db33f/84'/0'/0'/1]");
+    }
+
+    #[test]
+    fn parse_origin_without_path() {
+        let origin = "[D34DB33F]".parse::<KeyOrigin>().unwrap();
+        assert!(origin.path().children().is_empty());
+        assert_eq!(origin.to_string(), "[d34db33f]");
+    }
+
+    #[test]
+    fn error_missing_brackets() {
+        let err = "d34db33f/84'".parse::<KeyOrigin>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidOrigin);
+        assert_eq!(err.message(), "key origin must be enclosed in brackets");
+    }
+
+    #[test]
+    fn error_invalid_fingerprint() {
+        for origin in ["[d34db33/0]", "[+34db33f/0]", "[d34db33g]", "[]"] {
+            let err = origin.parse::<KeyOrigin>().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidOrigin);
+            assert_eq!(err.message(), "fingerprint must be 8 hex characters");
+        }
+    }
+
+    #[test]
+    fn error_invalid_origin_path() {
+        let err = "[d34db33f/m/0]".parse::<KeyOrigin>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidOrigin);
+
+        let err = "[d34db33f/0//1]".parse::<KeyOrigin>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidPath);
+        assert_eq!(err.message(), "empty path component");
+    }
+}
@@ -5,9 +5,13 @@ use alloc::{format, string::String};
 
 use zeroize::{Zeroize, Zeroizing};
 
+mod registry;
 mod version;
 
-pub use self::version::{KnownVersion, Version};
+pub use self::{
+    registry::{NetworkKind, ScriptType, VersionInfo, VersionRegistry},
+    version::{KnownVersion, Version},
+};
 use crate::{
     error::{Error, ErrorKind, Result},
     xkey::core::ExtendedKeyMetadata,
@@ -35,43 +39,111 @@ impl ExtendedKeyPayload {
         self.version
     }
 
+    /// Returns this payload with the version bytes replaced, e.g. to convert a `zpub` to an
+    /// `xpub` of the same key.
+    ///
+    /// Fails if the version is private for a public key or vice versa, or if the versions are not
+    /// both known to belong to the same coin and network. See
+    /// [`with_version_cross_network`](Self::with_version_cross_network) to skip the network check.
+    pub fn with_version(self, version: Version) -> Result<Self> {
+        check_version_visibility(version, self.version.is_private())?;
+
+        let from = self.version;
+        let (from_info, to_info) = match (from.info(), version.info()) {
+            (Some(from_info), Some(to_info)) => (from_info, to_info),
+            _ => {
+                return Err(Error::new(ErrorKind::InvalidVersion, "unknown version network")
+                    .with_context("from", from)
+                    .with_context("to", version));
+            },
+        };
+        if from_info.coin() != to_info.coin() || from_info.network() != to_info.network() {
+            return Err(Error::new(ErrorKind::InvalidVersion, "cross-network version conversion")
+                .with_context("from", from_info.prefix())
+                .with_context("to", to_info.prefix()));
+        }
+
+        Ok(self.with_version_cross_network(version).expect("visibility already checked"))
+    }
+
+    /// Returns this payload with the version bytes replaced, without checking that the versions
+    /// belong to the same coin and network.
+    ///
+    /// Fails if the version is private for a public key or vice versa.
+    pub fn with_version_cross_network(mut self, version: Version) -> Result<Self> {
+        check_version_visibility(version, self.version.is_private())?;
+        self.version = version;
+        Ok(self)
+    }
+
     /// Length of a serialized extended key payload (without Base58Check).
-    const KEY_PAYLOAD_LENGTH: usize = 78;
-    /// Base58Check adds 4 checksum bytes, so decode needs 82 bytes and encode needs the
-    /// Base58 max-length upper bound for 82 bytes: len + (len + 1) / 2 = 123.
-    const KEY_PAYLOAD_WITH_CHECKSUM_LENGTH: usize = Self::KEY_PAYLOAD_LENGTH + 4;
+    pub const LENGTH: usize = 78;
+    /// Base58Check adds 4 checksum bytes, so decode needs 82 bytes and rejects inputs longer than
+    /// the Base58 max-length upper bound for 82 bytes: len + (len + 1) / 2 = 123.
+    const KEY_PAYLOAD_WITH_CHECKSUM_LENGTH: usize = Self::LENGTH + 4;
     const MAX_KEY_PAYLOAD_STRING_LENGTH: usize =
         Self::KEY_PAYLOAD_WITH_CHECKSUM_LENGTH + Self::KEY_PAYLOAD_WITH_CHECKSUM_LENGTH.div_ceil(2);
 
-    /// Serializes this key into a 78-byte payload.
-    fn serialize(&self) -> [u8; Self::KEY_PAYLOAD_LENGTH] {
-        let mut out = [0u8; Self::KEY_PAYLOAD_LENGTH];
+    /// Serializes this payload into its raw 78-byte form (without Base58Check), as used by e.g.
+    /// PSBT global xpubs.
+    ///
+    /// # Warning
+    ///
+    /// The bytes of a private key payload contain the private key. See
+    /// [`write_bytes`](Self::write_bytes) to serialize into a buffer managed by the caller.
+    pub fn to_bytes(&self) -> [u8; Self::LENGTH] {
+        let mut out = [0u8; Self::LENGTH];
+        self.write_bytes(&mut out);
+        out
+    }
+
+    /// Serializes this payload into its raw 78-byte form (without Base58Check) in `out`.
+    pub fn write_bytes(&self, out: &mut [u8; Self::LENGTH]) {
         out[..4].copy_from_slice(&self.version.to_bytes());
         out[4] = self.meta.depth;
         out[5..9].copy_from_slice(&self.meta.parent_fingerprint);
         out[9..13].copy_from_slice(&self.meta.child_number.to_be_bytes());
         out[13..45].copy_from_slice(&self.meta.chain_code);
         out[45..78].copy_from_slice(&self.key_data);
-        out
+    }
+
+    /// Parses a raw 78-byte payload (without Base58Check), with the same validation as parsing
+    /// the Base58Check string.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        parse_payload(data)
     }
 }
 
-impl core::fmt::Display for ExtendedKeyPayload {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let serialized = Zeroizing::new(self.serialize());
-        let mut encoded = String::with_capacity(Self::MAX_KEY_PAYLOAD_STRING_LENGTH);
-        bs58::encode(&serialized[..])
+// Base58Check encoding/decoding
+impl ExtendedKeyPayload {
+    /// Maximum length of a Base58Check-encoded extended key.
+    ///
+    /// Keys with the usual `xpub`/`xprv`-like versions encode to 111 characters, but versions with
+    /// a larger leading byte (e.g. the Dash testnet versions) encode to 112 characters.
+    pub const MAX_ENCODED_LENGTH: usize = 112;
+
+    /// Encodes this payload as Base58Check into `out`, returning the encoded string.
+    ///
+    /// Unlike [`to_string`](ToString::to_string), no intermediate buffer is allocated, so the
+    /// caller controls where the encoded key lives (e.g. a buffer zeroized after use).
+    pub fn encode_to<'a>(&self, out: &'a mut [u8; Self::MAX_ENCODED_LENGTH]) -> &'a str {
+        let mut serialized = Zeroizing::new([0u8; Self::LENGTH]);
+        self.write_bytes(&mut serialized);
+        let len = bs58::encode(&serialized[..])
             .with_check()
-            .onto(&mut encoded)
+            .onto(&mut out[..])
             .expect("base58 encoding should fit the fixed buffer");
-        f.write_str(&encoded)
+        core::str::from_utf8(&out[..len]).expect("base58 alphabet should be ASCII")
     }
-}
 
-impl core::str::FromStr for ExtendedKeyPayload {
-    type Err = Error;
+    /// Encodes this payload as a Base58Check string that is zeroized on drop.
+    pub fn to_zeroizing_string(&self) -> Zeroizing<String> {
+        let mut buf = Zeroizing::new([0u8; Self::MAX_ENCODED_LENGTH]);
+        Zeroizing::new(String::from(self.encode_to(&mut buf)))
+    }
 
-    fn from_str(encoded: &str) -> Result<Self> {
+    /// Decodes a Base58Check-encoded payload from bytes, without intermediate allocations.
+    pub fn decode_from(encoded: &[u8]) -> Result<Self> {
         if encoded.len() > Self::MAX_KEY_PAYLOAD_STRING_LENGTH {
             return Err(Error::new(ErrorKind::InvalidPayload, "invalid base58check length")
                 .with_context("encoded_len", encoded.len())
@@ -94,21 +166,94 @@ impl core::str::FromStr for ExtendedKeyPayload {
                 })
         })?;
 
-        if len != Self::KEY_PAYLOAD_LENGTH {
+        if len != Self::LENGTH {
             return Err(Error::new(ErrorKind::InvalidPayload, "invalid extended key length")
                 .with_context("decoded_len", len)
-                .with_context("expected_len", Self::KEY_PAYLOAD_LENGTH));
+                .with_context("expected_len", Self::LENGTH));
         }
 
         parse_payload(&data[..len])
     }
 }
 
-pub(crate) fn parse_payload(data: &[u8]) -> Result<ExtendedKeyPayload> {
-    if data.len() != ExtendedKeyPayload::KEY_PAYLOAD_LENGTH {
+impl core::fmt::Display for ExtendedKeyPayload {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut buf = Zeroizing::new([0u8; Self::MAX_ENCODED_LENGTH]);
+        f.write_str(self.encode_to(&mut buf))
+    }
+}
+
+impl core::str::FromStr for ExtendedKeyPayload {
+    type Err = Error;
+
+    fn from_str(encoded: &str) -> Result<Self> {
+        Self::decode_from(encoded.as_bytes())
+    }
+}
+
+impl TryFrom<&[u8]> for ExtendedKeyPayload {
+    type Error = Error;
+
+    fn try_from(data: &[u8]) -> Result<Self> {
+        Self::from_bytes(data)
+    }
+}
+
+/// Converts a Base58Check-encoded extended key to another version of the same coin and network,
+/// e.g. a `zpub` to an `xpub`.
+///
+/// The result is zeroized on drop, as private versions (e.g. `zprv`) are converted as well.
+///
+/// See [`ExtendedKeyPayload::with_version`] for the checks, and [`convert_version_cross_network`]
+/// to skip the network check.
+///
+/// ```rust
+/// use bip0032::{KnownVersion, convert_version};
+///
+/// let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+/// let zpub = convert_version(xpub, KnownVersion::Zpub).unwrap();
+/// assert!(zpub.starts_with("zpub"));
+/// assert_eq!(convert_version(&zpub, KnownVersion::Xpub).unwrap().as_str(), xpub);
+///
+/// // xpub -> vpub changes the network, xpub -> zprv the key visibility
+/// assert!(convert_version(xpub, KnownVersion::Vpub).is_err());
+/// assert!(convert_version(xpub, KnownVersion::Zprv).is_err());
+/// ```
+pub fn convert_version(encoded: &str, target: KnownVersion) -> Result<Zeroizing<String>> {
+    let payload = encoded.parse::<ExtendedKeyPayload>()?;
+    Ok(payload.with_version(target.into_version())?.to_zeroizing_string())
+}
+
+/// Converts a Base58Check-encoded extended key to another version, possibly of another coin or
+/// network, e.g. a `tpub` to an `xpub`.
+///
+/// See [`ExtendedKeyPayload::with_version_cross_network`].
+pub fn convert_version_cross_network(
+    encoded: &str,
+    target: KnownVersion,
+) -> Result<Zeroizing<String>> {
+    let payload = encoded.parse::<ExtendedKeyPayload>()?;
+    Ok(payload.with_version_cross_network(target.into_version())?.to_zeroizing_string())
+}
+
+/// Checks that `version` is private for a private key, or public for a public key.
+pub(crate) fn check_version_visibility(version: Version, private: bool) -> Result<()> {
+    if private && !version.is_private() {
+        return Err(Error::new(ErrorKind::InvalidVersion, "expected private version bytes")
+            .with_context("version", version));
+    }
+    if !private && !version.is_public() {
+        return Err(Error::new(ErrorKind::InvalidVersion, "expected public version bytes")
+            .with_context("version", version));
+    }
+    Ok(())
+}
+
+fn parse_payload(data: &[u8]) -> Result<ExtendedKeyPayload> {
+    if data.len() != ExtendedKeyPayload::LENGTH {
         return Err(Error::new(ErrorKind::InvalidPayload, "invalid extended key length")
             .with_context("decoded_len", data.len())
-            .with_context("expected_len", ExtendedKeyPayload::KEY_PAYLOAD_LENGTH));
+            .with_context("expected_len", ExtendedKeyPayload::LENGTH));
     }
 
     let mut raw_version_bytes = [0u8; 4];
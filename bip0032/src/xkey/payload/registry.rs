@@ -0,0 +1,306 @@
+//! Registry of extended key versions with their network, script type and prefix
+//! ([SLIP-0132](https://github.com/satoshilabs/slips/blob/master/slip-0132.md)).
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+use super::{KnownVersion, Version};
+use crate::{
+    error::{Error, ErrorKind, Result},
+    path::CoinType,
+};
+
+/// Network kind of an extended key version.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum NetworkKind {
+    /// Mainnet keys.
+    Mainnet,
+    /// Testnet keys (also used by signet and regtest).
+    Testnet,
+}
+
+/// Script type intended by an extended key version.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ScriptType {
+    /// **P2PKH** or **P2SH**.
+    P2pkh,
+    /// **P2SH-P2WPKH**.
+    P2shP2wpkh,
+    /// **P2WPKH**.
+    P2wpkh,
+    /// Multi-signature **P2SH-P2WSH**.
+    P2shP2wsh,
+    /// Multi-signature **P2WSH**.
+    P2wsh,
+}
+
+impl ScriptType {
+    /// Returns true for multi-signature script types.
+    pub const fn is_multisig(self) -> bool {
+        matches!(self, Self::P2shP2wsh | Self::P2wsh)
+    }
+}
+
+/// An extended key version with its coin, network, script type and Base58 prefix.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VersionInfo {
+    version: Version,
+    prefix: Cow<'static, str>,
+    coin: CoinType,
+    network: NetworkKind,
+    script_type: ScriptType,
+}
+
+impl VersionInfo {
+    const fn known(
+        version: KnownVersion,
+        prefix: &'static str,
+        coin: CoinType,
+        network: NetworkKind,
+        script_type: ScriptType,
+    ) -> Self {
+        Self {
+            version: version.into_version(),
+            prefix: Cow::Borrowed(prefix),
+            coin,
+            network,
+            script_type,
+        }
+    }
+
+    /// Returns the version bytes.
+    pub const fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns the Base58 prefix of encoded keys, e.g. `zpub`.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the SLIP-0044 coin type of the mainnet of the coin.
+    pub const fn coin(&self) -> CoinType {
+        self.coin
+    }
+
+    /// Returns the network kind.
+    pub const fn network(&self) -> NetworkKind {
+        self.network
+    }
+
+    /// Returns the intended script type.
+    pub const fn script_type(&self) -> ScriptType {
+        self.script_type
+    }
+}
+
+/// The built-in versions, one entry per [`KnownVersion`].
+static KNOWN_VERSIONS: [VersionInfo; 34] = {
+    use self::{NetworkKind::*, ScriptType::*};
+    use super::KnownVersion as K;
+
+    const BTC: CoinType = CoinType::BITCOIN;
+    const LTC: CoinType = CoinType::LITECOIN;
+    const DOGE: CoinType = CoinType::DOGECOIN;
+    const DASH: CoinType = CoinType::DASH;
+
+    [
+        VersionInfo::known(K::Xpub, "xpub", BTC, Mainnet, P2pkh),
+        VersionInfo::known(K::Xprv, "xprv", BTC, Mainnet, P2pkh),
+        VersionInfo::known(K::Tpub, "tpub", BTC, Testnet, P2pkh),
+        VersionInfo::known(K::Tprv, "tprv", BTC, Testnet, P2pkh),
+        VersionInfo::known(K::Ypub, "ypub", BTC, Mainnet, P2shP2wpkh),
+        VersionInfo::known(K::Yprv, "yprv", BTC, Mainnet, P2shP2wpkh),
+        VersionInfo::known(K::YpubShWsh, "Ypub", BTC, Mainnet, P2shP2wsh),
+        VersionInfo::known(K::YprvShWsh, "Yprv", BTC, Mainnet, P2shP2wsh),
+        VersionInfo::known(K::Upub, "upub", BTC, Testnet, P2shP2wpkh),
+        VersionInfo::known(K::Uprv, "uprv", BTC, Testnet, P2shP2wpkh),
+        VersionInfo::known(K::UpubShWsh, "Upub", BTC, Testnet, P2shP2wsh),
+        VersionInfo::known(K::UprvShWsh, "Uprv", BTC, Testnet, P2shP2wsh),
+        VersionInfo::known(K::Zpub, "zpub", BTC, Mainnet, P2wpkh),
+        VersionInfo::known(K::Zprv, "zprv", BTC, Mainnet, P2wpkh),
+        VersionInfo::known(K::ZpubWsh, "Zpub", BTC, Mainnet, P2wsh),
+        VersionInfo::known(K::ZprvWsh, "Zprv", BTC, Mainnet, P2wsh),
+        VersionInfo::known(K::Vpub, "vpub", BTC, Testnet, P2wpkh),
+        VersionInfo::known(K::Vprv, "vprv", BTC, Testnet, P2wpkh),
+        VersionInfo::known(K::VpubWsh, "Vpub", BTC, Testnet, P2wsh),
+        VersionInfo::known(K::VprvWsh, "Vprv", BTC, Testnet, P2wsh),
+        VersionInfo::known(K::Ltub, "Ltub", LTC, Mainnet, P2pkh),
+        VersionInfo::known(K::Ltpv, "Ltpv", LTC, Mainnet, P2pkh),
+        VersionInfo::known(K::Mtub, "Mtub", LTC, Mainnet, P2shP2wpkh),
+        VersionInfo::known(K::Mtpv, "Mtpv", LTC, Mainnet, P2shP2wpkh),
+        VersionInfo::known(K::Ttub, "ttub", LTC, Testnet, P2pkh),
+        VersionInfo::known(K::Ttpv, "ttpv", LTC, Testnet, P2pkh),
+        VersionInfo::known(K::Dgub, "dgub", DOGE, Mainnet, P2pkh),
+        VersionInfo::known(K::Dgpv, "dgpv", DOGE, Mainnet, P2pkh),
+        VersionInfo::known(K::Tgub, "tgub", DOGE, Testnet, P2pkh),
+        VersionInfo::known(K::Tgpv, "tgpv", DOGE, Testnet, P2pkh),
+        VersionInfo::known(K::Drkp, "drkp", DASH, Mainnet, P2pkh),
+        VersionInfo::known(K::Drkv, "drkv", DASH, Mainnet, P2pkh),
+        VersionInfo::known(K::DrkpTestnet, "DRKP", DASH, Testnet, P2pkh),
+        VersionInfo::known(K::DrkvTestnet, "DRKV", DASH, Testnet, P2pkh),
+    ]
+};
+
+impl KnownVersion {
+    /// Returns the coin, network, script type and prefix of this version.
+    pub fn info(self) -> &'static VersionInfo {
+        let version = self.into_version();
+        KNOWN_VERSIONS
+            .iter()
+            .find(|info| info.version == version)
+            .expect("every known version has an entry")
+    }
+
+    /// Returns the Base58 prefix of encoded keys, e.g. `zpub`.
+    pub fn prefix(self) -> &'static str {
+        match &self.info().prefix {
+            Cow::Borrowed(prefix) => prefix,
+            Cow::Owned(_) => unreachable!("known versions have static prefixes"),
+        }
+    }
+
+    /// Returns the known version with the given Base58 prefix, e.g. `zpub` (case-sensitive).
+    pub fn from_prefix(prefix: &str) -> Option<Self> {
+        KNOWN_VERSIONS
+            .iter()
+            .find(|info| info.prefix == prefix)
+            .and_then(|info| info.version.into_known_version())
+    }
+}
+
+impl Version {
+    /// Returns the coin, network, script type and prefix of this version, if it is known.
+    pub fn info(self) -> Option<&'static VersionInfo> {
+        self.into_known_version().map(KnownVersion::info)
+    }
+}
+
+/// A registry of extended key versions: the built-in [`KnownVersion`]s plus versions registered
+/// at runtime.
+///
+/// ```rust
+/// use bip0032::{CoinType, NetworkKind, ScriptType, Version, VersionRegistry};
+///
+/// let mut registry = VersionRegistry::new();
+/// assert_eq!(registry.get(Version::ZPUB).unwrap().prefix(), "zpub");
+///
+/// // a wallet-specific version for Litecoin P2WPKH keys
+/// let version = Version::Public(0x04B2_4747);
+/// let info = registry
+///     .register(version, CoinType::LITECOIN, NetworkKind::Mainnet, ScriptType::P2wpkh)
+///     .unwrap();
+/// assert_eq!(info.prefix(), "zpub");
+/// assert_eq!(registry.get(version).unwrap().coin(), CoinType::LITECOIN);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct VersionRegistry {
+    custom: Vec<VersionInfo>,
+}
+
+impl VersionRegistry {
+    /// Creates a registry with the built-in versions only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a custom version, computing its Base58 prefix.
+    ///
+    /// Fails if the version is already registered, or if encoded keys of this version do not all
+    /// share the same Base58 prefix.
+    pub fn register(
+        &mut self,
+        version: Version,
+        coin: CoinType,
+        network: NetworkKind,
+        script_type: ScriptType,
+    ) -> Result<&VersionInfo> {
+        if self.get(version).is_some() {
+            return Err(Error::new(ErrorKind::InvalidVersion, "version already registered")
+                .with_context("version", version));
+        }
+        let prefix = base58_prefix(version).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidVersion, "version has no stable Base58 prefix")
+                .with_context("version", version)
+        })?;
+
+        self.custom.push(VersionInfo {
+            version,
+            prefix: Cow::Owned(prefix),
+            coin,
+            network,
+            script_type,
+        });
+        Ok(self.custom.last().expect("just registered"))
+    }
+
+    /// Returns the registered entry of a version.
+    pub fn get(&self, version: Version) -> Option<&VersionInfo> {
+        version
+            .info()
+            .or_else(|| self.custom.iter().find(|info| info.version == version))
+    }
+
+    /// Returns the registered entry of raw version bytes, public or private.
+    pub fn get_raw(&self, raw: u32) -> Option<&VersionInfo> {
+        self.get(Version::Public(raw)).or_else(|| self.get(Version::Private(raw)))
+    }
+
+    /// Returns the registered entry with the given Base58 prefix (case-sensitive).
+    ///
+    /// Built-in versions take precedence over custom versions with the same prefix.
+    pub fn from_prefix(&self, prefix: &str) -> Option<&VersionInfo> {
+        self.iter().find(|info| info.prefix == prefix)
+    }
+
+    /// Returns all registered entries, built-in versions first.
+    pub fn iter(&self) -> impl Iterator<Item = &VersionInfo> {
+        KNOWN_VERSIONS.iter().chain(self.custom.iter())
+    }
+}
+
+/// Returns the 4-character Base58 prefix shared by all encoded keys of a version, if any.
+///
+/// Base58 preserves the order of same-length inputs, so the prefix is shared by all keys if the
+/// smallest and largest 82-byte (payload and checksum) encodings agree.
+fn base58_prefix(version: Version) -> Option<String> {
+    let mut data = [0u8; 82];
+    data[..4].copy_from_slice(&version.to_bytes());
+    let min = bs58::encode(&data[..]).into_string();
+    data[4..].fill(0xFF);
+    let max = bs58::encode(&data[..]).into_string();
+
+    (min.len() == max.len() && min.get(..4) == max.get(..4)).then(|| min[..4].into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_prefixes_match_encoding() {
+        for info in &KNOWN_VERSIONS {
+            assert_eq!(base58_prefix(info.version).as_deref(), Some(info.prefix()));
+            let known = info.version.into_known_version().unwrap();
+            assert_eq!(known.info(), info);
+            assert_eq!(KnownVersion::from_prefix(info.prefix()), Some(known));
+        }
+    }
+
+    #[test]
+    fn unstable_prefix_is_rejected() {
+        // keys of this version start with `xptS` or `xptT`
+        assert_eq!(base58_prefix(Version::Public(0x0488_B050)), None);
+        let err = VersionRegistry::new()
+            .register(
+                Version::Public(0x0488_B050),
+                CoinType::BITCOIN,
+                NetworkKind::Testnet,
+                ScriptType::P2pkh,
+            )
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidVersion);
+    }
+}
@@ -126,6 +126,46 @@ impl Version {
     pub const VPRV_WSH: Version = Version::Private(0x0257_5048);
     // ========================================================================
 
+    // ========================================================================
+    // https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    // ========================================================================
+    /// Litecoin mainnet **P2PKH** or **P2SH** public key version.
+    pub const LTUB: Version = Version::Public(0x019D_A462);
+    /// Litecoin mainnet **P2PKH** or **P2SH** private key version.
+    pub const LTPV: Version = Version::Private(0x019D_9CFE);
+
+    /// Litecoin mainnet **P2SH-P2WPKH** public key version.
+    pub const MTUB: Version = Version::Public(0x01B2_6EF6);
+    /// Litecoin mainnet **P2SH-P2WPKH** private key version.
+    pub const MTPV: Version = Version::Private(0x01B2_6792);
+
+    /// Litecoin testnet **P2PKH** or **P2SH** public key version.
+    pub const TTUB: Version = Version::Public(0x0436_F6E1);
+    /// Litecoin testnet **P2PKH** or **P2SH** private key version.
+    pub const TTPV: Version = Version::Private(0x0436_EF7D);
+
+    /// Dogecoin mainnet **P2PKH** or **P2SH** public key version.
+    pub const DGUB: Version = Version::Public(0x02FA_CAFD);
+    /// Dogecoin mainnet **P2PKH** or **P2SH** private key version.
+    pub const DGPV: Version = Version::Private(0x02FA_C398);
+
+    /// Dogecoin testnet **P2PKH** or **P2SH** public key version.
+    pub const TGUB: Version = Version::Public(0x0432_A9A8);
+    /// Dogecoin testnet **P2PKH** or **P2SH** private key version.
+    pub const TGPV: Version = Version::Private(0x0432_A243);
+
+    /// Dash mainnet **P2PKH** or **P2SH** public key version.
+    pub const DRKP: Version = Version::Public(0x02FE_52CC);
+    /// Dash mainnet **P2PKH** or **P2SH** private key version.
+    pub const DRKV: Version = Version::Private(0x02FE_52F8);
+
+    /// Dash testnet **P2PKH** or **P2SH** public key version.
+    pub const DRKP_TESTNET: Version = Version::Public(0x3A80_5837);
+    /// Dash testnet **P2PKH** or **P2SH** private key version.
+    pub const DRKV_TESTNET: Version = Version::Private(0x3A80_61A0);
+
+    // ========================================================================
+
     /// Returns the known version descriptor for this version, if any.
     pub const fn into_known_version(self) -> Option<KnownVersion> {
         Some(match self {
@@ -154,12 +194,29 @@ impl Version {
             Self::VPUB_WSH => KnownVersion::VpubWsh,
             Self::VPRV_WSH => KnownVersion::VprvWsh,
 
+            Self::LTUB => KnownVersion::Ltub,
+            Self::LTPV => KnownVersion::Ltpv,
+            Self::MTUB => KnownVersion::Mtub,
+            Self::MTPV => KnownVersion::Mtpv,
+            Self::TTUB => KnownVersion::Ttub,
+            Self::TTPV => KnownVersion::Ttpv,
+
+            Self::DGUB => KnownVersion::Dgub,
+            Self::DGPV => KnownVersion::Dgpv,
+            Self::TGUB => KnownVersion::Tgub,
+            Self::TGPV => KnownVersion::Tgpv,
+
+            Self::DRKP => KnownVersion::Drkp,
+            Self::DRKV => KnownVersion::Drkv,
+            Self::DRKP_TESTNET => KnownVersion::DrkpTestnet,
+            Self::DRKV_TESTNET => KnownVersion::DrkvTestnet,
+
             _ => return None,
         })
     }
 }
 
-/// Standard extended key versions (BIP32 and common extensions).
+/// Standard extended key versions (BIP32, common extensions and SLIP-0132).
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum KnownVersion {
@@ -212,6 +269,41 @@ pub enum KnownVersion {
     VpubWsh,
     /// Bitcoin testnet multi-signature **P2WSH** private key version.
     VprvWsh,
+
+    /// Litecoin mainnet **P2PKH** or **P2SH** public key version.
+    Ltub,
+    /// Litecoin mainnet **P2PKH** or **P2SH** private key version.
+    Ltpv,
+
+    /// Litecoin mainnet **P2SH-P2WPKH** public key version.
+    Mtub,
+    /// Litecoin mainnet **P2SH-P2WPKH** private key version.
+    Mtpv,
+
+    /// Litecoin testnet **P2PKH** or **P2SH** public key version.
+    Ttub,
+    /// Litecoin testnet **P2PKH** or **P2SH** private key version.
+    Ttpv,
+
+    /// Dogecoin mainnet **P2PKH** or **P2SH** public key version.
+    Dgub,
+    /// Dogecoin mainnet **P2PKH** or **P2SH** private key version.
+    Dgpv,
+
+    /// Dogecoin testnet **P2PKH** or **P2SH** public key version.
+    Tgub,
+    /// Dogecoin testnet **P2PKH** or **P2SH** private key version.
+    Tgpv,
+
+    /// Dash mainnet **P2PKH** or **P2SH** public key version.
+    Drkp,
+    /// Dash mainnet **P2PKH** or **P2SH** private key version.
+    Drkv,
+
+    /// Dash testnet **P2PKH** or **P2SH** public key version.
+    DrkpTestnet,
+    /// Dash testnet **P2PKH** or **P2SH** private key version.
+    DrkvTestnet,
 }
 
 impl KnownVersion {
@@ -242,6 +334,23 @@ impl KnownVersion {
             Self::Vprv => Version::VPRV,
             Self::VpubWsh => Version::VPUB_WSH,
             Self::VprvWsh => Version::VPRV_WSH,
+
+            Self::Ltub => Version::LTUB,
+            Self::Ltpv => Version::LTPV,
+            Self::Mtub => Version::MTUB,
+            Self::Mtpv => Version::MTPV,
+            Self::Ttub => Version::TTUB,
+            Self::Ttpv => Version::TTPV,
+
+            Self::Dgub => Version::DGUB,
+            Self::Dgpv => Version::DGPV,
+            Self::Tgub => Version::TGUB,
+            Self::Tgpv => Version::TGPV,
+
+            Self::Drkp => Version::DRKP,
+            Self::Drkv => Version::DRKV,
+            Self::DrkpTestnet => Version::DRKP_TESTNET,
+            Self::DrkvTestnet => Version::DRKV_TESTNET,
         }
     }
 
@@ -294,6 +403,25 @@ mod tests {
         (KnownVersion::VpubWsh, Version::VPUB_WSH),
         (KnownVersion::VprvWsh, Version::VPRV_WSH),
         // ========================================================================
+
+        // ========================================================================
+        // https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+        // ========================================================================
+        (KnownVersion::Ltub, Version::LTUB),
+        (KnownVersion::Ltpv, Version::LTPV),
+        (KnownVersion::Mtub, Version::MTUB),
+        (KnownVersion::Mtpv, Version::MTPV),
+        (KnownVersion::Ttub, Version::TTUB),
+        (KnownVersion::Ttpv, Version::TTPV),
+        (KnownVersion::Dgub, Version::DGUB),
+        (KnownVersion::Dgpv, Version::DGPV),
+        (KnownVersion::Tgub, Version::TGUB),
+        (KnownVersion::Tgpv, Version::TGPV),
+        (KnownVersion::Drkp, Version::DRKP),
+        (KnownVersion::Drkv, Version::DRKV),
+        (KnownVersion::DrkpTestnet, Version::DRKP_TESTNET),
+        (KnownVersion::DrkvTestnet, Version::DRKV_TESTNET),
+        // ========================================================================
     ];
 
     #[test]
@@ -302,6 +430,7 @@ mod tests {
             assert_eq!(known.into_version(), *version);
             assert_eq!(version.into_known_version(), Some(*known));
             assert_eq!(KnownVersion::from_raw(version.as_u32()), Some(*known));
+            assert_eq!(known.info().version(), *version);
         }
     }
 
@@ -69,6 +69,7 @@ where
                             chain_code: right,
                         },
                         private_key,
+                        origin: None,
                     });
                 },
                 Err(err) => {
@@ -114,14 +115,21 @@ where
 
             match derived {
                 Ok(private_key) => {
+                    let parent_fingerprint = key_fingerprint(parent_public_bytes.as_ref());
                     return Ok(Self {
                         meta: ExtendedKeyMetadata {
                             depth: self.meta.depth.saturating_add(1),
-                            parent_fingerprint: key_fingerprint(parent_public_bytes.as_ref()),
+                            parent_fingerprint,
                             child_number: ChildNumber::from(child).into(),
                             chain_code: right,
                         },
                         private_key,
+                        origin: child_origin(
+                            &self.meta,
+                            self.origin.as_ref(),
+                            parent_fingerprint,
+                            child.into(),
+                        ),
                     });
                 },
                 Err(err) => {
@@ -176,14 +184,21 @@ where
 
             match derived {
                 Ok(private_key) => {
+                    let parent_fingerprint = key_fingerprint(parent_public_bytes.as_ref());
                     return Ok(Self {
                         meta: ExtendedKeyMetadata {
                             depth: self.meta.depth.saturating_add(1),
-                            parent_fingerprint: key_fingerprint(parent_public_bytes.as_ref()),
+                            parent_fingerprint,
                             child_number: child.into(),
                             chain_code: right,
                         },
                         private_key,
+                        origin: child_origin(
+                            &self.meta,
+                            self.origin.as_ref(),
+                            parent_fingerprint,
+                            child,
+                        ),
                     });
                 },
                 Err(err) => {
@@ -238,14 +253,21 @@ where
 
             match derived {
                 Ok(public_key) => {
+                    let parent_fingerprint = key_fingerprint(parent_public_bytes.as_ref());
                     return Ok(Self {
                         meta: ExtendedKeyMetadata {
                             depth: self.meta.depth.saturating_add(1),
-                            parent_fingerprint: key_fingerprint(parent_public_bytes.as_ref()),
+                            parent_fingerprint,
                             child_number: child.into(),
                             chain_code: right,
                         },
                         public_key,
+                        origin: child_origin(
+                            &self.meta,
+                            self.origin.as_ref(),
+                            parent_fingerprint,
+                            child,
+                        ),
                     });
                 },
                 Err(err) => {
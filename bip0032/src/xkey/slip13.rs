@@ -0,0 +1,261 @@
+//! Identity-based key derivation for e.g. SSH, GPG and HTTPS login, as specified by
+//! [SLIP-0013](https://github.com/satoshilabs/slips/blob/master/slip-0013.md).
+//!
+//! An [`Identity`] (`proto://user@host:port/path` and an index) is hashed into a hardened path
+//! `m/13'/a'/b'/c'/d'`, which is then derived with SLIP-0010 from the master key of the curve
+//! used by the service, usually nist256p1 or ed25519.
+//!
+//! ```rust
+//! use bip0032::{
+//!     DerivationPath, ExtendedPrivateKey,
+//!     curve::{ed25519::*, nist256p1::*},
+//!     slip10::{Slip10HardenedOnlyDerivation, Slip10MasterKey, Slip10NonHardenedDerivation},
+//!     slip13::Identity,
+//! };
+//!
+//! # let seed = [0u8; 64];
+//! let identity = "ssh://satoshi@bitcoin.org".parse::<Identity>().unwrap();
+//! let path = identity.derivation_path();
+//! assert!(path.to_string().starts_with("m/13'/"));
+//!
+//! let master = ExtendedPrivateKey::<Ed25519Curve<Ed25519DalekBackend>>::new_slip10(&seed).unwrap();
+//! let key = Slip10HardenedOnlyDerivation::derive_slip10_path(&master, &path).unwrap();
+//! let ssh = key.public_key().to_openssh(Some(&identity.to_string()));
+//! assert!(ssh.starts_with("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5"));
+//!
+//! let master = ExtendedPrivateKey::<Nist256p1Curve<P256Backend>>::new_slip10(&seed).unwrap();
+//! let path = DerivationPath::from(path);
+//! let key = Slip10NonHardenedDerivation::derive_slip10_path(&master, &path).unwrap();
+//! let ssh = key.public_key().to_openssh(None);
+//! assert!(ssh.starts_with("ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTY"));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, iter, str::FromStr};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    base64,
+    curve::{
+        UncompressedPublicKey,
+        ed25519::{Ed25519Backend, Ed25519Curve},
+        nist256p1::{Nist256p1Backend, Nist256p1Curve},
+    },
+    error::{Error, ErrorKind, Result},
+    path::{ChildNumber, DerivationPath, HardenedDerivationPath},
+    xkey::ExtendedPublicKey,
+};
+
+/// The purpose level of SLIP-0013 derivation paths, `m/13'`.
+pub const PURPOSE: u32 = 13;
+
+/// An identity of a service to log in to, e.g. `ssh://satoshi@bitcoin.org:22`, and an index to
+/// derive several keys for the same identity.
+///
+/// Components containing URI delimiters, e.g. the `Name <email>` user IDs of GPG, are set with the
+/// builder methods rather than parsed.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Identity {
+    proto: Option<String>,
+    user: Option<String>,
+    host: Option<String>,
+    port: Option<String>,
+    path: Option<String>,
+    index: u32,
+}
+
+impl Identity {
+    /// Creates an identity of a host, with index 0.
+    pub fn new(host: impl Into<String>) -> Self {
+        Self { host: Some(host.into()), ..Self::default() }
+    }
+
+    /// Sets the protocol, e.g. `ssh` or `https`.
+    pub fn with_proto(mut self, proto: impl Into<String>) -> Self {
+        self.proto = Some(proto.into());
+        self
+    }
+
+    /// Sets the user.
+    pub fn with_user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    /// Sets the port.
+    pub fn with_port(mut self, port: impl Into<String>) -> Self {
+        self.port = Some(port.into());
+        self
+    }
+
+    /// Sets the path, including its leading `/`.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Sets the index.
+    pub const fn with_index(mut self, index: u32) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Returns the protocol.
+    pub fn proto(&self) -> Option<&str> {
+        self.proto.as_deref()
+    }
+
+    /// Returns the user.
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    /// Returns the host.
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// Returns the port.
+    pub fn port(&self) -> Option<&str> {
+        self.port.as_deref()
+    }
+
+    /// Returns the path.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Returns the index.
+    pub const fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the identity hash: `SHA-256(index as 4 little-endian bytes || URI)`.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut engine = Sha256::new();
+        engine.update(self.index.to_le_bytes());
+        engine.update(self.to_string().as_bytes());
+        engine.finalize().into()
+    }
+
+    /// Returns the derivation path of this identity, `m/13'/a'/b'/c'/d'` with `a` to `d` the
+    /// first 16 bytes of the identity hash as little-endian numbers.
+    pub fn derivation_path(&self) -> HardenedDerivationPath {
+        let hash = self.hash();
+        let indices = hash[..16]
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().expect("chunk of 4 bytes")));
+        let path = iter::once(PURPOSE)
+            .chain(indices)
+            .map(|index| ChildNumber::from(index | ChildNumber::HARDENED_OFFSET))
+            .collect::<DerivationPath>();
+        HardenedDerivationPath::try_from(path).expect("identity paths are hardened")
+    }
+}
+
+/// Formats the URI of the identity, `[proto://][user@][host][:port][path]`, without its index.
+impl fmt::Display for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(proto) = &self.proto {
+            write!(f, "{proto}://")?;
+        }
+        if let Some(user) = &self.user {
+            write!(f, "{user}@")?;
+        }
+        if let Some(host) = &self.host {
+            f.write_str(host)?;
+        }
+        if let Some(port) = &self.port {
+            write!(f, ":{port}")?;
+        }
+        if let Some(path) = &self.path {
+            f.write_str(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Identity {
+    type Err = Error;
+
+    /// Parses the URI of an identity, with index 0.
+    fn from_str(uri: &str) -> Result<Self> {
+        let invalid = |component: &str| {
+            Error::new(ErrorKind::InvalidPath, "invalid identity URI")
+                .with_context("uri", uri)
+                .with_context("component", component)
+        };
+        let component = |value: &str, name: &str| {
+            if value.is_empty() { Err(invalid(name)) } else { Ok(Some(value.to_string())) }
+        };
+
+        let mut identity = Self::default();
+        let mut rest = uri;
+        if let Some((proto, after)) = rest.split_once("://") {
+            identity.proto = component(proto, "proto")?;
+            rest = after;
+        }
+        if let Some(start) = rest.find('/') {
+            identity.path = Some(rest[start..].to_string());
+            rest = &rest[..start];
+        }
+        if let Some((user, after)) = rest.rsplit_once('@') {
+            identity.user = component(user, "user")?;
+            rest = after;
+        }
+        // a colon inside the brackets of an IPv6 host is not a port separator
+        if let Some((host, port)) = rest.rsplit_once(':').filter(|(_, port)| !port.contains(']')) {
+            identity.port = component(port, "port")?;
+            rest = host;
+        }
+        identity.host = component(rest, "host")?;
+        Ok(identity)
+    }
+}
+
+// OpenSSH
+impl<B: Nist256p1Backend> ExtendedPublicKey<Nist256p1Curve<B>>
+where
+    B::PublicKey: UncompressedPublicKey,
+{
+    /// Encodes the public key of this key as an OpenSSH `ecdsa-sha2-nistp256` public key, as in
+    /// `authorized_keys` files.
+    pub fn to_openssh(&self, comment: Option<&str>) -> String {
+        let point = self.public_key.to_uncompressed_bytes();
+        openssh_public_key("ecdsa-sha2-nistp256", &[b"nistp256", &point], comment)
+    }
+}
+
+impl<B: Ed25519Backend> ExtendedPublicKey<Ed25519Curve<B>> {
+    /// Encodes the public key of this key as an OpenSSH `ssh-ed25519` public key, as in
+    /// `authorized_keys` files.
+    pub fn to_openssh(&self, comment: Option<&str>) -> String {
+        // SLIP-0010 ed25519 public keys are prefixed with 0x00
+        let public_key = self.to_bytes();
+        openssh_public_key("ssh-ed25519", &[&public_key[1..]], comment)
+    }
+}
+
+/// Formats `<key type> <base64 of the key blob> [comment]`, the key blob being the SSH strings of
+/// the key type and the fields of the key.
+fn openssh_public_key(key_type: &str, fields: &[&[u8]], comment: Option<&str>) -> String {
+    let mut blob = Vec::new();
+    for field in iter::once(key_type.as_bytes()).chain(fields.iter().copied()) {
+        blob.extend_from_slice(&(field.len() as u32).to_be_bytes());
+        blob.extend_from_slice(field);
+    }
+
+    let mut out = String::from(key_type);
+    out.push(' ');
+    base64::encode_to(&mut out, &blob).expect("writing to a string never fails");
+    if let Some(comment) = comment {
+        out.push(' ');
+        out.push_str(comment);
+    }
+    out
+}
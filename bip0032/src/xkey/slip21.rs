@@ -0,0 +1,85 @@
+//! Hierarchical derivation of symmetric keys, as specified by
+//! [SLIP-0021](https://github.com/satoshilabs/slips/blob/master/slip-0021.md).
+//!
+//! Nodes are addressed by paths of byte string labels instead of indices, e.g.
+//! `m/"SLIP-0021"/"Master encryption key"`, and every node holds a 32-byte symmetric key.
+//! Derivation is one-way: a key does not reveal its parent or siblings.
+//!
+//! ```rust
+//! use bip0032::slip21::Slip21Node;
+//!
+//! # let seed = [0u8; 64];
+//! let master = Slip21Node::new(&seed);
+//! let encryption = master.derive_path(["SLIP-0021", "Master encryption key"]);
+//! let authentication = master.derive_path(["SLIP-0021", "Authentication key"]);
+//! assert_ne!(encryption.key(), authentication.key());
+//! ```
+
+use core::fmt;
+
+use hmac::Mac;
+use zeroize::Zeroize;
+
+use crate::xkey::core::*;
+
+/// HMAC key of the master node.
+const HMAC_KEY: &[u8] = b"Symmetric key seed";
+
+/// A SLIP-0021 node: the derivation key of its children and its symmetric key.
+///
+/// Both halves are zeroized on drop, so intermediate nodes of a path do not outlive it.
+pub struct Slip21Node {
+    chain_key: [u8; 32],
+    key: [u8; 32],
+}
+
+impl Clone for Slip21Node {
+    fn clone(&self) -> Self {
+        Self { chain_key: self.chain_key, key: self.key }
+    }
+}
+
+/// Never prints the key material.
+impl fmt::Debug for Slip21Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Slip21Node").finish_non_exhaustive()
+    }
+}
+
+impl Drop for Slip21Node {
+    fn drop(&mut self) {
+        self.chain_key.zeroize();
+        self.key.zeroize();
+    }
+}
+
+impl Slip21Node {
+    /// Generates the master node from a seed.
+    pub fn new(seed: &[u8]) -> Self {
+        let (chain_key, key) = derive_master_key_parts(seed, HMAC_KEY);
+        Self { chain_key, key }
+    }
+
+    /// Derives the child node of a label.
+    pub fn derive_child(&self, label: impl AsRef<[u8]>) -> Self {
+        let (chain_key, key) = hmac_sha512_split(&self.chain_key, |mac| {
+            mac.update(&[0u8]);
+            mac.update(label.as_ref());
+        });
+        Self { chain_key, key }
+    }
+
+    /// Derives the descendant node along a path of labels, relative to this node.
+    pub fn derive_path<L: AsRef<[u8]>>(&self, labels: impl IntoIterator<Item = L>) -> Self {
+        labels.into_iter().fold(self.clone(), |node, label| node.derive_child(label))
+    }
+
+    /// Returns the symmetric key of this node.
+    ///
+    /// # Warning
+    ///
+    /// Exposes raw key material. Handle with care.
+    pub fn key(&self) -> &[u8; 32] {
+        &self.key
+    }
+}
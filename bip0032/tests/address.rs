@@ -0,0 +1,149 @@
+use bip0032::{
+    DerivationPath, ErrorKind, ExtendedPrivateKey, ExtendedPublicKey, KnownVersion,
+    address::{AddressType, Network},
+    curve::secp256k1::*,
+};
+
+// BIP-0039 seed of "abandon abandon abandon abandon abandon abandon abandon abandon abandon
+// abandon abandon about" without passphrase, used by the BIP-0044/49/84/86 test vectors.
+const SEED: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+
+fn derive<B: Secp256k1Backend>(path: &str) -> ExtendedPublicKey<Secp256k1Curve<B>> {
+    let seed = const_hex::decode(SEED).unwrap();
+    let master = ExtendedPrivateKey::<Secp256k1Curve<B>>::new(&seed).unwrap();
+    let path = path.parse::<DerivationPath>().unwrap();
+    master.derive_path(&path).unwrap().public_key()
+}
+
+fn run_vectors<B: Secp256k1Backend>() {
+    let cases = [
+        // https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki
+        (
+            "m/44'/0'/0'/0/0",
+            Network::Bitcoin,
+            AddressType::P2pkh,
+            "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+            "76a914d986ed01b7a22225a70edbf2ba7cfb63a15cb3aa88ac",
+        ),
+        // https://github.com/bitcoin/bips/blob/master/bip-0049.mediawiki
+        (
+            "m/49'/1'/0'/0/0",
+            Network::Testnet,
+            AddressType::P2shP2wpkh,
+            "2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2",
+            "a914336caa13e08b96080a32b5d818d59b4ab3b3674287",
+        ),
+        // https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki
+        (
+            "m/84'/0'/0'/0/0",
+            Network::Bitcoin,
+            AddressType::P2wpkh,
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            "0014c0cebcd6c3d3ca8c75dc5ec62ebe55330ef910e2",
+        ),
+        (
+            "m/84'/0'/0'/0/1",
+            Network::Bitcoin,
+            AddressType::P2wpkh,
+            "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g",
+            "00149c90f934ea51fa0f6504177043e0908da6929983",
+        ),
+        (
+            "m/84'/0'/0'/1/0",
+            Network::Bitcoin,
+            AddressType::P2wpkh,
+            "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el",
+            "00143e34985dca6fddc9fb369940e4c7d8e2873f529c",
+        ),
+        // https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki
+        (
+            "m/86'/0'/0'/0/0",
+            Network::Bitcoin,
+            AddressType::P2tr,
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+        ),
+        (
+            "m/86'/0'/0'/0/1",
+            Network::Bitcoin,
+            AddressType::P2tr,
+            "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+            "5120a82f29944d65b86ae6b5e5cc75e294ead6c59391a1edc5e016e3498c67fc7bbb",
+        ),
+        (
+            "m/86'/0'/0'/1/0",
+            Network::Bitcoin,
+            AddressType::P2tr,
+            "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7",
+            "5120882d74e5d0572d5a816cef0041a96b6c1de832f6f9676d9605c44d5e9a97d3dc",
+        ),
+    ];
+
+    for (path, network, address_type, expected, script_pubkey) in cases {
+        let key = derive::<B>(path);
+        let address = key.to_address(network, address_type).unwrap();
+        assert_eq!(address.to_string(), expected, "{path}");
+        assert_eq!(address.network(), network);
+        assert_eq!(address.address_type(), address_type);
+        assert_eq!(const_hex::encode(address.script_pubkey()), script_pubkey, "{path}");
+    }
+}
+
+#[test]
+fn test_address_vectors() {
+    #[cfg(feature = "k256")]
+    run_vectors::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_vectors::<Secp256k1FfiBackend>();
+}
+
+fn run_networks<B: Secp256k1Backend>() {
+    let key = derive::<B>("m/84'/1'/0'/0/0");
+    let testnet = key.to_address(Network::Testnet, AddressType::P2wpkh).unwrap().to_string();
+    let signet = key.to_address(Network::Signet, AddressType::P2wpkh).unwrap().to_string();
+    let regtest = key.to_address(Network::Regtest, AddressType::P2wpkh).unwrap().to_string();
+    assert_eq!(testnet, "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl");
+    assert_eq!(signet, testnet);
+    assert_eq!(regtest, "bcrt1q6rz28mcfaxtmd6v789l9rrlrusdprr9pz3cppk");
+
+    let p2pkh = key.to_address(Network::Regtest, AddressType::P2pkh).unwrap().to_string();
+    assert!(p2pkh.starts_with('m') || p2pkh.starts_with('n'));
+    let p2tr = key.to_address(Network::Signet, AddressType::P2tr).unwrap().to_string();
+    assert!(p2tr.starts_with("tb1p"));
+}
+
+#[test]
+fn test_address_networks() {
+    #[cfg(feature = "k256")]
+    run_networks::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_networks::<Secp256k1FfiBackend>();
+}
+
+fn run_default_address<B: Secp256k1Backend>() {
+    let key = derive::<B>("m/84'/0'/0'/0/0");
+    let address = key.to_default_address(KnownVersion::Zpub).unwrap();
+    assert_eq!(address.to_string(), "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu");
+
+    let address = key.to_default_address(KnownVersion::Xpub).unwrap();
+    assert_eq!(address.address_type(), AddressType::P2pkh);
+    assert_eq!(address.network(), Network::Bitcoin);
+
+    let address = key.to_default_address(KnownVersion::Upub).unwrap();
+    assert_eq!(address.address_type(), AddressType::P2shP2wpkh);
+    assert_eq!(address.network(), Network::Testnet);
+    assert!(address.to_string().starts_with('2'));
+
+    let err = key.to_default_address(KnownVersion::ZpubWsh).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidVersion);
+    let err = key.to_default_address(KnownVersion::Ltub).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidVersion);
+}
+
+#[test]
+fn test_default_address() {
+    #[cfg(feature = "k256")]
+    run_default_address::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_default_address::<Secp256k1FfiBackend>();
+}
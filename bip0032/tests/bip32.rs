@@ -1,5 +1,9 @@
+use core::num::NonZeroUsize;
+
 use bip0032::{
-    DerivationPath, ExtendedKeyPayload, ExtendedPrivateKey, ExtendedPublicKey, KnownVersion,
+    ChildNumber, CoinType, DerivationCache, DerivationPath, ErrorKind, ExtendedKeyPayload,
+    ExtendedPrivateKey, ExtendedPublicKey, KeyOrigin, KnownVersion, NetworkKind, OriginKey,
+    ScriptType, Version, VersionRegistry, convert_version, convert_version_cross_network,
     curve::secp256k1::*,
 };
 
@@ -176,6 +180,377 @@ fn test_vector_4() {
     run_cases_for_all_backends(&cases);
 }
 
+fn run_origin_case<B: Secp256k1Backend>() {
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::<Curve<B>>::new(&seed).unwrap();
+    let master_fingerprint = const_hex::decode("3442193e").unwrap();
+
+    let origin = master.origin().unwrap();
+    assert_eq!(origin.fingerprint().as_slice(), master_fingerprint);
+    assert!(origin.path().children().is_empty());
+    assert_eq!(master.fingerprint().as_slice(), master_fingerprint);
+    assert_eq!(master.identifier()[..4], master.fingerprint());
+
+    let path = "m/0H/1".parse::<DerivationPath>().unwrap();
+    let derived = master.derive_path(&path).unwrap();
+    assert_eq!(derived.depth(), 2);
+    assert_eq!(derived.child_number(), ChildNumber::new(1, false).unwrap());
+    let origin = derived.origin().unwrap();
+    assert_eq!(origin.fingerprint().as_slice(), master_fingerprint);
+    assert_eq!(origin.path(), &path);
+
+    // the origin survives conversion to a public key and public derivation
+    let public = derived.public_key();
+    assert_eq!(public.origin(), Some(origin));
+    assert_eq!(public.fingerprint(), derived.fingerprint());
+    let path = "m/0H/1/2/3".parse::<DerivationPath>().unwrap();
+    let child = public.derive_path(&"m/2/3".parse().unwrap()).unwrap();
+    assert_eq!(child.depth(), 4);
+    assert_eq!(
+        child.parent_fingerprint(),
+        public.derive_child(ChildNumber::new(2, false).unwrap()).unwrap().fingerprint()
+    );
+    assert_eq!(child.origin().unwrap().path(), &path);
+
+    // keys decoded at a non-zero depth have no known origin
+    let xpub = public.encode_with(KnownVersion::Xpub.into_version()).unwrap().to_string();
+    let decoded = xpub.parse::<ExtendedPublicKey<Curve<B>>>().unwrap();
+    assert_eq!(decoded.origin(), None);
+    assert_eq!(decoded.derive_path(&"m/2".parse().unwrap()).unwrap().origin(), None);
+}
+
+#[test]
+fn test_key_origin() {
+    #[cfg(feature = "k256")]
+    run_origin_case::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_origin_case::<Secp256k1FfiBackend>();
+}
+
+fn run_origin_key_case<B: Secp256k1Backend>() {
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::<Curve<B>>::new(&seed).unwrap();
+    let account = master.derive_path(&"m/84'/0'/0'".parse().unwrap()).unwrap().public_key();
+    let zpub = account.encode_with(KnownVersion::Zpub.into_version()).unwrap().to_string();
+
+    let encoded = format!("[3442193e/84h/0h/0h]{zpub}");
+    let key = encoded.parse::<OriginKey<Curve<B>>>().unwrap();
+    assert_eq!(key.to_string(), format!("[3442193e/84'/0'/0']{zpub}"));
+    assert_eq!(key.version(), KnownVersion::Zpub.into_version());
+    assert_eq!(key.origin(), &account.origin().unwrap());
+
+    // the parsed origin is carried by keys derived from the origin key
+    let child = key.key().derive_path(&"m/0/5".parse().unwrap()).unwrap();
+    assert_eq!(child.origin().unwrap().to_string(), "[3442193e/84'/0'/0'/0/5]");
+
+    let mismatched = [
+        // origin path length does not match key depth
+        format!("[3442193e/84'/0']{zpub}"),
+        // origin path does not end with key child number
+        format!("[3442193e/84'/0'/1']{zpub}"),
+    ];
+    for encoded in &mismatched {
+        let err = encoded.parse::<OriginKey<Curve<B>>>().err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidOrigin);
+    }
+
+    let err = zpub.parse::<OriginKey<Curve<B>>>().err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidOrigin);
+
+    let purpose = master.derive_path(&"m/84'".parse().unwrap()).unwrap().public_key();
+    let origin = "[deadbeef/84']".parse::<KeyOrigin>().unwrap();
+    let err = OriginKey::new(origin, purpose, KnownVersion::Xpub.into_version())
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidOrigin);
+}
+
+#[test]
+fn test_origin_key() {
+    #[cfg(feature = "k256")]
+    run_origin_key_case::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_origin_key_case::<Secp256k1FfiBackend>();
+}
+
+fn run_derive_path_case<B: Secp256k1Backend>() {
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::<Curve<B>>::new(&seed).unwrap();
+    let xprv = KnownVersion::Xprv.into_version();
+    let xpub = KnownVersion::Xpub.into_version();
+
+    for path in ["m/86'/0'/0'/0/7", "m/0/1'/2", "m/0'/1'"] {
+        let path = path.parse::<DerivationPath>().unwrap();
+        let mut expected = master.clone();
+        for child in &path {
+            expected = expected.derive_child(*child).unwrap();
+        }
+
+        let derived = master.derive_path(&path).unwrap();
+        assert_eq!(
+            derived.encode_with(xprv).unwrap().to_string(),
+            expected.encode_with(xprv).unwrap().to_string()
+        );
+        assert_eq!(derived.origin(), expected.origin());
+        assert_eq!(derived.origin().unwrap().path(), &path);
+    }
+
+    let account = master.derive_path(&"m/86'/0'/0'".parse().unwrap()).unwrap().public_key();
+    let path = "m/0/7/9".parse::<DerivationPath>().unwrap();
+    let mut expected = account.clone();
+    for child in &path {
+        expected = expected.derive_child(*child).unwrap();
+    }
+    let derived = account.derive_path(&path).unwrap();
+    assert_eq!(
+        derived.encode_with(xpub).unwrap().to_string(),
+        expected.encode_with(xpub).unwrap().to_string()
+    );
+    assert_eq!(derived.origin().unwrap().to_string(), "[3442193e/86'/0'/0'/0/7/9]");
+
+    let err = account.derive_path(&"m/0/1'/2".parse().unwrap()).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidDerivation);
+    assert!(err.context().contains(&("child_index", "1".to_string())));
+}
+
+#[test]
+fn test_derive_path() {
+    #[cfg(feature = "k256")]
+    run_derive_path_case::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_derive_path_case::<Secp256k1FfiBackend>();
+}
+
+fn run_derivation_cache_case<B: Secp256k1Backend>() {
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::<Curve<B>>::new(&seed).unwrap();
+    let xprv = KnownVersion::Xprv.into_version();
+    let xpub = KnownVersion::Xpub.into_version();
+
+    let mut cache = DerivationCache::new(master.clone(), NonZeroUsize::new(4).unwrap());
+    for path in ["m/84'/0'/0'/0/0", "m/84'/0'/0'/0/1", "m/84'/0'/0'/1/0", "m/0/1"] {
+        let path = path.parse::<DerivationPath>().unwrap();
+        let cached = cache.derive_path(&path).unwrap();
+        let expected = master.derive_path(&path).unwrap();
+        assert_eq!(
+            cached.encode_with(xprv).unwrap().to_string(),
+            expected.encode_with(xprv).unwrap().to_string()
+        );
+        assert_eq!(cached.origin(), expected.origin());
+    }
+    // the least recently used prefixes m/84' and m/84'/0' were evicted
+    assert_eq!(cache.len(), 4);
+    assert!(cache.contains(&"m/84'/0'/0'".parse().unwrap()));
+    assert!(cache.contains(&"m/84'/0'/0'/0".parse().unwrap()));
+    assert!(cache.contains(&"m/84'/0'/0'/1".parse().unwrap()));
+    assert!(cache.contains(&"m/0".parse().unwrap()));
+    assert!(!cache.contains(&"m/84'".parse().unwrap()));
+    assert!(!cache.contains(&"m/84'/0'".parse().unwrap()));
+
+    let empty = cache.derive_path(&DerivationPath::default()).unwrap();
+    assert_eq!(empty.fingerprint(), master.fingerprint());
+    cache.clear();
+    assert!(cache.is_empty());
+
+    let account = master.derive_path(&"m/84'/0'/0'".parse().unwrap()).unwrap().public_key();
+    let mut cache = DerivationCache::new(account.clone(), NonZeroUsize::new(1).unwrap());
+    let path = "m/0/5".parse::<DerivationPath>().unwrap();
+    assert_eq!(
+        cache.derive_path(&path).unwrap().encode_with(xpub).unwrap().to_string(),
+        account.derive_path(&path).unwrap().encode_with(xpub).unwrap().to_string()
+    );
+    let err = cache.derive_path(&"m/0/1'".parse().unwrap()).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidDerivation);
+}
+
+#[test]
+fn test_derivation_cache() {
+    #[cfg(feature = "k256")]
+    run_derivation_cache_case::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_derivation_cache_case::<Secp256k1FfiBackend>();
+}
+
+fn run_derive_range_case<B: Secp256k1Backend>() {
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::<Curve<B>>::new(&seed).unwrap();
+    let chain = master.derive_path(&"m/84'/0'/0'/0".parse().unwrap()).unwrap().public_key();
+    let version = KnownVersion::Xpub.into_version();
+
+    let expected = (5..10)
+        .map(|i| chain.derive_child(ChildNumber::new(i, false).unwrap()).unwrap())
+        .collect::<Vec<_>>();
+    let ranged = chain.derive_range(5..10).unwrap();
+    let children = chain
+        .derive_children((5..10).map(|i| ChildNumber::new(i, false).unwrap()))
+        .unwrap();
+    assert_eq!(ranged.len(), expected.len());
+    for ((expected, ranged), child) in expected.iter().zip(&ranged).zip(&children) {
+        let encoded = expected.encode_with(version).unwrap().to_string();
+        assert_eq!(ranged.encode_with(version).unwrap().to_string(), encoded);
+        assert_eq!(child.encode_with(version).unwrap().to_string(), encoded);
+        assert_eq!(ranged.origin(), expected.origin());
+    }
+    assert!(chain.derive_range(3..3).unwrap().is_empty());
+
+    let err = chain.derive_children([ChildNumber::new(7, true).unwrap()]).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidDerivation);
+    assert!(err.context().contains(&("child_index", "7".to_string())));
+    assert!(err.context().contains(&("hardened", "true".to_string())));
+
+    let err = chain.derive_range(0x7fff_fffe..0x8000_0001).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidPath);
+    assert!(err.context().contains(&("child_index", "2147483648".to_string())));
+}
+
+#[test]
+fn test_derive_range() {
+    #[cfg(feature = "k256")]
+    run_derive_range_case::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_derive_range_case::<Secp256k1FfiBackend>();
+}
+
+#[cfg(feature = "rayon")]
+fn run_par_derive_range_case<B>()
+where
+    B: Secp256k1Backend,
+    B::PublicKey: Send + Sync,
+{
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::<Curve<B>>::new(&seed).unwrap();
+    let chain = master.derive_path(&"m/84'/0'/0'/0".parse().unwrap()).unwrap().public_key();
+    let version = KnownVersion::Xpub.into_version();
+
+    let expected = chain.derive_range(0..100).unwrap();
+    let parallel = chain.par_derive_range(0..100).unwrap();
+    assert_eq!(parallel.len(), expected.len());
+    for (expected, parallel) in expected.iter().zip(&parallel) {
+        assert_eq!(
+            parallel.encode_with(version).unwrap().to_string(),
+            expected.encode_with(version).unwrap().to_string()
+        );
+    }
+
+    assert_eq!(chain.par_derive_range(0x7fff_fff0..0x8000_0000).unwrap().len(), 16);
+    let err = chain.par_derive_range(0x7fff_ffff..0x8000_0001).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidPath);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_derive_range() {
+    #[cfg(feature = "k256")]
+    run_par_derive_range_case::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_par_derive_range_case::<Secp256k1FfiBackend>();
+}
+
+#[test]
+fn test_version_registry() {
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::<Curve<K256Backend>>::new(&seed).unwrap();
+    let registry = VersionRegistry::new();
+
+    for info in registry.iter() {
+        let version = info.version();
+        let encoded = if version.is_public() {
+            master.public_key().encode_with(version).unwrap().to_string()
+        } else {
+            master.encode_with(version).unwrap().to_string()
+        };
+        assert!(encoded.starts_with(info.prefix()), "{encoded}");
+        assert_eq!(registry.from_prefix(&encoded[..4]), Some(info));
+        assert_eq!(registry.get_raw(version.as_u32()), Some(info));
+    }
+
+    let ltub = KnownVersion::from_prefix("Ltub").unwrap();
+    assert_eq!(ltub.into_version(), Version::LTUB);
+    assert_eq!(ltub.info().coin(), CoinType::LITECOIN);
+    assert_eq!(ltub.info().network(), NetworkKind::Mainnet);
+    assert_eq!(ltub.info().script_type(), ScriptType::P2pkh);
+    assert_eq!(Version::VPUB_WSH.info().unwrap().script_type(), ScriptType::P2wsh);
+    assert_eq!(Version::TGUB.info().unwrap().network(), NetworkKind::Testnet);
+    assert_eq!(KnownVersion::Zpub.prefix(), "zpub");
+    assert_eq!(KnownVersion::from_prefix("ZPUB"), None);
+
+    let mut registry = registry;
+    let custom = Version::Private(0x0488_ADE5);
+    assert!(custom.info().is_none());
+    let info = registry
+        .register(custom, CoinType::BITCOIN_CASH, NetworkKind::Mainnet, ScriptType::P2pkh)
+        .unwrap()
+        .clone();
+    assert_eq!(info.prefix(), "xprv");
+    assert_eq!(registry.get(custom), Some(&info));
+    // built-in versions take precedence for shared prefixes
+    assert_eq!(registry.from_prefix("xprv").unwrap().version(), Version::XPRV);
+    let encoded = master.encode_with(custom).unwrap().to_string();
+    assert!(encoded.starts_with("xprv"));
+
+    let err = registry
+        .register(custom, CoinType::BITCOIN_CASH, NetworkKind::Mainnet, ScriptType::P2pkh)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidVersion);
+    let err = registry
+        .register(Version::ZPUB, CoinType::LITECOIN, NetworkKind::Mainnet, ScriptType::P2wpkh)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidVersion);
+}
+
+#[test]
+fn test_convert_version() {
+    // https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki
+    let seed = const_hex::decode("5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4").unwrap();
+    let master = ExtendedPrivateKey::<Curve<K256Backend>>::new(&seed).unwrap();
+    let account = master.derive_path(&"m/84'/0'/0'".parse().unwrap()).unwrap();
+    let zpub = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+    let zprv = "zprvAdG4iTXWBoARxkkzNpNh8r6Qag3irQB8PzEMkAFeTRXxHpbF9z4QgEvBRmfvqWvGp42t42nvgGpNgYSJA9iefm1yYNZKEm7z6qUWCroSQnE";
+
+    let xpub = account.public_key().encode_with(Version::XPUB).unwrap().to_string();
+    let xprv = account.encode_with(Version::XPRV).unwrap().to_string();
+    assert_eq!(convert_version(&xpub, KnownVersion::Zpub).unwrap().as_str(), zpub);
+    assert_eq!(convert_version(&xprv, KnownVersion::Zprv).unwrap().as_str(), zprv);
+    assert_eq!(convert_version(zpub, KnownVersion::Xpub).unwrap().as_str(), xpub);
+    let ypub = convert_version(zpub, KnownVersion::Ypub).unwrap();
+    assert_eq!(convert_version(&ypub, KnownVersion::Zpub).unwrap().as_str(), zpub);
+
+    // private/public mismatch
+    let err = convert_version(zpub, KnownVersion::Zprv).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidVersion);
+    assert_eq!(err.message(), "expected public version bytes");
+    let err = convert_version_cross_network(zprv, KnownVersion::Vpub).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidVersion);
+    assert_eq!(err.message(), "expected private version bytes");
+
+    // cross-network and cross-coin conversions need to be forced
+    for target in [KnownVersion::Vpub, KnownVersion::Tpub, KnownVersion::Ltub] {
+        let err = convert_version(zpub, target).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidVersion);
+        assert!(err.context().contains(&("from", "zpub".to_string())));
+
+        let converted = convert_version_cross_network(zpub, target).unwrap();
+        assert!(converted.starts_with(target.prefix()));
+        assert_eq!(
+            convert_version_cross_network(&converted, KnownVersion::Zpub).unwrap().as_str(),
+            zpub
+        );
+    }
+
+    // the network of unknown versions cannot be checked
+    let custom = account.public_key().encode_with(Version::Public(0x0488_B21F)).unwrap();
+    let err = custom.with_version(Version::XPUB).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidVersion);
+    let payload = zpub.parse::<ExtendedKeyPayload>().unwrap();
+    let custom = payload.with_version_cross_network(Version::Public(0x0488_B21F)).unwrap();
+    assert_eq!(custom.version(), Version::Public(0x0488_B21F));
+
+    let err = convert_version("xpub", KnownVersion::Zpub).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidPayload);
+}
+
 fn assert_invalid_xpub<B: Secp256k1Backend>(keys: &[&str]) {
     for &key in keys {
         let parsed = key.parse::<ExtendedKeyPayload>();
@@ -253,3 +628,129 @@ fn test_vector_5_invalid_extended_keys() {
 
     assert_invalid_case_for_all_backends(&invalid_xpub, &invalid_xprv);
 }
+
+#[test]
+fn test_payload_bytes() {
+    let xpub = "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw";
+    let xprv = "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7";
+
+    for encoded in [xpub, xprv] {
+        let payload = encoded.parse::<ExtendedKeyPayload>().unwrap();
+        let bytes = payload.to_bytes();
+        assert_eq!(bytes.len(), ExtendedKeyPayload::LENGTH);
+
+        assert_eq!(bytes[..4], payload.version().to_bytes());
+        // depth 1, parent fingerprint 3442193e, child number 0H
+        assert_eq!(bytes[4..13], const_hex::decode("013442193e80000000").unwrap()[..]);
+
+        let mut buf = [0xFF; ExtendedKeyPayload::LENGTH];
+        payload.write_bytes(&mut buf);
+        assert_eq!(buf, bytes);
+
+        let decoded = ExtendedKeyPayload::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.version(), payload.version());
+        assert_eq!(decoded.to_string(), encoded);
+        let decoded = ExtendedKeyPayload::try_from(&bytes[..]).unwrap();
+        assert_eq!(decoded.to_string(), encoded);
+    }
+
+    let bytes = xpub.parse::<ExtendedKeyPayload>().unwrap().to_bytes();
+    let err = ExtendedKeyPayload::from_bytes(&bytes[..77]).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidPayload);
+    assert!(err.context().contains(&("decoded_len", "77".to_string())));
+
+    // zero depth with non-zero parent fingerprint
+    let mut invalid = bytes;
+    invalid[4] = 0;
+    let err = ExtendedKeyPayload::from_bytes(&invalid).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidPayload);
+    assert_eq!(err.message(), "zero depth with non-zero parent fingerprint");
+
+    // invalid public key prefix
+    let mut invalid = bytes;
+    invalid[45] = 0x04;
+    let err = ExtendedKeyPayload::from_bytes(&invalid).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidKeyData);
+}
+
+#[test]
+fn test_payload_encode_to() {
+    let xprv = "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7";
+    let payload = ExtendedKeyPayload::decode_from(xprv.as_bytes()).unwrap();
+
+    let mut buf = [0u8; ExtendedKeyPayload::MAX_ENCODED_LENGTH];
+    assert_eq!(payload.encode_to(&mut buf), xprv);
+    assert_eq!(payload.to_zeroizing_string().as_str(), xprv);
+    assert_eq!(payload.to_string(), xprv);
+
+    // Dash testnet versions encode to the maximum length
+    let payload = payload.with_version_cross_network(KnownVersion::DrkvTestnet.into_version());
+    let encoded = payload.unwrap().to_zeroizing_string();
+    assert_eq!(encoded.len(), ExtendedKeyPayload::MAX_ENCODED_LENGTH);
+    assert!(encoded.starts_with("DRKV"));
+    let decoded = ExtendedKeyPayload::decode_from(encoded.as_bytes()).unwrap();
+    assert_eq!(decoded.version(), KnownVersion::DrkvTestnet.into_version());
+
+    let err = ExtendedKeyPayload::decode_from(&xprv.as_bytes()[..110]).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidPayload);
+    let err = ExtendedKeyPayload::decode_from(&[b'x'; 124]).err().unwrap();
+    assert_eq!(err.message(), "invalid base58check length");
+    let err = ExtendedKeyPayload::decode_from(b"xprv\xff").err().unwrap();
+    assert_eq!(err.message(), "invalid base58check encoding");
+}
+
+fn run_key_equality_case<B: Secp256k1Backend>() {
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::<Curve<B>>::new(&seed).unwrap();
+    let child = master.derive_path(&"m/0H".parse().unwrap()).unwrap();
+
+    // keys are equal regardless of their origin
+    let xprv = child
+        .encode_with(KnownVersion::Xprv.into_version())
+        .unwrap()
+        .to_zeroizing_string();
+    let decoded = xprv.parse::<ExtendedPrivateKey<Curve<B>>>().unwrap();
+    assert_eq!(decoded.origin(), None);
+    assert_eq!(decoded, child);
+    assert_ne!(master, child);
+    assert_ne!(master.derive_child(ChildNumber::new(1, true).unwrap()).unwrap(), child);
+
+    let public = child.public_key();
+    let xpub = public.encode_with(KnownVersion::Xpub.into_version()).unwrap().to_string();
+    assert_eq!(xpub.parse::<ExtendedPublicKey<Curve<B>>>().unwrap(), public);
+    assert_ne!(master.public_key(), public);
+
+    // public keys can be used as map keys
+    let children = public.derive_range(0..4).unwrap();
+    let index = children
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(i, key)| (key, i))
+        .collect::<std::collections::HashMap<_, _>>();
+    assert_eq!(index[&public.derive_child(ChildNumber::new(2, false).unwrap()).unwrap()], 2);
+    let sorted = children.into_iter().collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(sorted.len(), 4);
+
+    // debug output shows the fingerprint, depth and child number, but no key material
+    let debug = format!("{child:?}");
+    assert_eq!(
+        debug,
+        "ExtendedPrivateKey { fingerprint: 5c1bd648, depth: 1, child_number: 0', .. }"
+    );
+    let secret = const_hex::encode(child.to_bytes().as_slice());
+    assert!(!debug.contains(&secret));
+    assert!(!debug.contains(&const_hex::encode(child.chain_code())));
+    assert_eq!(
+        format!("{public:?}"),
+        "ExtendedPublicKey { fingerprint: 5c1bd648, depth: 1, child_number: 0', .. }"
+    );
+}
+
+#[test]
+fn test_key_equality_and_debug() {
+    #[cfg(feature = "k256")]
+    run_key_equality_case::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_key_equality_case::<Secp256k1FfiBackend>();
+}
@@ -0,0 +1,194 @@
+use bip0032::{
+    ErrorKind, ExtendedPrivateKey, Version,
+    bip85::{Bip39Language, Drng},
+    curve::secp256k1::*,
+};
+
+// BIP-0085 test vectors
+const ROOT: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+fn root<B: Secp256k1Backend>() -> ExtendedPrivateKey<Secp256k1Curve<B>> {
+    ROOT.parse().unwrap()
+}
+
+fn run_entropy<B: Secp256k1Backend>() {
+    let root = root::<B>();
+    let cases = [
+        (
+            [0, 0],
+            "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f00b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7",
+        ),
+        (
+            [0, 1],
+            "70c6e3e8ebee8dc4c0dbba66076819bb8c09672527c4277ca8729532ad711872218f826919f6b67218adde99018a6df9095ab2b58d803b5b93ec9802085a690e",
+        ),
+    ];
+    for (application, expected) in cases {
+        let entropy = root.bip85_entropy(&application).unwrap();
+        assert_eq!(const_hex::encode(*entropy), expected);
+    }
+
+    // application indices are hardened
+    let err = root.bip85_entropy(&[0x8000_0000]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidPath);
+}
+
+fn run_drng<B: Secp256k1Backend>() {
+    let entropy = root::<B>().bip85_entropy(&[0, 0]).unwrap();
+    let mut drng = Drng::new(&entropy);
+    let mut out = [0u8; 80];
+    drng.fill_bytes(&mut out);
+    assert_eq!(
+        const_hex::encode(out),
+        "b78b1ee6b345eae6836c2d53d33c64cdaf9a696487be81b03e822dc84b3f1cd883d7559e53d175f243e4c349e822a957bbff9224bc5dde9492ef54e8a439f6bc8c7355b87a925a37ee405a7502991111"
+    );
+
+    // reading in pieces across the rate boundary gives the same stream
+    let mut whole = [0u8; 300];
+    Drng::new(&entropy).fill_bytes(&mut whole);
+    let mut pieces = [0u8; 300];
+    let mut drng = Drng::new(&entropy);
+    for chunk in pieces.chunks_mut(7) {
+        drng.fill_bytes(chunk);
+    }
+    assert_eq!(whole, pieces);
+    assert_eq!(&whole[..80], &out);
+}
+
+fn run_bip39<B: Secp256k1Backend>() {
+    let root = root::<B>();
+    let cases = [
+        (12, "6250b68daf746d12a24d58b4787a714b"),
+        (18, "938033ed8b12698449d4bbca3c853c66b293ea1b1ce9d9dc"),
+        (24, "ae131e2312cdc61331542efe0d1077bac5ea803adf24b313a4f0e48e9c51f37f"),
+    ];
+    for (words, expected) in cases {
+        let entropy = root.bip85_bip39(Bip39Language::English, words, 0).unwrap();
+        assert_eq!(const_hex::encode(&*entropy), expected);
+    }
+
+    // every word count takes a prefix of its own entropy
+    for words in [12, 15, 18, 21, 24] {
+        let entropy = root.bip85_bip39(Bip39Language::Japanese, words, 1).unwrap();
+        assert_eq!(entropy.len(), words as usize * 4 / 3);
+        let full = root.bip85_entropy(&[39, 1, words, 1]).unwrap();
+        assert_eq!(&entropy[..], &full[..entropy.len()]);
+    }
+
+    // languages and indices derive distinct entropy
+    let english = root.bip85_bip39(Bip39Language::English, 12, 0).unwrap();
+    assert_ne!(english, root.bip85_bip39(Bip39Language::Czech, 12, 0).unwrap());
+    assert_ne!(english, root.bip85_bip39(Bip39Language::English, 12, 1).unwrap());
+
+    for words in [0, 11, 13, 25] {
+        let err = root.bip85_bip39(Bip39Language::English, words, 0).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidEntropy);
+        assert_eq!(err.message(), "invalid mnemonic word count");
+    }
+}
+
+#[test]
+fn test_bip39_language() {
+    for (index, language) in Bip39Language::ALL.into_iter().enumerate() {
+        assert_eq!(language.index(), index as u32);
+        assert_eq!(Bip39Language::from_index(index as u32), Some(language));
+    }
+    assert_eq!(Bip39Language::Portuguese.index(), 9);
+    assert_eq!(Bip39Language::from_index(10), None);
+}
+
+fn run_wif_and_xprv<B: Secp256k1Backend>() {
+    let root = root::<B>();
+
+    let wif = root.bip85_wif(0).unwrap();
+    assert_eq!(
+        const_hex::encode(*wif.to_bytes()),
+        "7040bb53104f27367f317558e78a994ada7296c6fde36a364e5baf206e502bb1"
+    );
+    assert_eq!(wif.to_wif().as_str(), "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp");
+
+    let xprv = root.bip85_xprv(0).unwrap();
+    assert_eq!(
+        xprv.encode_with(Version::XPRV).unwrap().to_string(),
+        "xprv9s21ZrQH143K2srSbCSg4m4kLvPMzcWydgmKEnMmoZUurYuBuYG46c6P71UGXMzmriLzCCBvKQWBUv3vPB3m1SATMhp3uEjXHJ42jFg7myX"
+    );
+    let entropy = root.bip85_entropy(&[32, 0]).unwrap();
+    assert_eq!(xprv.chain_code(), entropy[..32]);
+    assert_eq!(&xprv.to_bytes()[..], &entropy[32..]);
+    // the derived key is a master key of its own
+    assert_eq!(xprv.depth(), 0);
+    assert_eq!(xprv.origin().unwrap().fingerprint(), xprv.fingerprint());
+}
+
+fn run_hex_and_passwords<B: Secp256k1Backend>() {
+    let root = root::<B>();
+
+    let hex = root.bip85_hex(64, 0).unwrap();
+    assert_eq!(
+        const_hex::encode(&*hex),
+        "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c"
+    );
+    assert_eq!(root.bip85_hex(16, 0).unwrap().len(), 16);
+
+    assert_eq!(root.bip85_base64_password(21, 0).unwrap().as_str(), "dKLoepugzdVJvdL56ogNV");
+    assert_eq!(root.bip85_base85_password(12, 0).unwrap().as_str(), "_s`{TW89)i4`");
+
+    // the longest passwords never include padding
+    let base64 = root.bip85_base64_password(86, 0).unwrap();
+    assert_eq!(base64.len(), 86);
+    assert!(!base64.contains('='));
+    assert_eq!(root.bip85_base85_password(80, 0).unwrap().len(), 80);
+
+    let cases = [
+        (root.bip85_hex(15, 0).unwrap_err(), "invalid entropy length"),
+        (root.bip85_hex(65, 0).unwrap_err(), "invalid entropy length"),
+        (root.bip85_base64_password(19, 0).unwrap_err(), "invalid password length"),
+        (root.bip85_base64_password(87, 0).unwrap_err(), "invalid password length"),
+        (root.bip85_base85_password(9, 0).unwrap_err(), "invalid password length"),
+        (root.bip85_base85_password(81, 0).unwrap_err(), "invalid password length"),
+    ];
+    for (err, message) in cases {
+        assert_eq!(err.kind(), ErrorKind::InvalidEntropy);
+        assert_eq!(err.message(), message);
+    }
+}
+
+fn run_dice<B: Secp256k1Backend>() {
+    let root = root::<B>();
+    let rolls = root.bip85_dice(6, 10, 0).unwrap();
+    assert_eq!(*rolls, [1, 0, 0, 2, 0, 1, 5, 5, 2, 4]);
+
+    for sides in [2, 20, 256, 257, 1000, u32::MAX >> 1] {
+        let rolls = root.bip85_dice(sides, 50, 0).unwrap();
+        assert_eq!(rolls.len(), 50);
+        assert!(rolls.iter().all(|&roll| roll < sides));
+    }
+
+    let err = root.bip85_dice(1, 10, 0).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidEntropy);
+    assert_eq!(err.message(), "invalid number of dice sides");
+    let err = root.bip85_dice(6, 0, 0).unwrap_err();
+    assert_eq!(err.message(), "invalid number of dice rolls");
+}
+
+#[test]
+fn test_bip85() {
+    #[cfg(feature = "k256")]
+    {
+        run_entropy::<K256Backend>();
+        run_drng::<K256Backend>();
+        run_bip39::<K256Backend>();
+        run_wif_and_xprv::<K256Backend>();
+        run_hex_and_passwords::<K256Backend>();
+        run_dice::<K256Backend>();
+    }
+    #[cfg(feature = "secp256k1")]
+    {
+        run_entropy::<Secp256k1FfiBackend>();
+        run_drng::<Secp256k1FfiBackend>();
+        run_bip39::<Secp256k1FfiBackend>();
+        run_wif_and_xprv::<Secp256k1FfiBackend>();
+        run_hex_and_passwords::<Secp256k1FfiBackend>();
+        run_dice::<Secp256k1FfiBackend>();
+    }
+}
@@ -0,0 +1,397 @@
+use bip0032::{
+    Bip48Path, Bip48ScriptType, Change, ChildNumber, CoinType, DerivationPath, ErrorKind,
+    ExtendedPrivateKey, KnownVersion,
+    address::{AddressType, Network},
+    bsms::{BSMS_VERSION, DescriptorRecord, KeyRecord, NO_PATH_RESTRICTIONS, Token},
+    curve::{RecoverableEcdsa, Signer, Verifier, secp256k1::*},
+    multisig::MultisigPolicy,
+};
+
+const SEEDS: [[u8; 32]; 3] = [[0x01; 32], [0x02; 32], [0x03; 32]];
+
+// Fixed records of the accounts `m/48'/0'/0'/2'` of the BIP-0032 test vector 1 and 2 seeds. The
+// signatures and the first address were checked against an independent implementation of the
+// derivation, RFC 6979 message signing and P2WSH addresses.
+const VECTOR_KEY_RECORDS: [&str; 4] = [
+    "BSMS 1.0\n\
+        00\n\
+        [3442193e/48'/0'/0'/2']xpub6E64WfdQwBGz85XhbZryr9gUGUPBgoSu5WV6tJWpzAvgAmpVpdPHkT3XYm9R5J6MeWzvLQoz4q845taC9Q28XutbptxAmg7q8QPkjvTL4oi\n\
+        Signer 1 key\n\
+        IIkrIowFz7JCZBffy7CZmY4nKx/jax4DbMzb80qh45X1bghzVshtsuMdKJljvpCSj8lsBUXpM8j0TZOhkiXUL7A=",
+    "BSMS 1.0\n\
+        a54044308ceac9b7\n\
+        [3442193e/48'/0'/0'/2']xpub6E64WfdQwBGz85XhbZryr9gUGUPBgoSu5WV6tJWpzAvgAmpVpdPHkT3XYm9R5J6MeWzvLQoz4q845taC9Q28XutbptxAmg7q8QPkjvTL4oi\n\
+        Signer 1 key\n\
+        HzJOuL9+kKzDLfuXczVMZFLa38bSNLowWYJs9fwtd8SzR5yxlTL0AF6RxT/JLDl5X2cCwCd+f29U5+LRhjmDRK8=",
+    "BSMS 1.0\n\
+        00\n\
+        [bd16bee5/48'/0'/0'/2']xpub6DwQ4gBCmJZM3TaKogP41tpjuEwnMH2nWEi3PFev37LfsWPvjZrh1GfAG8xvoDYMPWGKG1oBPMCfKpkVbJtUHRaqRdCb6X6o1e9PQTVK88a\n\
+        Signer 2 key\n\
+        IGPc9+V+74ycoTUp02GaVv7RPaSFme5HFcz+TU5PBg6QXDCjv47pv8e8ZmLpAsvvquSaB5qectF77lDANkLx23g=",
+    "BSMS 1.0\n\
+        a54044308ceac9b7\n\
+        [bd16bee5/48'/0'/0'/2']xpub6DwQ4gBCmJZM3TaKogP41tpjuEwnMH2nWEi3PFev37LfsWPvjZrh1GfAG8xvoDYMPWGKG1oBPMCfKpkVbJtUHRaqRdCb6X6o1e9PQTVK88a\n\
+        Signer 2 key\n\
+        H7+WGiZHRhN/TKEXHZH3uU7IiXLv3bsTbAP+bKtgGuP/IiBj+/dN+zEZqdSE2/KfN3kIrK5C2TJ2UT2Ns1UFUis=",
+];
+const VECTOR_DESCRIPTOR_RECORD: &str = "BSMS 1.0\n\
+    wsh(sortedmulti(2,[3442193e/48'/0'/0'/2']xpub6E64WfdQwBGz85XhbZryr9gUGUPBgoSu5WV6tJWpzAvgAmpVpdPHkT3XYm9R5J6MeWzvLQoz4q845taC9Q28XutbptxAmg7q8QPkjvTL4oi/**,[bd16bee5/48'/0'/0'/2']xpub6DwQ4gBCmJZM3TaKogP41tpjuEwnMH2nWEi3PFev37LfsWPvjZrh1GfAG8xvoDYMPWGKG1oBPMCfKpkVbJtUHRaqRdCb6X6o1e9PQTVK88a/**))\n\
+    /0/*,/1/*\n\
+    bc1qlpsgumjm2dlcljqc96c38n6q74jtn88enkr3wrz0rtp9jp6war7s2h4lrs";
+
+fn account_keys<B: Secp256k1Backend>(
+    script_type: Bip48ScriptType,
+) -> Vec<ExtendedPrivateKey<Secp256k1Curve<B>>> {
+    let account = Bip48Path::new(CoinType::TESTNET, 0, script_type);
+    let path = DerivationPath::from(account.account_path().unwrap());
+    SEEDS
+        .iter()
+        .map(|seed| {
+            let master = ExtendedPrivateKey::<Secp256k1Curve<B>>::new(seed).unwrap();
+            master.derive_path(&path).unwrap()
+        })
+        .collect()
+}
+
+fn key_records<B: Secp256k1Backend>(
+    token: Token,
+    keys: &[ExtendedPrivateKey<Secp256k1Curve<B>>],
+) -> Vec<KeyRecord<B>>
+where
+    B::PrivateKey: Signer<RecoverableEcdsa>,
+{
+    keys.iter()
+        .enumerate()
+        .map(|(i, key)| {
+            let version = KnownVersion::Tpub.into_version();
+            KeyRecord::sign(token, key, version, &format!("Signer {i}")).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_token() {
+    assert_eq!("00".parse::<Token>().unwrap(), Token::NoEncryption);
+    assert_eq!(Token::NoEncryption.to_string(), "00");
+    assert!(!Token::NoEncryption.is_encrypted());
+
+    let token = "a54044308ceac9b7".parse::<Token>().unwrap();
+    assert_eq!(token, Token::Bits64([0xa5, 0x40, 0x44, 0x30, 0x8c, 0xea, 0xc9, 0xb7]));
+    assert_eq!(token.to_string(), "a54044308ceac9b7");
+    assert!(token.is_encrypted());
+
+    let token = Token::Bits128([0x0f; 16]);
+    assert_eq!(token.to_string().parse::<Token>().unwrap(), token);
+    assert_eq!(token.as_bytes(), &[0x0f; 16]);
+
+    for (token, message) in [
+        ("0", "invalid token length"),
+        ("01", "invalid token length"),
+        ("a54044308ceac9", "invalid token length"),
+        ("a54044308ceac9zz", "invalid token"),
+        ("+a4044308ceac9b7", "invalid token"),
+    ] {
+        let err = token.parse::<Token>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidRecord);
+        assert_eq!(err.message(), message, "{token}");
+    }
+}
+
+fn run_key_record<B: Secp256k1Backend>()
+where
+    B::PrivateKey: Signer<RecoverableEcdsa>,
+    B::PublicKey: Verifier<RecoverableEcdsa>,
+{
+    let token = Token::Bits64([0xa5; 8]);
+    let keys = account_keys::<B>(Bip48ScriptType::P2wsh);
+    let record = key_records(token, &keys).remove(0);
+    record.verify().unwrap();
+    assert_eq!(record.description(), "Signer 0");
+    assert_eq!(record.key().key(), &keys[0].public_key());
+    assert_eq!(record.key().origin(), &keys[0].origin().unwrap());
+    assert!((31..35).contains(&record.signature()[0]));
+
+    let encoded = record.to_string();
+    let lines = encoded.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], BSMS_VERSION);
+    assert_eq!(lines[1], "a5a5a5a5a5a5a5a5");
+    let fingerprint = const_hex::encode(record.key().origin().fingerprint());
+    assert!(lines[2].starts_with(&format!("[{fingerprint}/48'/1'/0'/2']tpub")));
+    assert_eq!(lines[3], "Signer 0");
+    assert_eq!(lines[4].len(), 88);
+
+    let parsed = encoded.parse::<KeyRecord<B>>().unwrap();
+    parsed.verify().unwrap();
+    assert_eq!(parsed.to_string(), encoded);
+    // a trailing newline and CRLF line endings are accepted
+    let parsed = format!("{}\r\n", encoded.replace('\n', "\r\n"))
+        .parse::<KeyRecord<B>>()
+        .unwrap();
+    assert_eq!(parsed.to_string(), encoded);
+
+    // tampering with the signed lines breaks the signature
+    let tampered = encoded.replace("Signer 0", "Signer 9").parse::<KeyRecord<B>>().unwrap();
+    let err = tampered.verify().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidSignature);
+    let other = key_records(Token::NoEncryption, &keys).remove(0);
+    let tampered = encoded.replace("a5a5a5a5a5a5a5a5", "00").parse::<KeyRecord<B>>().unwrap();
+    assert!(tampered.verify().is_err());
+    assert_eq!(other.to_string().lines().nth(1), Some("00"));
+    other.verify().unwrap();
+
+    // a signature by another key does not verify
+    let foreign = other.to_string().lines().last().unwrap().to_string();
+    let mixed = [&lines[..4].join("\n"), foreign.as_str()]
+        .join("\n")
+        .parse::<KeyRecord<B>>()
+        .unwrap();
+    assert!(mixed.verify().is_err());
+
+    // the header must be of a compressed key
+    let mut uncompressed = lines.clone();
+    let header = format!("G{}", &lines[4][1..]);
+    uncompressed[4] = &header;
+    let err = uncompressed.join("\n").parse::<KeyRecord<B>>().unwrap().verify().unwrap_err();
+    assert_eq!(err.message(), "invalid message signature header");
+}
+
+fn run_key_record_errors<B: Secp256k1Backend>()
+where
+    B::PrivateKey: Signer<RecoverableEcdsa>,
+{
+    let keys = account_keys::<B>(Bip48ScriptType::P2wsh);
+    let version = KnownVersion::Tpub.into_version();
+
+    let err = KeyRecord::sign(Token::NoEncryption, &keys[0], version, &"x".repeat(81)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidRecord);
+    assert_eq!(err.message(), "description too long");
+    assert!(KeyRecord::sign(Token::NoEncryption, &keys[0], version, &"x".repeat(80)).is_ok());
+    let err = KeyRecord::sign(Token::NoEncryption, &keys[0], version, "a\nb").unwrap_err();
+    assert_eq!(err.message(), "description must be a single line");
+    let err = KeyRecord::sign(Token::NoEncryption, &keys[0], KnownVersion::Tprv.into_version(), "")
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidVersion);
+
+    let record = key_records(Token::NoEncryption, &keys).remove(0).to_string();
+    let lines = record.lines().collect::<Vec<_>>();
+    let cases = [
+        (record.replace(BSMS_VERSION, "BSMS 2.0"), "unsupported BSMS version"),
+        (lines[..4].join("\n"), "unexpected number of record lines"),
+        (format!("{record}\nextra"), "unexpected number of record lines"),
+        (record.replace(lines[4], &lines[4][..87]), "invalid signature encoding"),
+        (record.replace(lines[4], &format!("{}!", &lines[4][..87])), "invalid signature encoding"),
+    ];
+    for (record, message) in cases {
+        let err = record.parse::<KeyRecord<B>>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidRecord);
+        assert_eq!(err.message(), message);
+    }
+
+    let err = record
+        .replace(lines[2], lines[2].split(']').nth(1).unwrap())
+        .parse::<KeyRecord<B>>();
+    assert_eq!(err.unwrap_err().kind(), ErrorKind::InvalidOrigin);
+}
+
+fn run_descriptor_record<B: Secp256k1Backend>()
+where
+    B::PrivateKey: Signer<RecoverableEcdsa>,
+    B::PublicKey: Verifier<RecoverableEcdsa>,
+{
+    let token = Token::Bits128([0x42; 16]);
+    for (script_type, address_type, prefix, wrapper) in [
+        (Bip48ScriptType::P2wsh, AddressType::P2wsh, "tb1q", "wsh(sortedmulti(2,"),
+        (Bip48ScriptType::P2shP2wsh, AddressType::P2shP2wsh, "2", "sh(wsh(sortedmulti(2,"),
+    ] {
+        let keys = account_keys::<B>(script_type);
+        let records = key_records(token, &keys);
+        let record =
+            DescriptorRecord::from_key_records(&token, 2, &records, address_type, Network::Testnet)
+                .unwrap();
+        assert!(record.first_address().starts_with(prefix));
+        assert_eq!(record.path_restrictions(), &[Change::External, Change::Internal]);
+        assert!(record.template().starts_with(wrapper));
+        assert_eq!(record.template().matches("/**").count(), 3);
+
+        // the first address is the one of the multisig policy over the account keys
+        let cosigners = keys.iter().map(ExtendedPrivateKey::public_key).collect();
+        let policy = MultisigPolicy::new(2, cosigners, address_type).unwrap();
+        let first = policy.address(Network::Testnet, Change::External, 0).unwrap();
+        assert_eq!(record.first_address(), first.to_string());
+        assert_eq!(
+            record.address(Network::Testnet, Change::Internal, 3).unwrap(),
+            policy.address(Network::Testnet, Change::Internal, 3).unwrap()
+        );
+
+        let encoded = record.to_string();
+        let lines = encoded.lines().collect::<Vec<_>>();
+        assert_eq!(lines, [BSMS_VERSION, &record.template(), "/0/*,/1/*", record.first_address()]);
+
+        let parsed = encoded.parse::<DescriptorRecord<B>>().unwrap();
+        parsed.verify(Network::Testnet).unwrap();
+        assert_eq!(parsed.to_string(), encoded);
+        for key in &keys {
+            assert!(parsed.contains_key(&key.public_key()));
+        }
+        assert!(!parsed.contains_key(
+            &keys[0].public_key().derive_child(ChildNumber::new(0, false).unwrap()).unwrap()
+        ));
+
+        // another network or another first address do not verify
+        let err = parsed.verify(Network::Bitcoin).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidRecord);
+        assert_eq!(err.message(), "first address mismatch");
+        let other = policy.address(Network::Testnet, Change::External, 1).unwrap().to_string();
+        let forged = encoded.replace(record.first_address(), &other);
+        assert!(forged.parse::<DescriptorRecord<B>>().unwrap().verify(Network::Testnet).is_err());
+
+        // without path restrictions the first address is on the external chain
+        let unrestricted = encoded.replace("/0/*,/1/*", NO_PATH_RESTRICTIONS);
+        let parsed = unrestricted.parse::<DescriptorRecord<B>>().unwrap();
+        assert!(parsed.path_restrictions().is_empty());
+        parsed.verify(Network::Testnet).unwrap();
+        assert_eq!(parsed.to_string(), unrestricted);
+    }
+}
+
+fn run_descriptor_record_errors<B: Secp256k1Backend>()
+where
+    B::PrivateKey: Signer<RecoverableEcdsa>,
+    B::PublicKey: Verifier<RecoverableEcdsa>,
+{
+    let token = Token::Bits64([0x01; 8]);
+    let keys = account_keys::<B>(Bip48ScriptType::P2wsh);
+    let records = key_records(token, &keys);
+
+    let err = DescriptorRecord::from_key_records(
+        &Token::Bits64([0x02; 8]),
+        2,
+        &records,
+        AddressType::P2wsh,
+        Network::Testnet,
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidRecord);
+    assert_eq!(err.message(), "key record token mismatch");
+
+    let forged = records[1].to_string().replace("Signer 1", "Signer 7").parse().unwrap();
+    let err = DescriptorRecord::from_key_records(
+        &token,
+        2,
+        &[records[0].clone(), forged],
+        AddressType::P2wsh,
+        Network::Testnet,
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidSignature);
+
+    let err = DescriptorRecord::from_key_records(
+        &token,
+        2,
+        &records,
+        AddressType::P2wpkh,
+        Network::Testnet,
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidScript);
+
+    let record = DescriptorRecord::from_key_records(
+        &token,
+        2,
+        &records,
+        AddressType::P2wsh,
+        Network::Testnet,
+    )
+    .unwrap()
+    .to_string();
+    let template = record.lines().nth(1).unwrap();
+    let first_key = template.trim_start_matches("wsh(sortedmulti(2,").split(',').next().unwrap();
+    let cases = [
+        (record.replace("sortedmulti", "multi"), "unsupported descriptor template"),
+        (record.replacen("/**", "/1/*", 1), "template keys require an origin and `/**`"),
+        (record.replacen("/**", "/0/*", 1), "template keys require an origin and `/**`"),
+        (record.replace("/0/*,/1/*", "/0/*,/2/*"), "invalid path restriction"),
+        (record.replace("/0/*,/1/*", "/0/*;/1/*"), "invalid path restriction"),
+        (
+            record.replace(template, &format!("wpkh({first_key})")),
+            "unsupported descriptor template",
+        ),
+    ];
+    for (record, message) in cases {
+        let err = record.parse::<DescriptorRecord<B>>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidRecord, "{record}");
+        assert_eq!(err.message(), message);
+    }
+}
+
+fn run_vectors<B: Secp256k1Backend>()
+where
+    B::PublicKey: Verifier<RecoverableEcdsa>,
+{
+    let records = VECTOR_KEY_RECORDS
+        .iter()
+        .map(|vector| {
+            let record = vector.parse::<KeyRecord<B>>().unwrap();
+            record.verify().unwrap();
+            assert_eq!(record.to_string(), *vector);
+
+            // the signature covers the description
+            let tampered = vector.replace(" key\n", " key!\n").parse::<KeyRecord<B>>().unwrap();
+            let err = tampered.verify().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidSignature);
+            record
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(records[0].token(), &Token::NoEncryption);
+    assert_eq!(records[1].token(), &Token::Bits64(0xa540_4430_8cea_c9b7_u64.to_be_bytes()));
+    assert_eq!(records[2].description(), "Signer 2 key");
+
+    let descriptor = VECTOR_DESCRIPTOR_RECORD.parse::<DescriptorRecord<B>>().unwrap();
+    descriptor.verify(Network::Bitcoin).unwrap();
+    assert_eq!(descriptor.to_string(), VECTOR_DESCRIPTOR_RECORD);
+    assert_eq!(descriptor.path_restrictions(), [Change::External, Change::Internal]);
+    assert!(records.iter().all(|record| descriptor.contains_key(record.key().key())));
+
+    let unencrypted = [records[0].clone(), records[2].clone()];
+    let built = DescriptorRecord::from_key_records(
+        &Token::NoEncryption,
+        2,
+        &unencrypted,
+        AddressType::P2wsh,
+        Network::Bitcoin,
+    )
+    .unwrap();
+    assert_eq!(built.to_string(), VECTOR_DESCRIPTOR_RECORD);
+}
+
+#[test]
+fn test_bsms_records() {
+    #[cfg(feature = "k256")]
+    {
+        run_key_record::<K256Backend>();
+        run_key_record_errors::<K256Backend>();
+        run_descriptor_record::<K256Backend>();
+        run_descriptor_record_errors::<K256Backend>();
+        run_vectors::<K256Backend>();
+    }
+    #[cfg(feature = "secp256k1")]
+    {
+        run_key_record::<Secp256k1FfiBackend>();
+        run_key_record_errors::<Secp256k1FfiBackend>();
+        run_descriptor_record::<Secp256k1FfiBackend>();
+        run_descriptor_record_errors::<Secp256k1FfiBackend>();
+        run_vectors::<Secp256k1FfiBackend>();
+    }
+}
+
+#[cfg(all(feature = "k256", feature = "secp256k1"))]
+#[test]
+fn test_bsms_backends_agree() {
+    let token = Token::Bits64([0x07; 8]);
+    let k256 = key_records(token, &account_keys::<K256Backend>(Bip48ScriptType::P2wsh));
+    let ffi = key_records(token, &account_keys::<Secp256k1FfiBackend>(Bip48ScriptType::P2wsh));
+    for (k256, ffi) in k256.iter().zip(&ffi) {
+        assert_eq!(k256.to_string(), ffi.to_string());
+    }
+}
@@ -0,0 +1,132 @@
+use bip0032::{
+    DerivationPath, ErrorKind, ExtendedPublicKey,
+    curve::secp256k1::*,
+    descriptor::{Descriptor, checksum},
+};
+
+const XPUB_1: &str = "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL";
+const XPUB_2: &str = "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y";
+const XPUB_3: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+
+type Curve<B> = Secp256k1Curve<B>;
+
+fn derive<B: Secp256k1Backend>(xpub: &str, path: &str) -> ExtendedPublicKey<Curve<B>> {
+    let key = xpub.parse::<ExtendedPublicKey<Curve<B>>>().unwrap();
+    key.derive_path(&path.parse::<DerivationPath>().unwrap()).unwrap()
+}
+
+fn run_roundtrip_case<B: Secp256k1Backend>() {
+    let cases = [
+        // https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki#test-vectors
+        format!("sh(multi(2,[00000000/111'/222]{XPUB_1},{XPUB_2}/0))#tjg09x5t"),
+        format!("pkh([d34db33f/44'/0'/0']{XPUB_1}/1/*)#ml40v0wf"),
+        format!("sh(wpkh({XPUB_2}/1/*))#0p8rm3vl"),
+        format!("tr({XPUB_3}/0/*)#svstklww"),
+        format!(
+            "wsh(sortedmulti(2,[00000000/48'/0'/0'/2']{XPUB_1}/0/*,{XPUB_2}/0/*,{XPUB_3}/0/*))#ahntxw9d"
+        ),
+    ];
+
+    for case in &cases {
+        let descriptor = case.parse::<Descriptor<Curve<B>>>().unwrap();
+        assert_eq!(&descriptor.to_string(), case);
+
+        // the checksum is optional when parsing
+        let (body, _) = case.split_once('#').unwrap();
+        let descriptor = body.parse::<Descriptor<Curve<B>>>().unwrap();
+        assert_eq!(&descriptor.to_string(), case);
+    }
+}
+
+fn run_derive_case<B: Secp256k1Backend>() {
+    let descriptor = format!("pkh([d34db33f/44h/0h/0h]{XPUB_1}/1/*)");
+    let descriptor = descriptor.parse::<Descriptor<Curve<B>>>().unwrap();
+    assert!(descriptor.has_wildcard());
+
+    let keys = descriptor.derive_keys(42).unwrap();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].to_bytes(), derive::<B>(XPUB_1, "m/1/42").to_bytes());
+    assert_eq!(keys[0].origin().unwrap().to_string(), "[d34db33f/44'/0'/0'/1/42]");
+
+    let descriptor = format!("sortedmulti(2,{XPUB_1}/0/*,{XPUB_2}/0/*,{XPUB_3}/0/*)");
+    let descriptor = descriptor.parse::<Descriptor<Curve<B>>>().unwrap();
+    let mut expected = [XPUB_1, XPUB_2, XPUB_3].map(|xpub| derive::<B>(xpub, "m/0/3").to_bytes());
+    expected.sort();
+    let keys = descriptor
+        .derive_keys(3)
+        .unwrap()
+        .iter()
+        .map(|key| key.to_bytes())
+        .collect::<Vec<_>>();
+    assert_eq!(keys, expected);
+
+    // keys without a wildcard ignore the index
+    let descriptor = format!("wpkh({XPUB_2}/0)").parse::<Descriptor<Curve<B>>>().unwrap();
+    assert!(!descriptor.has_wildcard());
+    assert_eq!(
+        descriptor.derive_keys(7).unwrap()[0].to_bytes(),
+        descriptor.derive_keys(8).unwrap()[0].to_bytes()
+    );
+}
+
+fn run_invalid_case<B: Secp256k1Backend>() {
+    let cases = [
+        // checksum mismatch
+        (format!("pkh({XPUB_1}/1/*)#ml40v0wf"), ErrorKind::InvalidDescriptor),
+        // invalid checksum length
+        (format!("pkh({XPUB_1}/1/*)#ml40v0w"), ErrorKind::InvalidDescriptor),
+        // hardened derivation step
+        (format!("pkh({XPUB_1}/1'/*)"), ErrorKind::InvalidDescriptor),
+        // hardened wildcard
+        (format!("pkh({XPUB_1}/1/*')"), ErrorKind::InvalidDescriptor),
+        // wildcard not at the end
+        (format!("pkh({XPUB_1}/*/1)"), ErrorKind::InvalidDescriptor),
+        // threshold larger than the number of keys
+        (format!("wsh(multi(3,{XPUB_1},{XPUB_2}))"), ErrorKind::InvalidDescriptor),
+        // too many keys for bare multisig
+        (
+            format!("multi(1,{XPUB_1},{XPUB_2},{XPUB_3},{XPUB_1}/1)"),
+            ErrorKind::InvalidDescriptor,
+        ),
+        // unsupported descriptor
+        (format!("combo({XPUB_1})"), ErrorKind::InvalidDescriptor),
+        // private keys are not supported
+        (
+            "pkh(xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi)".to_owned(),
+            ErrorKind::InvalidVersion,
+        ),
+        // invalid key origin
+        (format!("pkh([d34db33f/m/0]{XPUB_1})"), ErrorKind::InvalidOrigin),
+    ];
+
+    for (descriptor, kind) in &cases {
+        let err = descriptor.parse::<Descriptor<Curve<B>>>().err().unwrap();
+        assert_eq!(err.kind(), *kind, "{descriptor}");
+    }
+}
+
+fn run_cases_for_all_backends() {
+    #[cfg(feature = "k256")]
+    {
+        run_roundtrip_case::<K256Backend>();
+        run_derive_case::<K256Backend>();
+        run_invalid_case::<K256Backend>();
+    }
+    #[cfg(feature = "secp256k1")]
+    {
+        run_roundtrip_case::<Secp256k1FfiBackend>();
+        run_derive_case::<Secp256k1FfiBackend>();
+        run_invalid_case::<Secp256k1FfiBackend>();
+    }
+}
+
+#[test]
+fn test_descriptors() {
+    run_cases_for_all_backends();
+}
+
+#[test]
+fn test_checksum() {
+    let descriptor = format!("sh(multi(2,[00000000/111'/222]{XPUB_1},{XPUB_2}/0))");
+    assert_eq!(checksum(&descriptor).unwrap(), "tjg09x5t");
+}
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+use bip0032::{
+    AccountDiscovery, Bip44Path, Change, CoinType, DerivationPath, ExtendedPrivateKey,
+    ExtendedPublicKey, KnownVersion, Purpose, curve::secp256k1::*,
+};
+
+type Curve<B> = Secp256k1Curve<B>;
+
+fn used_keys<B: Secp256k1Backend>(
+    master: &ExtendedPrivateKey<Curve<B>>,
+    paths: &[&str],
+) -> HashSet<[u8; 33]> {
+    paths
+        .iter()
+        .map(|path| {
+            let path = path.parse::<DerivationPath>().unwrap();
+            master.derive_path(&path).unwrap().public_key().to_bytes()
+        })
+        .collect()
+}
+
+fn run_discover<B: Secp256k1Backend>() {
+    let master = ExtendedPrivateKey::<Curve<B>>::new(&[0x42; 32]).unwrap();
+    let used = used_keys(
+        &master,
+        &[
+            "m/44'/0'/0'/0/0",
+            "m/84'/0'/0'/0/0",
+            "m/84'/0'/0'/0/1",
+            // within the gap limit of index 1
+            "m/84'/0'/0'/0/21",
+            // beyond the gap limit of index 21
+            "m/84'/0'/0'/0/42",
+            "m/84'/0'/0'/1/3",
+            "m/84'/0'/1'/0/0",
+            // beyond the first unused account 2
+            "m/84'/0'/3'/0/0",
+            // only change addresses do not make an account used
+            "m/49'/0'/0'/1/0",
+        ],
+    );
+
+    let mut queried = 0;
+    let mut usage = |key: &[u8; 33]| {
+        queried += 1;
+        used.contains(key)
+    };
+    let discovery = AccountDiscovery::new(CoinType::BITCOIN);
+    let accounts = discovery
+        .discover(&master, &[Purpose::Bip44, Purpose::Bip49, Purpose::Bip84], &mut usage)
+        .unwrap();
+
+    let summary = accounts
+        .iter()
+        .map(|account| {
+            (
+                account.account().unwrap().to_derivation_path().unwrap().to_string(),
+                account.external().used().to_vec(),
+                account.internal().used().to_vec(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        [
+            ("m/44'/0'/0'".to_string(), vec![0], vec![]),
+            ("m/84'/0'/0'".to_string(), vec![0, 1, 21], vec![3]),
+            ("m/84'/0'/1'".to_string(), vec![0], vec![]),
+        ]
+    );
+    assert_eq!(accounts[1].chain(Change::External).next_unused(), 22);
+    assert_eq!(accounts[1].chain(Change::Internal).next_unused(), 4);
+    assert_eq!(accounts[2].chain(Change::Internal).next_unused(), 0);
+    assert!(accounts.iter().all(|account| account.is_used()));
+
+    // 44'/0': 21 + 20, 44'/1': 20 + 20, 49'/0': 20 + 21, 84'/0': 42 + 24, 84'/1': 21 + 20,
+    // 84'/2': 20 + 20
+    assert_eq!(queried, 41 + 40 + 41 + 66 + 41 + 40);
+}
+
+fn run_scan_account<B: Secp256k1Backend>() {
+    let master = ExtendedPrivateKey::<Curve<B>>::new(&[0x42; 32]).unwrap();
+    let used = used_keys(&master, &["m/84'/0'/0'/0/2", "m/84'/0'/0'/1/0", "m/84'/0'/0'/1/7"]);
+    let mut usage = |key: &[u8; 33]| used.contains(key);
+
+    let path = "m/84'/0'/0'".parse::<DerivationPath>().unwrap();
+    let account = master.derive_path(&path).unwrap().public_key();
+    let discovery = AccountDiscovery::new(CoinType::BITCOIN).with_gap_limit(5);
+    assert_eq!(discovery.gap_limit(), 5);
+
+    let scan = discovery.scan_account(&account, &mut usage).unwrap();
+    assert_eq!(scan.account(), Some(&Bip44Path::new(Purpose::Bip84, CoinType::BITCOIN, 0)));
+    assert_eq!(scan.external().used(), &[2]);
+    assert_eq!(scan.internal().used(), &[0]);
+    assert_eq!(scan.internal().next_unused(), 1);
+
+    // a watch-only account key decoded from an xpub has no known origin
+    let xpub = account.encode_with(KnownVersion::Zpub.into_version()).unwrap().to_string();
+    let account = xpub.parse::<ExtendedPublicKey<Curve<B>>>().unwrap();
+    let watch_only = discovery.scan_account(&account, &mut usage).unwrap();
+    assert_eq!(watch_only.account(), None);
+    assert_eq!(watch_only.external(), scan.external());
+    assert_eq!(watch_only.internal(), scan.internal());
+
+    let chain = discovery.scan_chain(&account, Change::Internal, &mut usage).unwrap();
+    assert_eq!(chain.change(), Change::Internal);
+    assert_eq!(&chain, scan.internal());
+
+    // nothing used: the scan stops after the gap limit
+    let mut queried = 0;
+    let chain = discovery
+        .scan_chain(&account, Change::External, &mut |_: &[u8; 33]| {
+            queried += 1;
+            false
+        })
+        .unwrap();
+    assert!(!chain.is_used());
+    assert_eq!(chain.next_unused(), 0);
+    assert_eq!(queried, 5);
+}
+
+#[test]
+fn test_account_discovery() {
+    #[cfg(feature = "k256")]
+    {
+        run_discover::<K256Backend>();
+        run_scan_account::<K256Backend>();
+    }
+    #[cfg(feature = "secp256k1")]
+    {
+        run_discover::<Secp256k1FfiBackend>();
+        run_scan_account::<Secp256k1FfiBackend>();
+    }
+}
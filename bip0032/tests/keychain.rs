@@ -0,0 +1,130 @@
+use bip0032::{
+    AccountKeychain, ChainState, Change, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey,
+    KnownVersion, OriginKey, curve::secp256k1::*,
+};
+
+type Curve<B> = Secp256k1Curve<B>;
+
+fn account<B: Secp256k1Backend>() -> (ExtendedPrivateKey<Curve<B>>, ExtendedPublicKey<Curve<B>>) {
+    let master = ExtendedPrivateKey::<Curve<B>>::new(&[0x42; 32]).unwrap();
+    let path = "m/84'/0'/0'".parse::<DerivationPath>().unwrap();
+    let account = master.derive_path(&path).unwrap().public_key();
+    (master, account)
+}
+
+fn run_keychain<B: Secp256k1Backend>() {
+    let (master, account) = account::<B>();
+    let mut keychain = AccountKeychain::new(account.clone()).unwrap();
+    assert_eq!(keychain.lookahead(), AccountKeychain::<Curve<B>>::DEFAULT_LOOKAHEAD);
+    assert_eq!(keychain.origin(), account.origin());
+
+    // keys are handed out in order on each chain
+    for expected in 0..3 {
+        let (index, key) = keychain.next_receive().unwrap();
+        assert_eq!(index, expected);
+        let path = format!("m/84'/0'/0'/0/{index}").parse::<DerivationPath>().unwrap();
+        assert_eq!(key, master.derive_path(&path).unwrap().public_key());
+        assert_eq!(key.origin().unwrap().path(), &path);
+    }
+    let (index, key) = keychain.next_change().unwrap();
+    assert_eq!(index, 0);
+    assert_eq!(keychain.lookup(&key.to_bytes()), Some((Change::Internal, 0)));
+    assert_eq!(
+        keychain.chain_state(Change::External),
+        ChainState { next_index: 3, highest_used: None }
+    );
+
+    // reverse lookup covers the lookahead after the next unused index
+    let last = keychain.derive(Change::External, 22).unwrap();
+    assert_eq!(keychain.lookup(&last.to_bytes()), Some((Change::External, 22)));
+    let beyond = keychain.derive(Change::External, 23).unwrap();
+    assert_eq!(keychain.lookup(&beyond.to_bytes()), None);
+    assert_eq!(keychain.lookup(&master.public_key().to_bytes()), None);
+
+    // marking a used key moves the next unused index and the lookahead
+    assert_eq!(keychain.mark_used_key(&last.to_bytes()).unwrap(), Some((Change::External, 22)));
+    assert_eq!(keychain.highest_used(Change::External), Some(22));
+    assert_eq!(keychain.lookup(&beyond.to_bytes()), Some((Change::External, 23)));
+    assert_eq!(keychain.mark_used_key(&master.public_key().to_bytes()).unwrap(), None);
+    assert_eq!(keychain.next_receive().unwrap().0, 23);
+
+    // marking a lower index keeps the highest used index
+    keychain.mark_used(Change::External, 1).unwrap();
+    assert_eq!(keychain.highest_used(Change::External), Some(22));
+    assert_eq!(keychain.highest_used(Change::Internal), None);
+    assert!(keychain.mark_used(Change::Internal, 0x8000_0000).is_err());
+
+    keychain.set_lookahead(30).unwrap();
+    let ahead = keychain.derive(Change::Internal, 30).unwrap();
+    assert_eq!(keychain.lookup(&ahead.to_bytes()), Some((Change::Internal, 30)));
+    let max = AccountKeychain::<Curve<B>>::MAX_LOOKAHEAD;
+    assert!(keychain.set_lookahead(max + 1).is_err());
+    assert_eq!(keychain.lookahead(), 30);
+
+    // marking a far index as used only looks ahead of it, up to the last non-hardened index
+    keychain.mark_used(Change::Internal, 0x7FFF_FFF0).unwrap();
+    let far = keychain.derive(Change::Internal, 0x7FFF_FFFF).unwrap();
+    assert_eq!(keychain.lookup(&far.to_bytes()), Some((Change::Internal, 0x7FFF_FFFF)));
+    let skipped = keychain.derive(Change::Internal, 1_000).unwrap();
+    assert_eq!(keychain.lookup(&skipped.to_bytes()), None);
+    keychain.mark_used(Change::Internal, 0x7FFF_FFFF).unwrap();
+    assert_eq!(keychain.highest_used(Change::Internal), Some(0x7FFF_FFFF));
+}
+
+fn run_keychain_state<B: Secp256k1Backend>() {
+    let (_, account) = account::<B>();
+    let mut keychain = AccountKeychain::new(account.clone()).unwrap();
+    keychain.next_receive().unwrap();
+    keychain.mark_used(Change::Internal, 4).unwrap();
+
+    let state = keychain.state();
+    assert_eq!(state.account.key(), &account);
+    assert_eq!(state.account.version(), KnownVersion::Xpub.into_version());
+    assert_eq!(state.origin, account.origin());
+    assert_eq!(state.internal, ChainState { next_index: 0, highest_used: Some(4) });
+
+    let restored = AccountKeychain::from_state(state).unwrap();
+    assert_eq!(restored.origin(), account.origin());
+    assert_eq!(restored.chain_state(Change::External), keychain.chain_state(Change::External));
+    assert_eq!(restored.chain_state(Change::Internal), keychain.chain_state(Change::Internal));
+    let key = restored.derive(Change::Internal, 24).unwrap();
+    assert_eq!(restored.lookup(&key.to_bytes()), Some((Change::Internal, 24)));
+    assert_eq!(key.origin(), keychain.derive(Change::Internal, 24).unwrap().origin());
+
+    // an origin that does not match the account key is rejected
+    let mut state = keychain.state();
+    state.origin = Some("[00000000/84'/0'/1']".parse().unwrap());
+    assert!(AccountKeychain::from_state(state).is_err());
+
+    // hardened chain indices and oversized lookaheads are rejected
+    let mut state = keychain.state();
+    state.external.next_index = 0x8000_0000;
+    assert!(AccountKeychain::from_state(state).is_err());
+    let mut state = keychain.state();
+    state.internal.highest_used = Some(0x8000_0000);
+    assert!(AccountKeychain::from_state(state).is_err());
+    let mut state = keychain.state();
+    state.lookahead = AccountKeychain::<Curve<B>>::MAX_LOOKAHEAD + 1;
+    assert!(AccountKeychain::from_state(state).is_err());
+
+    // a watch-only keychain from a key origin expression
+    let xpub = account.encode_with(KnownVersion::Xpub.into_version()).unwrap();
+    let origin = account.origin().unwrap();
+    let watch_only = format!("{origin}{xpub}").parse::<OriginKey<Curve<B>>>().unwrap();
+    let keychain = AccountKeychain::new(watch_only.into_key()).unwrap();
+    assert_eq!(keychain.origin(), Some(origin));
+}
+
+#[test]
+fn test_account_keychain() {
+    #[cfg(feature = "k256")]
+    {
+        run_keychain::<K256Backend>();
+        run_keychain_state::<K256Backend>();
+    }
+    #[cfg(feature = "secp256k1")]
+    {
+        run_keychain::<Secp256k1FfiBackend>();
+        run_keychain_state::<Secp256k1FfiBackend>();
+    }
+}
@@ -0,0 +1,265 @@
+use bip0032::{
+    Bip48Path, Bip48ScriptType, Change, CoinType, DerivationPath, ErrorKind, ExtendedPrivateKey,
+    ExtendedPublicKey, KnownVersion,
+    address::{Address, AddressType, Network},
+    curve::secp256k1::*,
+    multisig::{
+        MAX_P2SH_PUBLIC_KEYS, MAX_PUBLIC_KEYS, MultisigPolicy, MultisigScript, sort_public_keys,
+    },
+};
+
+fn key(hex: &str) -> [u8; 33] {
+    const_hex::decode_to_array(hex).unwrap()
+}
+
+#[test]
+fn test_bip67_vectors() {
+    // https://github.com/bitcoin/bips/blob/master/bip-0067.mediawiki#test-vectors
+    let cases: [(&[&str], &[usize], &str); 3] = [
+        (
+            &[
+                "02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8",
+                "02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f",
+            ],
+            &[1, 0],
+            "39bgKC7RFbpoCRbtD5KEdkYKtNyhpsNa3Z",
+        ),
+        (
+            &[
+                "02632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed0",
+                "027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e77",
+                "02e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b404",
+            ],
+            &[0, 1, 2],
+            "3CKHTjBKxCARLzwABMu9yD85kvtm7WnMfH",
+        ),
+        (
+            &[
+                "030000000000000000000000000000000000004141414141414141414141414141",
+                "020000000000000000000000000000000000004141414141414141414141414141",
+                "020000000000000000000000000000000000004141414141414141414141414140",
+                "030000000000000000000000000000000000004141414141414141414141414140",
+            ],
+            &[2, 1, 3, 0],
+            "32V85igBri9zcfBRVupVvwK18NFtS37FuD",
+        ),
+    ];
+
+    for (keys, order, address) in cases {
+        let keys = keys.iter().map(|hex| key(hex)).collect::<Vec<_>>();
+        let script = MultisigScript::sorted(2, keys.clone()).unwrap();
+        let sorted = order.iter().map(|&i| keys[i]).collect::<Vec<_>>();
+        assert_eq!(script.public_keys(), &sorted[..]);
+
+        let mut in_place = keys.clone();
+        sort_public_keys(&mut in_place);
+        assert_eq!(in_place, sorted);
+
+        let p2sh = script.to_address(Network::Bitcoin, AddressType::P2sh).unwrap();
+        assert_eq!(p2sh.to_string(), address);
+        assert_eq!(script.redeem_script(AddressType::P2sh).unwrap(), Some(script.to_bytes()));
+        assert_eq!(script.witness_script(AddressType::P2sh).unwrap(), None);
+    }
+
+    let script = MultisigScript::sorted(
+        2,
+        vec![
+            key("02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8"),
+            key("02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f"),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        const_hex::encode(script.to_bytes()),
+        "522102fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f2102ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f852ae"
+    );
+}
+
+#[test]
+fn test_script_addresses() {
+    // https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki#examples
+    let script =
+        const_hex::decode("210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac")
+            .unwrap();
+    let address = Address::from_script(&script, Network::Bitcoin, AddressType::P2wsh).unwrap();
+    assert_eq!(
+        address.to_string(),
+        "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3"
+    );
+    assert_eq!(
+        const_hex::encode(address.script_pubkey()),
+        "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262"
+    );
+    let address = Address::from_script(&script, Network::Testnet, AddressType::P2wsh).unwrap();
+    assert_eq!(
+        address.to_string(),
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7"
+    );
+
+    // the P2SH-P2WSH redeem script is the P2WSH output script
+    let key = key("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+    let multisig = MultisigScript::new(1, vec![key]).unwrap();
+    let p2wsh = multisig.script_pubkey(AddressType::P2wsh).unwrap();
+    let redeem_script = multisig.redeem_script(AddressType::P2shP2wsh).unwrap().unwrap();
+    assert_eq!(redeem_script, p2wsh);
+    assert_eq!(multisig.witness_script(AddressType::P2shP2wsh).unwrap(), Some(multisig.to_bytes()));
+    assert_eq!(multisig.redeem_script(AddressType::P2wsh).unwrap(), None);
+    assert_eq!(
+        multisig
+            .to_address(Network::Bitcoin, AddressType::P2shP2wsh)
+            .unwrap()
+            .to_string(),
+        Address::from_script(&redeem_script, Network::Bitcoin, AddressType::P2sh)
+            .unwrap()
+            .to_string()
+    );
+
+    let err = Address::from_script(&script, Network::Bitcoin, AddressType::P2wpkh).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidScript);
+    assert_eq!(err.message(), "address type requires a public key");
+    let err = multisig.script_pubkey(AddressType::P2tr).unwrap_err();
+    assert_eq!(err.message(), "address type requires a public key");
+}
+
+#[test]
+fn test_script_limits() {
+    let keys = (0..=MAX_PUBLIC_KEYS as u8).map(|i| [i; 33]).collect::<Vec<_>>();
+
+    let err = MultisigScript::new(1, keys.clone()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidScript);
+    assert_eq!(err.message(), "invalid number of multisig keys");
+    let err = MultisigScript::new(1, Vec::new()).unwrap_err();
+    assert_eq!(err.message(), "invalid number of multisig keys");
+    let err = MultisigScript::new(0, keys[..2].to_vec()).unwrap_err();
+    assert_eq!(err.message(), "invalid multisig threshold");
+    let err = MultisigScript::new(3, keys[..2].to_vec()).unwrap_err();
+    assert_eq!(err.message(), "invalid multisig threshold");
+
+    // counts above 16 are pushed as one byte
+    let script = MultisigScript::new(17, keys[..MAX_PUBLIC_KEYS].to_vec()).unwrap();
+    let bytes = script.to_bytes();
+    assert_eq!(&bytes[..2], &[0x01, 17]);
+    assert_eq!(&bytes[bytes.len() - 3..], &[0x01, 20, 0xAE]);
+    assert!(script.to_address(Network::Bitcoin, AddressType::P2wsh).is_ok());
+
+    // P2SH redeem scripts are limited to 520 bytes, or 15 keys
+    let script = MultisigScript::new(1, keys[..15].to_vec()).unwrap();
+    assert!(script.to_address(Network::Bitcoin, AddressType::P2sh).is_ok());
+    let script = MultisigScript::new(1, keys[..16].to_vec()).unwrap();
+    let err = script.to_address(Network::Bitcoin, AddressType::P2sh).unwrap_err();
+    assert_eq!(err.message(), "redeem script too large");
+    assert!(script.to_address(Network::Bitcoin, AddressType::P2shP2wsh).is_ok());
+}
+
+fn cosigners<B: Secp256k1Backend>(
+    path: &DerivationPath,
+) -> Vec<ExtendedPublicKey<Secp256k1Curve<B>>> {
+    [[0x01; 32], [0x02; 32], [0x03; 32]]
+        .iter()
+        .map(|seed| {
+            let master = ExtendedPrivateKey::<Secp256k1Curve<B>>::new(seed).unwrap();
+            master.derive_path(path).unwrap().public_key()
+        })
+        .collect()
+}
+
+fn run_policy<B: Secp256k1Backend>() {
+    let account = Bip48Path::new(CoinType::BITCOIN, 0, Bip48ScriptType::P2wsh);
+    let path = DerivationPath::from(account.account_path().unwrap());
+    let cosigners = cosigners::<B>(&path);
+    let policy = MultisigPolicy::new(2, cosigners.clone(), AddressType::P2wsh).unwrap();
+    assert_eq!(policy.threshold(), 2);
+    assert_eq!(policy.cosigners().len(), 3);
+
+    let keys = policy.derive_keys(Change::Internal, 7).unwrap();
+    let mut expected = cosigners
+        .iter()
+        .map(|cosigner| {
+            cosigner
+                .derive_path(&"m/1/7".parse::<DerivationPath>().unwrap())
+                .unwrap()
+                .to_bytes()
+        })
+        .collect::<Vec<_>>();
+    expected.sort();
+    assert_eq!(keys.iter().map(ExtendedPublicKey::to_bytes).collect::<Vec<_>>(), expected);
+    for key in &keys {
+        let origin = key.origin().unwrap();
+        assert_eq!(origin.path().to_string(), "m/48'/0'/0'/2'/1/7");
+    }
+
+    // the script does not depend on the order of the cosigners
+    let script = policy.script(Change::Internal, 7).unwrap();
+    assert_eq!(script, MultisigScript::new(2, expected.clone()).unwrap());
+    let mut reversed = cosigners.clone();
+    reversed.reverse();
+    let other = MultisigPolicy::new(2, reversed, AddressType::P2wsh).unwrap();
+    assert_eq!(
+        other.address(Network::Bitcoin, Change::Internal, 7).unwrap(),
+        policy.address(Network::Bitcoin, Change::Internal, 7).unwrap()
+    );
+    let address = policy.address(Network::Bitcoin, Change::Internal, 7).unwrap();
+    assert_eq!(address.address_type(), AddressType::P2wsh);
+    assert_eq!(address.script_pubkey(), script.script_pubkey(AddressType::P2wsh).unwrap());
+    assert_ne!(address, policy.address(Network::Bitcoin, Change::Internal, 8).unwrap());
+
+    let nested = MultisigPolicy::new(2, cosigners.clone(), AddressType::P2shP2wsh).unwrap();
+    let address = nested.address(Network::Testnet, Change::External, 0).unwrap();
+    assert!(address.to_string().starts_with('2'));
+
+    let mut duplicate = cosigners.clone();
+    duplicate[2] = duplicate[0].clone();
+    let err = MultisigPolicy::new(2, duplicate, AddressType::P2wsh).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidScript);
+    assert_eq!(err.message(), "duplicate cosigner key");
+    let err = MultisigPolicy::new(2, cosigners.clone(), AddressType::P2wpkh).unwrap_err();
+    assert_eq!(err.message(), "address type requires a public key");
+    let err = MultisigPolicy::new(4, cosigners, AddressType::P2sh).unwrap_err();
+    assert_eq!(err.message(), "invalid multisig threshold");
+
+    // P2SH redeem scripts fit at most 15 keys
+    let many = (1..=MAX_P2SH_PUBLIC_KEYS as u8 + 1)
+        .map(|i| {
+            let master = ExtendedPrivateKey::<Secp256k1Curve<B>>::new(&[i; 32]).unwrap();
+            master.derive_path(&path).unwrap().public_key()
+        })
+        .collect::<Vec<_>>();
+    let err = MultisigPolicy::new(2, many.clone(), AddressType::P2sh).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidScript);
+    assert_eq!(err.message(), "too many keys for a P2SH script");
+    MultisigPolicy::new(2, many[1..].to_vec(), AddressType::P2sh).unwrap();
+    MultisigPolicy::new(2, many, AddressType::P2wsh).unwrap();
+}
+
+fn run_script_types<B: Secp256k1Backend>() {
+    let key = cosigners::<B>(&DerivationPath::default()).remove(0);
+
+    // single-key addresses do not accept script types
+    let err = key.to_address(Network::Bitcoin, AddressType::P2wsh).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidScript);
+    assert_eq!(err.message(), "address type requires a script");
+    assert!(key.to_default_address(KnownVersion::ZpubWsh).is_err());
+
+    for (version, script_type) in [
+        (KnownVersion::ZpubWsh, Bip48ScriptType::P2wsh),
+        (KnownVersion::YpubShWsh, Bip48ScriptType::P2shP2wsh),
+    ] {
+        assert_eq!(Bip48ScriptType::from_known_version(version), Some(script_type));
+        assert_eq!(version.info().script_type(), script_type.script_type());
+        assert!(script_type.address_type().is_script());
+    }
+}
+
+#[test]
+fn test_multisig_policy() {
+    #[cfg(feature = "k256")]
+    {
+        run_policy::<K256Backend>();
+        run_script_types::<K256Backend>();
+    }
+    #[cfg(feature = "secp256k1")]
+    {
+        run_policy::<Secp256k1FfiBackend>();
+        run_script_types::<Secp256k1FfiBackend>();
+    }
+}
@@ -0,0 +1,261 @@
+use bip0032::{
+    AccountKeychain, Change, ChildNumber, DerivationPath, ExposedPrivateKey, ExtendedPrivateKey,
+    ExtendedPublicKey, HardenedDerivationPath, KeyOrigin, KeychainState, KnownVersion, OriginKey,
+    Version, VersionedPublicKey, curve::secp256k1::*,
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+type Curve = Secp256k1Curve<K256Backend>;
+
+// BIP-0032 test vector 1
+const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+const XPUB: &str = "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw";
+const XPRV: &str = "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7";
+
+fn to_cbor<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    ciborium::into_writer(value, &mut out).unwrap();
+    out
+}
+
+fn from_cbor<T: DeserializeOwned>(bytes: &[u8]) -> T {
+    ciborium::from_reader(bytes).unwrap()
+}
+
+fn cbor_bytes(bytes: &[u8]) -> ciborium::Value {
+    ciborium::Value::Bytes(bytes.to_vec())
+}
+
+fn derive(path: &str) -> ExtendedPrivateKey<Curve> {
+    let seed = const_hex::decode(SEED).unwrap();
+    let master = ExtendedPrivateKey::<Curve>::new(&seed).unwrap();
+    master.derive_path(&path.parse::<DerivationPath>().unwrap()).unwrap()
+}
+
+#[test]
+fn test_serde_path() {
+    let path: DerivationPath = "m/84'/0'/0'/1/5".parse().unwrap();
+    let json = serde_json::to_string(&path).unwrap();
+    assert_eq!(json, "\"m/84'/0'/0'/1/5\"");
+    assert_eq!(serde_json::from_str::<DerivationPath>(&json).unwrap(), path);
+
+    let cbor = to_cbor(&path);
+    // byte string header + 5 children of 4 bytes
+    assert_eq!(cbor.len(), 1 + 20);
+    assert_eq!(&cbor[1..5], &[0x80, 0x00, 0x00, 0x54]);
+    assert_eq!(from_cbor::<DerivationPath>(&cbor), path);
+
+    let root = DerivationPath::default();
+    assert_eq!(serde_json::to_string(&root).unwrap(), "\"m\"");
+    assert_eq!(from_cbor::<DerivationPath>(&to_cbor(&root)), root);
+
+    let hardened: HardenedDerivationPath = "m/48'/0'/0'/2'".parse().unwrap();
+    let json = serde_json::to_string(&hardened).unwrap();
+    assert_eq!(json, "\"m/48'/0'/0'/2'\"");
+    assert_eq!(serde_json::from_str::<HardenedDerivationPath>(&json).unwrap(), hardened);
+    assert_eq!(from_cbor::<HardenedDerivationPath>(&to_cbor(&hardened)), hardened);
+
+    // non-hardened children are rejected by both encodings
+    assert!(serde_json::from_str::<HardenedDerivationPath>("\"m/48'/0\"").is_err());
+    let cbor = to_cbor(&path);
+    assert!(ciborium::from_reader::<HardenedDerivationPath, _>(&cbor[..]).is_err());
+
+    // binary paths must be a multiple of 4 bytes
+    let cbor = to_cbor(&cbor_bytes(&[0x80, 0x00, 0x00]));
+    assert!(ciborium::from_reader::<DerivationPath, _>(&cbor[..]).is_err());
+}
+
+#[test]
+fn test_serde_child_number() {
+    let hardened = ChildNumber::new(44, true).unwrap();
+    let json = serde_json::to_string(&hardened).unwrap();
+    assert_eq!(json, "\"44'\"");
+    assert_eq!(serde_json::from_str::<ChildNumber>(&json).unwrap(), hardened);
+
+    let cbor = to_cbor(&hardened);
+    assert_eq!(from_cbor::<u32>(&cbor), 0x8000_002C);
+    assert_eq!(from_cbor::<ChildNumber>(&cbor), hardened);
+
+    let normal = ChildNumber::new(7, false).unwrap();
+    assert_eq!(serde_json::to_string(&normal).unwrap(), "\"7\"");
+    assert_eq!(from_cbor::<ChildNumber>(&to_cbor(&normal)), normal);
+
+    assert!(serde_json::from_str::<ChildNumber>("\"x\"").is_err());
+}
+
+#[test]
+fn test_serde_version() {
+    let json = serde_json::to_string(&KnownVersion::Zpub).unwrap();
+    assert_eq!(json, "\"zpub\"");
+    assert_eq!(serde_json::from_str::<KnownVersion>(&json).unwrap(), KnownVersion::Zpub);
+    let cbor = to_cbor(&KnownVersion::Zpub);
+    assert_eq!(&cbor[1..], &[0x04, 0xB2, 0x47, 0x46]);
+    assert_eq!(from_cbor::<KnownVersion>(&cbor), KnownVersion::Zpub);
+    assert!(serde_json::from_str::<KnownVersion>("\"abcd\"").is_err());
+
+    let cases = [
+        (Version::XPRV, "\"xprv\""),
+        (Version::public(0x0102_0304), "\"public:0x01020304\""),
+        (Version::private(0x0102_0304), "\"private:0x01020304\""),
+    ];
+    for (version, expected) in cases {
+        let json = serde_json::to_string(&version).unwrap();
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_str::<Version>(&json).unwrap(), version);
+
+        let cbor = to_cbor(&version);
+        assert_eq!(cbor.len(), 1 + 5);
+        assert_eq!(cbor[1], u8::from(version.is_private()));
+        assert_eq!(from_cbor::<Version>(&cbor), version);
+    }
+
+    assert!(serde_json::from_str::<Version>("\"public:01020304\"").is_err());
+    assert!(serde_json::from_str::<Version>("\"secret:0x01020304\"").is_err());
+    let cbor = to_cbor(&cbor_bytes(&[2, 0x04, 0x88, 0xB2, 0x1E]));
+    assert!(ciborium::from_reader::<Version, _>(&cbor[..]).is_err());
+}
+
+#[test]
+fn test_serde_extended_public_key() {
+    let key = derive("m/0H").public_key();
+    assert_eq!(key.encode_with(Version::XPUB).unwrap().to_string(), XPUB);
+
+    let json = serde_json::to_string(&key).unwrap();
+    assert_eq!(json, format!("\"{XPUB}\""));
+    let decoded: ExtendedPublicKey<Curve> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.encode_with(Version::XPUB).unwrap().to_string(), XPUB);
+
+    let cbor = to_cbor(&key);
+    // byte string header (2 bytes for 78-byte length) + 78-byte payload
+    assert_eq!(cbor.len(), 2 + 78);
+    let decoded: ExtendedPublicKey<Curve> = from_cbor(&cbor);
+    assert_eq!(decoded.encode_with(Version::XPUB).unwrap().to_string(), XPUB);
+
+    // private keys are not accepted as public keys
+    let json = format!("\"{XPRV}\"");
+    assert!(serde_json::from_str::<ExtendedPublicKey<Curve>>(&json).is_err());
+}
+
+#[test]
+fn test_serde_versioned_public_key() {
+    let key = derive("m/0H").public_key();
+    let tpub = key.encode_with(Version::TPUB).unwrap().to_string();
+    assert!(tpub.starts_with("tpub"));
+    let versioned = tpub.parse::<VersionedPublicKey<Curve>>().unwrap();
+    assert_eq!(versioned.version(), Version::TPUB);
+
+    let json = serde_json::to_string(&versioned).unwrap();
+    assert_eq!(json, format!("\"{tpub}\""));
+    let decoded: VersionedPublicKey<Curve> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.version(), Version::TPUB);
+    assert_eq!(decoded.key(), &key);
+    assert_eq!(decoded.to_string(), tpub);
+
+    let cbor = to_cbor(&versioned);
+    assert_eq!(cbor.len(), 2 + 78);
+    let decoded: VersionedPublicKey<Curve> = from_cbor(&cbor);
+    assert_eq!(decoded.to_string(), tpub);
+
+    // plain keys are versioned as `xpub`
+    assert_eq!(VersionedPublicKey::from(key.clone()).to_string(), XPUB);
+
+    // private keys are neither accepted nor created
+    let json = format!("\"{XPRV}\"");
+    assert!(serde_json::from_str::<VersionedPublicKey<Curve>>(&json).is_err());
+    assert!(VersionedPublicKey::new(key, Version::TPRV).is_err());
+}
+
+#[test]
+fn test_serde_exposed_private_key() {
+    let key = derive("m/0H");
+
+    let json = serde_json::to_string(&ExposedPrivateKey::new(key.clone())).unwrap();
+    assert_eq!(json, format!("\"{XPRV}\""));
+    let decoded: ExposedPrivateKey<Curve> = serde_json::from_str(&json).unwrap();
+    let decoded = decoded.into_inner();
+    assert_eq!(decoded.encode_with(Version::XPRV).unwrap().to_string(), XPRV);
+
+    let cbor = to_cbor(&ExposedPrivateKey::from(key));
+    assert_eq!(cbor.len(), 2 + 78);
+    let decoded: ExposedPrivateKey<Curve> = from_cbor(&cbor);
+    assert_eq!(decoded.as_inner().encode_with(Version::XPRV).unwrap().to_string(), XPRV);
+
+    // public keys are not accepted as private keys
+    let json = format!("\"{XPUB}\"");
+    assert!(serde_json::from_str::<ExposedPrivateKey<Curve>>(&json).is_err());
+}
+
+#[derive(Serialize, Deserialize)]
+struct WalletMetadata {
+    version: KnownVersion,
+    path: HardenedDerivationPath,
+    account: ExtendedPublicKey<Curve>,
+}
+
+#[test]
+fn test_serde_wallet_metadata() {
+    let metadata = WalletMetadata {
+        version: KnownVersion::Xpub,
+        path: "m/0'".parse().unwrap(),
+        account: derive("m/0H").public_key(),
+    };
+
+    let json = serde_json::to_string(&metadata).unwrap();
+    assert_eq!(json, format!("{{\"version\":\"xpub\",\"path\":\"m/0'\",\"account\":\"{XPUB}\"}}"));
+    let decoded: WalletMetadata = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.version, metadata.version);
+    assert_eq!(decoded.path, metadata.path);
+    assert_eq!(decoded.account.encode_with(Version::XPUB).unwrap().to_string(), XPUB);
+
+    let decoded: WalletMetadata = from_cbor(&to_cbor(&metadata));
+    assert_eq!(decoded.version, metadata.version);
+    assert_eq!(decoded.path, metadata.path);
+    assert_eq!(decoded.account.encode_with(Version::XPUB).unwrap().to_string(), XPUB);
+}
+
+#[test]
+fn test_serde_keychain_state() {
+    let origin: KeyOrigin = "[3442193e/84'/0'/0']".parse().unwrap();
+    let json = serde_json::to_string(&origin).unwrap();
+    assert_eq!(json, "\"[3442193e/84'/0'/0']\"");
+    assert_eq!(serde_json::from_str::<KeyOrigin>(&json).unwrap(), origin);
+    let cbor = to_cbor(&origin);
+    // byte string header + fingerprint + 3 children of 4 bytes
+    assert_eq!(cbor.len(), 1 + 4 + 12);
+    assert_eq!(&cbor[1..5], &[0x34, 0x42, 0x19, 0x3e]);
+    assert_eq!(from_cbor::<KeyOrigin>(&cbor), origin);
+    assert!(ciborium::from_reader::<KeyOrigin, _>(&to_cbor(&cbor_bytes(&[0x34]))[..]).is_err());
+
+    let account = derive("m/84'/0'/0'").public_key();
+    let mut keychain = AccountKeychain::new(account.clone()).unwrap();
+    keychain.next_receive().unwrap();
+    keychain.mark_used(Change::Internal, 2).unwrap();
+    let state = keychain.state();
+
+    let json = serde_json::to_value(&state).unwrap();
+    assert_eq!(json["origin"], serde_json::Value::String(account.origin().unwrap().to_string()));
+    assert_eq!(json["external"]["next_index"], 1);
+    assert_eq!(json["internal"]["highest_used"], 2);
+    let restored = serde_json::from_value::<KeychainState<Curve>>(json).unwrap();
+    assert_eq!(restored.account.key(), &account);
+    assert_eq!(restored.account.version(), Version::XPUB);
+    assert_eq!(restored.origin, account.origin());
+
+    let restored = from_cbor::<KeychainState<Curve>>(&to_cbor(&state));
+    let keychain = AccountKeychain::from_state(restored).unwrap();
+    assert_eq!(keychain.origin(), account.origin());
+    assert_eq!(keychain.chain_state(Change::Internal), state.internal);
+
+    // a testnet keychain keeps its `tpub`
+    let origin = account.origin().unwrap();
+    let tpub = account.encode_with(Version::TPUB).unwrap();
+    let account = format!("{origin}{tpub}").parse::<OriginKey<Curve>>().unwrap();
+    let keychain = AccountKeychain::new(account).unwrap();
+    assert_eq!(keychain.version(), Version::TPUB);
+    let json = serde_json::to_value(keychain.state()).unwrap();
+    assert_eq!(json["account"], serde_json::Value::String(tpub.to_string()));
+    let restored = serde_json::from_value::<KeychainState<Curve>>(json).unwrap();
+    let keychain = AccountKeychain::from_state(restored).unwrap();
+    assert_eq!(keychain.version(), Version::TPUB);
+    assert_eq!(keychain.origin(), Some(origin));
+}
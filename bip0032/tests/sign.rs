@@ -0,0 +1,245 @@
+use bip0032::{
+    DerivationPath, ErrorKind, ExtendedPrivateKey,
+    curve::{secp256k1::*, *},
+};
+use sha2::{Digest, Sha256};
+
+fn decode_hex<const N: usize>(value: &str) -> [u8; N] {
+    let bytes = const_hex::decode(value).expect("hex decode failed");
+    bytes.try_into().expect("unexpected hex length")
+}
+
+fn run_secp256k1_vectors<B: Secp256k1Backend>()
+where
+    B::PrivateKey: Signer<Ecdsa, Error = CurveError> + SchnorrSigner<Error = CurveError>,
+    B::PublicKey: Verifier<Ecdsa, Error = CurveError> + Verifier<Schnorr, Error = CurveError>,
+{
+    // RFC 6979 deterministic ECDSA, as used by Bitcoin Core and Trezor
+    let key = <B::PrivateKey as CurvePrivateKey>::from_bytes(&decode_hex(
+        "0000000000000000000000000000000000000000000000000000000000000001",
+    ))
+    .ok()
+    .expect("valid private key");
+    let digest: [u8; 32] = Sha256::digest(b"Satoshi Nakamoto").into();
+    let signature = Signer::<Ecdsa>::sign(&key, &digest).unwrap();
+    assert_eq!(
+        signature,
+        decode_hex::<64>(
+            "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8\
+             2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"
+        )
+    );
+    Verifier::<Ecdsa>::verify(&key.to_public(), &digest, &signature).unwrap();
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv (index 0)
+    let key = <B::PrivateKey as CurvePrivateKey>::from_bytes(&decode_hex(
+        "0000000000000000000000000000000000000000000000000000000000000003",
+    ))
+    .ok()
+    .expect("valid private key");
+    let message = [0u8; 32];
+    let signature = Signer::<Schnorr>::sign(&key, &message).unwrap();
+    assert_eq!(
+        signature,
+        decode_hex::<64>(
+            "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215\
+             25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0"
+        )
+    );
+    Verifier::<Schnorr>::verify(&key.to_public(), &message, &signature).unwrap();
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv (index 1)
+    let key = <B::PrivateKey as CurvePrivateKey>::from_bytes(&decode_hex(
+        "b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfef",
+    ))
+    .ok()
+    .expect("valid private key");
+    let message =
+        decode_hex::<32>("243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89");
+    let aux_rand =
+        decode_hex::<32>("0000000000000000000000000000000000000000000000000000000000000001");
+    let signature = key.sign_with_aux_rand(&message, &aux_rand).unwrap();
+    assert_eq!(
+        signature,
+        decode_hex::<64>(
+            "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de3341\
+             8906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a"
+        )
+    );
+    Verifier::<Schnorr>::verify(&key.to_public(), &message, &signature).unwrap();
+    // the deterministic variant uses all-zero auxiliary randomness
+    assert_ne!(Signer::<Schnorr>::sign(&key, &message).unwrap(), signature);
+    assert_eq!(
+        Signer::<Schnorr>::sign(&key, &message).unwrap(),
+        key.sign_with_aux_rand(&message, &[0u8; 32]).unwrap()
+    );
+}
+
+fn run_recoverable_ecdsa<B: Secp256k1Backend>() -> [u8; 65]
+where
+    B::PrivateKey: Signer<Ecdsa, Error = CurveError> + Signer<RecoverableEcdsa, Error = CurveError>,
+    B::PublicKey: Verifier<RecoverableEcdsa, Error = CurveError>,
+{
+    let key = <B::PrivateKey as CurvePrivateKey>::from_bytes(&decode_hex(
+        "0000000000000000000000000000000000000000000000000000000000000001",
+    ))
+    .ok()
+    .expect("valid private key");
+    let other = <B::PrivateKey as CurvePrivateKey>::from_bytes(&decode_hex(
+        "0000000000000000000000000000000000000000000000000000000000000002",
+    ))
+    .ok()
+    .expect("valid private key");
+    let digest: [u8; 32] = Sha256::digest(b"Satoshi Nakamoto").into();
+
+    // the same deterministic signature as the non-recoverable form
+    let signature = Signer::<RecoverableEcdsa>::sign(&key, &digest).unwrap();
+    assert_eq!(signature[..64], Signer::<Ecdsa>::sign(&key, &digest).unwrap());
+    assert!(signature[64] < 4);
+    Verifier::<RecoverableEcdsa>::verify(&key.to_public(), &digest, &signature).unwrap();
+
+    // the recovered key must be the verifying key
+    assert!(Verifier::<RecoverableEcdsa>::verify(&other.to_public(), &digest, &signature).is_err());
+    let mut wrong_id = signature;
+    wrong_id[64] ^= 1;
+    assert!(Verifier::<RecoverableEcdsa>::verify(&key.to_public(), &digest, &wrong_id).is_err());
+    let mut invalid_id = signature;
+    invalid_id[64] = 4;
+    assert!(Verifier::<RecoverableEcdsa>::verify(&key.to_public(), &digest, &invalid_id).is_err());
+
+    signature
+}
+
+#[test]
+fn test_secp256k1_recoverable_ecdsa() {
+    #[cfg(feature = "k256")]
+    #[cfg_attr(not(feature = "secp256k1"), allow(unused_variables))]
+    let k256 = run_recoverable_ecdsa::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    #[cfg_attr(not(feature = "k256"), allow(unused_variables))]
+    let ffi = run_recoverable_ecdsa::<Secp256k1FfiBackend>();
+
+    #[cfg(all(feature = "k256", feature = "secp256k1"))]
+    assert_eq!(k256, ffi);
+}
+
+#[test]
+fn test_secp256k1_vectors() {
+    #[cfg(feature = "k256")]
+    run_secp256k1_vectors::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_secp256k1_vectors::<Secp256k1FfiBackend>();
+}
+
+fn run_secp256k1_extended_key_case<B: Secp256k1Backend>() -> ([u8; 64], [u8; 64])
+where
+    B::PrivateKey: Signer<Ecdsa> + SchnorrSigner,
+    B::PublicKey: Verifier<Ecdsa> + Verifier<Schnorr>,
+{
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::<Secp256k1Curve<B>>::new(&seed).unwrap();
+    let key = master
+        .derive_path(&"m/86'/0'/0'/0/1".parse::<DerivationPath>().unwrap())
+        .unwrap();
+    let public = key.public_key();
+    let other = master.public_key();
+
+    let digest: [u8; 32] = Sha256::digest(b"bip0032").into();
+    let ecdsa = key.sign::<Ecdsa>(&digest).unwrap();
+    public.verify::<Ecdsa>(&digest, &ecdsa).unwrap();
+    let err = other.verify::<Ecdsa>(&digest, &ecdsa).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidSignature);
+
+    let schnorr = key.sign::<Schnorr>(b"bip0032").unwrap();
+    public.verify::<Schnorr>(b"bip0032", &schnorr).unwrap();
+    let err = public.verify::<Schnorr>(b"bip0033", &schnorr).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidSignature);
+
+    let randomized = key.sign_schnorr_with_aux_rand(b"bip0032", &[0x42; 32]).unwrap();
+    assert_ne!(randomized, schnorr);
+    public.verify::<Schnorr>(b"bip0032", &randomized).unwrap();
+
+    (ecdsa, schnorr)
+}
+
+#[test]
+fn test_secp256k1_extended_key_signing() {
+    #[cfg(feature = "k256")]
+    #[cfg_attr(not(feature = "secp256k1"), allow(unused_variables))]
+    let k256 = run_secp256k1_extended_key_case::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    #[cfg_attr(not(feature = "k256"), allow(unused_variables))]
+    let ffi = run_secp256k1_extended_key_case::<Secp256k1FfiBackend>();
+
+    // both backends produce the same deterministic signatures
+    #[cfg(all(feature = "k256", feature = "secp256k1"))]
+    assert_eq!(k256, ffi);
+}
+
+#[cfg(feature = "p256")]
+#[test]
+fn test_nist256p1_signing() {
+    use bip0032::slip10::*;
+    use nist256p1::{Nist256p1Curve, P256Backend};
+
+    // RFC 6979 A.2.5, SHA-256 with message "sample"
+    let key = <<Nist256p1Curve<P256Backend> as Curve>::PrivateKey as CurvePrivateKey>::from_bytes(
+        &decode_hex("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721"),
+    )
+    .unwrap();
+    let digest: [u8; 32] = Sha256::digest(b"sample").into();
+    let signature = Signer::<Ecdsa>::sign(&key, &digest).unwrap();
+    assert_eq!(
+        signature,
+        decode_hex::<64>(
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716\
+             f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+        )
+    );
+
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master =
+        <ExtendedPrivateKey<Nist256p1Curve<P256Backend>> as Slip10MasterKey>::new_slip10(&seed)
+            .unwrap();
+    let path = "m/0'/1".parse::<DerivationPath>().unwrap();
+    let key = Slip10NonHardenedDerivation::derive_slip10_path(&master, &path).unwrap();
+    let signature = key.sign::<Ecdsa>(&digest).unwrap();
+    key.public_key().verify::<Ecdsa>(&digest, &signature).unwrap();
+    let err = master.public_key().verify::<Ecdsa>(&digest, &signature).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidSignature);
+}
+
+#[cfg(feature = "ed25519-dalek")]
+#[test]
+fn test_ed25519_signing() {
+    use bip0032::{HardenedDerivationPath, slip10::*};
+    use ed25519::{Ed25519Curve, Ed25519DalekBackend};
+
+    // RFC 8032 7.1, TEST 1
+    let key =
+        <<Ed25519Curve<Ed25519DalekBackend> as Curve>::PrivateKey as CurvePrivateKey>::from_bytes(
+            &decode_hex("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"),
+        )
+        .unwrap();
+    let signature = Signer::<Eddsa>::sign(&key, b"").unwrap();
+    assert_eq!(
+        signature,
+        decode_hex::<64>(
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555\
+             fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        )
+    );
+
+    let seed = const_hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master =
+        <ExtendedPrivateKey<Ed25519Curve<Ed25519DalekBackend>> as Slip10MasterKey>::new_slip10(
+            &seed,
+        )
+        .unwrap();
+    let path = "m/44'/501'/0'".parse::<HardenedDerivationPath>().unwrap();
+    let key = Slip10HardenedOnlyDerivation::derive_slip10_path(&master, &path).unwrap();
+    let signature = key.sign::<Eddsa>(b"message").unwrap();
+    key.public_key().verify::<Eddsa>(b"message", &signature).unwrap();
+    let err = key.public_key().verify::<Eddsa>(b"massage", &signature).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidSignature);
+}
@@ -1,7 +1,12 @@
 #![cfg(feature = "slip10")]
 #![allow(dead_code)]
 
-use bip0032::{DerivationPath, ExtendedPrivateKey, HardenedDerivationPath, curve::*, slip10::*};
+use core::num::NonZeroUsize;
+
+use bip0032::{
+    DerivationCache, DerivationPath, ExtendedPrivateKey, HardenedDerivationPath, curve::*,
+    slip10::*,
+};
 
 pub struct Case {
     pub path: &'static str,
@@ -30,6 +35,12 @@ where
     let path = case.path.parse::<HardenedDerivationPath>().unwrap();
     let derived = Slip10HardenedOnlyDerivation::derive_slip10_path(&master, &path).unwrap();
 
+    let mut cache = DerivationCache::new(master, NonZeroUsize::new(2).unwrap());
+    let cached = cache.derive_slip10_hardened_path(&path).unwrap();
+    assert_eq!(cached.chain_code(), derived.chain_code());
+    assert_eq!(cached.to_bytes().as_ref(), derived.to_bytes().as_ref());
+
+    assert_eq!(derived.origin().unwrap().path(), &DerivationPath::from(path));
     assert_eq!(derived.parent_fingerprint(), decode_hex(case.fingerprint));
     assert_eq!(derived.chain_code(), decode_hex(case.chain_code));
 
@@ -51,6 +62,12 @@ where
     let path = case.path.parse::<DerivationPath>().unwrap();
     let derived = Slip10NonHardenedDerivation::derive_slip10_path(&master, &path).unwrap();
 
+    let mut cache = DerivationCache::new(master, NonZeroUsize::new(2).unwrap());
+    let cached = cache.derive_slip10_path(&path).unwrap();
+    assert_eq!(cached.chain_code(), derived.chain_code());
+    assert_eq!(cached.to_bytes().as_ref(), derived.to_bytes().as_ref());
+
+    assert_eq!(derived.origin().unwrap().path(), &path);
     assert_eq!(derived.parent_fingerprint(), decode_hex(case.fingerprint));
     assert_eq!(derived.chain_code(), decode_hex(case.chain_code));
 
@@ -73,6 +90,13 @@ where
     let path = case.path.parse::<DerivationPath>().unwrap();
     let derived = Slip10NonHardenedDerivation::derive_slip10_path(&master_public, &path).unwrap();
 
+    let mut cache = DerivationCache::new(master_public, NonZeroUsize::new(2).unwrap());
+    let cached = cache.derive_slip10_path(&path).unwrap();
+    assert_eq!(cached.chain_code(), derived.chain_code());
+    assert_eq!(cached.to_bytes(), derived.to_bytes());
+
+    assert_eq!(derived.origin().unwrap().path(), &path);
+    assert_eq!(derived.origin().unwrap().fingerprint(), master.fingerprint());
     assert_eq!(derived.parent_fingerprint(), decode_hex(case.fingerprint));
     assert_eq!(derived.chain_code(), decode_hex(case.chain_code));
 
@@ -0,0 +1,150 @@
+use bip0032::{
+    DerivationPath, ErrorKind, ExtendedPrivateKey,
+    curve::{ed25519::*, nist256p1::*},
+    slip10::{Slip10HardenedOnlyDerivation, Slip10MasterKey, Slip10NonHardenedDerivation},
+    slip13::Identity,
+};
+
+fn base64_decode(encoded: &str) -> Vec<u8> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = Vec::new();
+    let (mut acc, mut bits) = (0u32, 0);
+    for c in encoded.bytes().filter(|&c| c != b'=') {
+        acc = (acc << 6) | ALPHABET.iter().position(|&a| a == c).unwrap() as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    out
+}
+
+/// Splits an SSH key blob into its length-prefixed strings.
+fn ssh_strings(mut blob: &[u8]) -> Vec<Vec<u8>> {
+    let mut strings = Vec::new();
+    while !blob.is_empty() {
+        let len = u32::from_be_bytes(blob[..4].try_into().unwrap()) as usize;
+        strings.push(blob[4..4 + len].to_vec());
+        blob = &blob[4 + len..];
+    }
+    strings
+}
+
+#[test]
+fn test_slip13_vector() {
+    let identity = "https://satoshi@bitcoin.org/login".parse::<Identity>().unwrap();
+    assert_eq!(
+        const_hex::encode(identity.hash()),
+        "d0e2389d4c8394a9f3e32de01104bf6e8db2d9e2bb0905d60fffa5a18fd696db"
+    );
+
+    // m/13'/2637750992'/2845082444'/3761103859'/4005495825' with the hardening bits included
+    let path = DerivationPath::from(identity.derivation_path());
+    let indices = path.children().iter().map(|&child| u32::from(child)).collect::<Vec<_>>();
+    assert_eq!(indices, [0x8000_000D, 2637750992, 2845082444, 3761103859, 4005495825]);
+    assert_eq!(
+        identity.derivation_path().to_string(),
+        "m/13'/490267344'/697598796'/1613620211'/1858012177'"
+    );
+
+    // the index is part of the hash
+    let other = identity.clone().with_index(1);
+    assert_ne!(other.hash(), identity.hash());
+    assert_ne!(other.derivation_path(), identity.derivation_path());
+}
+
+#[test]
+fn test_identity_uri() {
+    let identity = "https://satoshi@bitcoin.org:443/login".parse::<Identity>().unwrap();
+    assert_eq!(identity.proto(), Some("https"));
+    assert_eq!(identity.user(), Some("satoshi"));
+    assert_eq!(identity.host(), Some("bitcoin.org"));
+    assert_eq!(identity.port(), Some("443"));
+    assert_eq!(identity.path(), Some("/login"));
+    assert_eq!(identity.index(), 0);
+    assert_eq!(identity.to_string(), "https://satoshi@bitcoin.org:443/login");
+
+    let built = Identity::new("bitcoin.org")
+        .with_proto("https")
+        .with_user("satoshi")
+        .with_port("443")
+        .with_path("/login");
+    assert_eq!(built, identity);
+
+    for uri in
+        ["bitcoin.org", "ssh://bitcoin.org", "satoshi@bitcoin.org", "[::1]:22", "[::1]", "a/b/c"]
+    {
+        assert_eq!(uri.parse::<Identity>().unwrap().to_string(), uri);
+    }
+    let ipv6 = "ssh://root@[::1]".parse::<Identity>().unwrap();
+    assert_eq!(ipv6.host(), Some("[::1]"));
+    assert_eq!(ipv6.port(), None);
+    let ipv6 = "ssh://root@[::1]:2222".parse::<Identity>().unwrap();
+    assert_eq!(ipv6.host(), Some("[::1]"));
+    assert_eq!(ipv6.port(), Some("2222"));
+
+    // GPG user IDs are set with the builder
+    let gpg = Identity::new("Satoshi Nakamoto <satoshi@bitcoin.org>").with_proto("gpg");
+    assert_eq!(gpg.to_string(), "gpg://Satoshi Nakamoto <satoshi@bitcoin.org>");
+    assert_eq!(gpg.user(), None);
+
+    for (uri, component) in [
+        ("", "host"),
+        ("https://", "host"),
+        ("://bitcoin.org", "proto"),
+        ("@bitcoin.org", "user"),
+        ("bitcoin.org:", "port"),
+        (":22", "host"),
+        ("/login", "host"),
+    ] {
+        let err = uri.parse::<Identity>().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidPath);
+        assert_eq!(err.message(), "invalid identity URI");
+        assert!(err.to_string().contains(component), "{uri}: {err}");
+    }
+}
+
+#[test]
+fn test_openssh_public_keys() {
+    let seed = [0x42; 64];
+    let identity = Identity::new("bitcoin.org").with_proto("ssh").with_user("satoshi");
+    let path = identity.derivation_path();
+
+    let master =
+        ExtendedPrivateKey::<Ed25519Curve<Ed25519DalekBackend>>::new_slip10(&seed).unwrap();
+    let key = master.derive_slip10_path(&path).unwrap().public_key();
+    let ssh = key.to_openssh(Some("satoshi@bitcoin.org"));
+    let parts = ssh.split(' ').collect::<Vec<_>>();
+    assert_eq!(parts[0], "ssh-ed25519");
+    assert_eq!(parts[2], "satoshi@bitcoin.org");
+    let strings = ssh_strings(&base64_decode(parts[1]));
+    assert_eq!(strings, [b"ssh-ed25519".to_vec(), key.to_bytes()[1..].to_vec()]);
+    assert_eq!(key.to_openssh(None), format!("ssh-ed25519 {}", parts[1]));
+
+    let master = ExtendedPrivateKey::<Nist256p1Curve<P256Backend>>::new_slip10(&seed).unwrap();
+    let key = Slip10NonHardenedDerivation::derive_slip10_path(&master, &path.clone().into())
+        .unwrap()
+        .public_key();
+    let ssh = key.to_openssh(None);
+    let parts = ssh.split(' ').collect::<Vec<_>>();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0], "ecdsa-sha2-nistp256");
+    let strings = ssh_strings(&base64_decode(parts[1]));
+    assert_eq!(strings[..2], [b"ecdsa-sha2-nistp256".to_vec(), b"nistp256".to_vec()]);
+    // an uncompressed point with the x coordinate and y parity of the compressed key
+    let point = &strings[2];
+    let compressed = key.to_bytes();
+    assert_eq!(point.len(), 65);
+    assert_eq!(point[0], 0x04);
+    assert_eq!(point[1..33], compressed[1..]);
+    assert_eq!(point[64] & 1, compressed[0] - 2);
+
+    // distinct identities derive distinct keys
+    let other = Identity::new("bitcoin.org").with_proto("ssh").with_user("hal");
+    let other_key =
+        Slip10NonHardenedDerivation::derive_slip10_path(&master, &other.derivation_path().into())
+            .unwrap()
+            .public_key();
+    assert_ne!(other_key.to_openssh(None), ssh);
+}
@@ -0,0 +1,53 @@
+use bip0032::slip21::Slip21Node;
+
+// SLIP-0021 test vectors, seed of the mnemonic "all all all all all all all all all all all all"
+const SEED: &str = "c76c4ac4f4e4a00d6b274d5c39c700bb4a7ddc04fbc6f78e85ca75007b5b495f74a9043eeb77bdd53aa6fc3a0e31462270316fa04b8c19114c8798706cd02ac8";
+
+#[test]
+fn test_slip21_vectors() {
+    let seed = const_hex::decode(SEED).unwrap();
+    let master = Slip21Node::new(&seed);
+    assert_eq!(
+        const_hex::encode(master.key()),
+        "dbf12b44133eaab506a740f6565cc117228cbf1dd70635cfa8ddfdc9af734756"
+    );
+
+    let slip21 = master.derive_child("SLIP-0021");
+    assert_eq!(
+        const_hex::encode(slip21.key()),
+        "1d065e3ac1bbe5c7fad32cf2305f7d709dc070d672044a19e610c77cdf33de0d"
+    );
+
+    let cases = [
+        (
+            "Master encryption key",
+            "ea163130e35bbafdf5ddee97a17b39cef2be4b4f390180d65b54cf05c6a82fde",
+        ),
+        ("Authentication key", "47194e938ab24cc82bfa25f6486ed54bebe79c40ae2a5a32ea6db294d81861a6"),
+    ];
+    for (label, expected) in cases {
+        assert_eq!(const_hex::encode(slip21.derive_child(label).key()), expected);
+        let node = master.derive_path(["SLIP-0021", label]);
+        assert_eq!(const_hex::encode(node.key()), expected);
+    }
+}
+
+#[test]
+fn test_slip21_derive_path() {
+    let master = Slip21Node::new(&[0x42; 32]);
+
+    // the empty path is the node itself
+    assert_eq!(master.derive_path::<&str>([]).key(), master.key());
+
+    // labels are byte strings
+    let by_str = master.derive_path(["a", "b"]);
+    let by_bytes = master.derive_path([b"a".as_slice(), b"b".as_slice()]);
+    assert_eq!(by_str.key(), by_bytes.key());
+    assert_eq!(master.derive_child("a").derive_child("b").key(), by_str.key());
+
+    // labels are not concatenated
+    assert_ne!(master.derive_child("ab").key(), by_str.key());
+    assert_ne!(master.derive_child("").key(), master.key());
+
+    assert_eq!(format!("{master:?}"), "Slip21Node { .. }");
+}
@@ -0,0 +1,118 @@
+use bip0032::{
+    DerivationPath, ExtendedPrivateKey,
+    curve::{secp256k1::*, *},
+    taproot::Parity,
+};
+
+// BIP-0039 seed of "abandon abandon abandon abandon abandon abandon abandon abandon abandon
+// abandon abandon about" without passphrase, used by the BIP-0086 test vectors.
+const SEED: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+
+fn derive<B: Secp256k1Backend>(path: &str) -> ExtendedPrivateKey<Secp256k1Curve<B>> {
+    let seed = const_hex::decode(SEED).unwrap();
+    let master = ExtendedPrivateKey::<Secp256k1Curve<B>>::new(&seed).unwrap();
+    master.derive_path(&path.parse::<DerivationPath>().unwrap()).unwrap()
+}
+
+fn public_key_of<B: Secp256k1Backend>(private_key: &[u8; 32]) -> [u8; 33] {
+    let private_key = <B::PrivateKey as CurvePrivateKey>::from_bytes(private_key)
+        .ok()
+        .expect("valid private key");
+    CurvePublicKey::to_bytes(&private_key.to_public())
+}
+
+fn run_bip86_vectors<B: Secp256k1Backend>()
+where
+    B::PrivateKey: NegatableKey,
+{
+    // https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki
+    let cases = [
+        (
+            "m/86'/0'/0'/0/0",
+            "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115",
+            "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+        ),
+        (
+            "m/86'/0'/0'/0/1",
+            "83dfe85a3151d2517290da461fe2815591ef69f2b18a2ce63f01697a8b313145",
+            "a82f29944d65b86ae6b5e5cc75e294ead6c59391a1edc5e016e3498c67fc7bbb",
+        ),
+        (
+            "m/86'/0'/0'/1/0",
+            "399f1b2f4393f29a18c937859c5dd8a77350103157eb880f02e8c08214277cef",
+            "882d74e5d0572d5a816cef0041a96b6c1de832f6f9676d9605c44d5e9a97d3dc",
+        ),
+    ];
+
+    for (path, internal_key, output_key) in cases {
+        let key = derive::<B>(path);
+        let public = key.public_key();
+
+        let (x_only, parity) = public.x_only_public_key();
+        assert_eq!(const_hex::encode(x_only.to_bytes()), internal_key, "{path}");
+        let prefix = public.to_bytes()[0];
+        assert_eq!(parity == Parity::Odd, prefix == 0x03);
+
+        let (tweaked, tweaked_parity) = public.tap_tweak(None).unwrap();
+        assert_eq!(const_hex::encode(tweaked.as_bytes()), output_key, "{path}");
+
+        // the even-y private key has the x-only key as even public key
+        let even = public_key_of::<B>(&key.to_even_y_bytes());
+        assert_eq!(even[0], 0x02);
+        assert_eq!(even[1..], x_only.to_bytes());
+
+        // the tweaked private key has the output key as public key
+        let tweaked_private = public_key_of::<B>(&key.tap_tweak(None).unwrap());
+        assert_eq!(tweaked_private[1..], tweaked.to_bytes());
+        assert_eq!(tweaked_private[0] == 0x03, tweaked_parity == Parity::Odd);
+    }
+}
+
+#[test]
+fn test_bip86_tweak() {
+    #[cfg(feature = "k256")]
+    run_bip86_vectors::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    run_bip86_vectors::<Secp256k1FfiBackend>();
+}
+
+fn run_script_tree_case<B: Secp256k1Backend>() -> [u8; 32]
+where
+    B::PrivateKey: NegatableKey + Signer<Schnorr, Error = CurveError>,
+    B::PublicKey: Verifier<Schnorr, Error = CurveError>,
+{
+    let merkle_root = [0x5a; 32];
+    let key = derive::<B>("m/86'/1'/0'/0/3");
+    let (key_path_only, _) = key.public_key().tap_tweak(None).unwrap();
+    let (output_key, _) = key.public_key().tap_tweak(Some(&merkle_root)).unwrap();
+    assert_ne!(key_path_only, output_key);
+
+    // a key path spend signs with the tweaked private key for the output key
+    let tweaked = key.tap_tweak(Some(&merkle_root)).unwrap();
+    let signing_key = <B::PrivateKey as CurvePrivateKey>::from_bytes(&tweaked)
+        .ok()
+        .expect("valid private key");
+    let signature = Signer::<Schnorr>::sign(&signing_key, b"sighash").unwrap();
+
+    let mut output_public = [0x02; 33];
+    output_public[1..].copy_from_slice(output_key.as_bytes());
+    let output_public = <B::PublicKey as CurvePublicKey>::from_bytes(&output_public)
+        .ok()
+        .expect("valid public key");
+    Verifier::<Schnorr>::verify(&output_public, b"sighash", &signature).unwrap();
+
+    output_key.to_bytes()
+}
+
+#[test]
+fn test_script_tree_tweak() {
+    #[cfg(feature = "k256")]
+    #[cfg_attr(not(feature = "secp256k1"), allow(unused_variables))]
+    let k256 = run_script_tree_case::<K256Backend>();
+    #[cfg(feature = "secp256k1")]
+    #[cfg_attr(not(feature = "k256"), allow(unused_variables))]
+    let ffi = run_script_tree_case::<Secp256k1FfiBackend>();
+
+    #[cfg(all(feature = "k256", feature = "secp256k1"))]
+    assert_eq!(k256, ffi);
+}
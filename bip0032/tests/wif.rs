@@ -0,0 +1,94 @@
+use bip0032::{
+    DerivationPath, ErrorKind, ExtendedKeyPayload, ExtendedPrivateKey, KnownVersion,
+    address::Network, curve::secp256k1::*, wif::WifPrivateKey,
+};
+
+// BIP-0039 seed of "abandon abandon abandon abandon abandon abandon abandon abandon abandon
+// abandon abandon about" without passphrase, used by the BIP-0084 test vectors.
+const SEED: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+
+// https://en.bitcoin.it/wiki/Wallet_import_format
+const PRIVATE_KEY: &str = "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d";
+
+/// Returns a master key with the given private key and a zero chain code.
+fn master_key_of<B: Secp256k1Backend>(private_key: &str) -> ExtendedPrivateKey<Secp256k1Curve<B>> {
+    let mut data = [0u8; ExtendedKeyPayload::LENGTH];
+    data[..4].copy_from_slice(&KnownVersion::Xprv.into_version().to_bytes());
+    data[46..].copy_from_slice(&const_hex::decode(private_key).unwrap());
+    ExtendedPrivateKey::try_from(ExtendedKeyPayload::from_bytes(&data).unwrap()).unwrap()
+}
+
+fn run_wif_vectors<B: Secp256k1Backend>() {
+    let key = master_key_of::<B>(PRIVATE_KEY);
+    let cases = [
+        (Network::Bitcoin, false, "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ"),
+        (Network::Bitcoin, true, "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617"),
+        (Network::Testnet, false, "91gGn1HgSap6CbU12F6z3pJri26xzp7Ay1VW6NHCoEayNXwRpu2"),
+        (Network::Testnet, true, "cMzLdeGd5vEqxB8B6VFQoRopQ3sLAAvEzDAoQgvX54xwofSWj1fx"),
+        (Network::Regtest, true, "cMzLdeGd5vEqxB8B6VFQoRopQ3sLAAvEzDAoQgvX54xwofSWj1fx"),
+    ];
+
+    for (network, compressed, expected) in cases {
+        let wif = key.to_wif(network, compressed);
+        assert_eq!(wif.as_str(), expected);
+
+        let imported = expected.parse::<WifPrivateKey<B>>().unwrap();
+        assert_eq!(imported.to_bytes(), key.to_bytes());
+        assert_eq!(imported.public_key(), key.public_key().to_bytes());
+        assert_eq!(imported.is_compressed(), compressed);
+        let expected_network = if network == Network::Bitcoin { network } else { Network::Testnet };
+        assert_eq!(imported.network(), expected_network);
+        assert_eq!(imported.to_wif().as_str(), expected);
+    }
+}
+
+fn run_bip84_wif<B: Secp256k1Backend>() {
+    // https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki
+    let seed = const_hex::decode(SEED).unwrap();
+    let master = ExtendedPrivateKey::<Secp256k1Curve<B>>::new(&seed).unwrap();
+    let path = "m/84'/0'/0'/0/0".parse::<DerivationPath>().unwrap();
+    let key = master.derive_path(&path).unwrap();
+    assert_eq!(
+        key.to_wif(Network::Bitcoin, true).as_str(),
+        "KyZpNDKnfs94vbrwhJneDi77V6jF64PWPF8x5cdJb8ifgg2DUc9d"
+    );
+}
+
+fn run_invalid_wif<B: Secp256k1Backend>() {
+    let cases = [
+        // invalid checksum
+        ("KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98618", ErrorKind::InvalidPayload),
+        // compression flag 0x02
+        ("KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvWxyf5d", ErrorKind::InvalidPayload),
+        // version byte 0x81
+        ("5KrPNVvAhnRBNMYRJUq58YMfyUMyVMQrQhhfFtcbT9rK67poC3F", ErrorKind::InvalidVersion),
+        // private key 0 not in 1..n-1
+        ("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73Nd2Mcv1", ErrorKind::InvalidKeyData),
+        // extended private key
+        (
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            ErrorKind::InvalidPayload,
+        ),
+    ];
+
+    for (encoded, kind) in cases {
+        let err = encoded.parse::<WifPrivateKey<B>>().err().unwrap();
+        assert_eq!(err.kind(), kind, "{encoded}");
+    }
+}
+
+#[test]
+fn test_wif() {
+    #[cfg(feature = "k256")]
+    {
+        run_wif_vectors::<K256Backend>();
+        run_bip84_wif::<K256Backend>();
+        run_invalid_wif::<K256Backend>();
+    }
+    #[cfg(feature = "secp256k1")]
+    {
+        run_wif_vectors::<Secp256k1FfiBackend>();
+        run_bip84_wif::<Secp256k1FfiBackend>();
+        run_invalid_wif::<Secp256k1FfiBackend>();
+    }
+}